@@ -0,0 +1,6 @@
+//! Music-theoretical analysis of notation.
+
+pub mod pitch_class;
+pub mod roman;
+pub mod serial;
+pub mod voice_leading;
@@ -0,0 +1,545 @@
+//! Pitch classes and pitch-class sets, with their normal forms, prime forms,
+//! Forte numbers and interval vectors.
+
+use std::convert::TryFrom;
+
+use crate::midi::midi_note::MidiNote;
+use crate::notation::pitch::Pitch;
+
+/// A pitch class: one of the twelve pitches of twelve-tone equal temperament
+/// regardless of octave and spelling, numbered from C = 0 to B = 11.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, Default)]
+pub struct PitchClass(u8);
+
+impl PitchClass {
+    /// Attempt to construct the pitch class with a number from 0 to 11.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the number is 12 or more.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::pitch_class::PitchClass;
+    ///
+    /// assert_eq!(PitchClass::new(4).unwrap().number(), 4);
+    /// assert_eq!(PitchClass::new(12), Err(String::from("Invalid pitch class 12.")));
+    /// ```
+    pub fn new(number: u8) -> Result<PitchClass, String> {
+        if number < 12 {
+            Ok(PitchClass(number))
+        } else {
+            Err(format!("Invalid pitch class {}.", number))
+        }
+    }
+    /// Return the pitch class's number from 0 to 11.
+    pub fn number(&self) -> u8 {
+        self.0
+    }
+    /// Return the pitch class a number of semitones higher, which may be
+    /// negative.
+    pub fn transpose(&self, semitones: i32) -> PitchClass {
+        PitchClass((i32::from(self.0) + semitones).rem_euclid(12) as u8)
+    }
+    /// Return the inversion of the pitch class around C, i.e. `12 - n`.
+    pub fn invert(&self) -> PitchClass {
+        PitchClass((12 - self.0) % 12)
+    }
+    /// Return the pitch of the pitch class in octave 4, from middle C up,
+    /// spelled like a [`MidiNote`]'s pitch: with sharps for C♯ and F♯ and
+    /// flats for the other black keys.
+    pub fn pitch(&self) -> Pitch {
+        Pitch::try_from(&MidiNote::new(60 + i16::from(self.0)).expect("valid MIDI note"))
+            .expect("pitch in octave 4")
+    }
+}
+
+impl From<&MidiNote> for PitchClass {
+    fn from(note: &MidiNote) -> Self {
+        PitchClass(note.get_note().rem_euclid(12) as u8)
+    }
+}
+
+impl TryFrom<&Pitch> for PitchClass {
+    type Error = String;
+
+    /// Attempt to find the pitch class of a pitch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` for rests and for pitches with microtonal
+    /// accidentals, which are between pitch classes.
+    fn try_from(pitch: &Pitch) -> Result<Self, Self::Error> {
+        let semitones = pitch.semitones()?;
+        if !semitones.is_integer() {
+            return Err(String::from("Microtonal pitches have no pitch class."));
+        }
+        Ok(PitchClass(semitones.to_integer().rem_euclid(12) as u8))
+    }
+}
+
+impl std::fmt::Display for PitchClass {
+    /// Write the pitch class's number, with `t` and `e` for 10 and 11.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            10 => write!(f, "t"),
+            11 => write!(f, "e"),
+            n => write!(f, "{}", n),
+        }
+    }
+}
+
+impl std::str::FromStr for PitchClass {
+    type Err = String;
+
+    /// Parse a pitch class's number from 0 to 11, also accepting `t`/`a` for
+    /// 10 and `e`/`b` for 11 in either case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "t" | "a" => Ok(PitchClass(10)),
+            "e" | "b" => Ok(PitchClass(11)),
+            n => PitchClass::new(
+                n.parse()
+                    .map_err(|_| format!("Invalid pitch class '{}'.", s))?,
+            ),
+        }
+    }
+}
+
+/// The prime forms of the trichords to hexachords, in the order of their
+/// Forte numbers. The prime forms are Rahn's, which differ from Forte's for
+/// 5-20, 6-Z29 and 6-31 (and their complements).
+static PRIME_FORMS: [&[&str]; 4] = [
+    &[
+        "012", "013", "014", "015", "016", "024", "025", "026", "027", "036", "037", "048",
+    ],
+    &[
+        "0123", "0124", "0134", "0125", "0126", "0127", "0145", "0156", "0167", "0235", "0135",
+        "0236", "0136", "0237", "0146", "0157", "0347", "0147", "0148", "0158", "0246", "0247",
+        "0257", "0248", "0268", "0358", "0258", "0369", "0137",
+    ],
+    &[
+        "01234", "01235", "01245", "01236", "01237", "01256", "01267", "02346", "01246", "01346",
+        "02347", "01356", "01248", "01257", "01268", "01347", "01348", "01457", "01367", "01568",
+        "01458", "01478", "02357", "01357", "02358", "02458", "01358", "02368", "01368", "01468",
+        "01369", "01469", "02468", "02469", "02479", "01247", "03458", "01258",
+    ],
+    &[
+        "012345", "012346", "012356", "012456", "012367", "012567", "012678", "023457", "012357",
+        "013457", "012457", "012467", "013467", "013458", "012458", "014568", "012478", "012578",
+        "013478", "014589", "023468", "012468", "023568", "013468", "013568", "013578", "013469",
+        "013569", "023679", "013679", "014579", "024579", "023579", "013579", "02468t", "012347",
+        "012348", "012378", "023458", "012358", "012368", "012369", "012568", "012569", "023469",
+        "012469", "012479", "012579", "013479", "014679",
+    ],
+];
+
+/// The Forte numbers of the trichords to hexachords with a Z-related set
+/// class, which has the same interval vector.
+static Z_RELATED: [&[u8]; 4] = [
+    &[],
+    &[15, 29],
+    &[12, 17, 18, 36, 37, 38],
+    &[
+        3, 4, 6, 10, 11, 12, 13, 17, 19, 23, 24, 25, 26, 28, 29, 36, 37, 38, 39, 40, 41, 42, 43,
+        44, 45, 46, 47, 48, 49, 50,
+    ],
+];
+
+/// A set of pitch classes, as used in atonal set theory.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Default)]
+pub struct PitchClassSet {
+    /// The pitch classes in ascending order, without duplicates.
+    pitch_classes: Vec<PitchClass>,
+}
+
+impl PitchClassSet {
+    /// Construct the empty set.
+    pub fn new() -> PitchClassSet {
+        Default::default()
+    }
+    /// Attempt to construct the set of the pitch classes of some pitches.
+    /// Rests are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a pitch is microtonal.
+    pub fn from_pitches(pitches: &[Pitch]) -> Result<PitchClassSet, String> {
+        let mut set = PitchClassSet::new();
+        for pitch in pitches.iter().filter(|pitch| pitch.semitones().is_ok()) {
+            set.insert(PitchClass::try_from(pitch)?);
+        }
+        Ok(set)
+    }
+    /// Attempt to construct the set class with a Forte number, e.g. `4-Z15`,
+    /// returning its prime form. The `Z` is optional. Numbers for sets of
+    /// 0--2 and 10--12 pitch classes are accepted too: `0-1`, `1-1`, `2-1` to
+    /// `2-6` (by interval class), `10-1` to `10-6`, `11-1` and `12-1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if there is no set class with the number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::pitch_class::PitchClassSet;
+    ///
+    /// let set = PitchClassSet::from_forte_number("3-11").unwrap();
+    /// assert_eq!(set.to_string(), "[0,3,7]");
+    /// assert_eq!(PitchClassSet::from_forte_number("7-Z36").unwrap().to_string(), "[0,1,2,3,5,6,8]");
+    /// assert!(PitchClassSet::from_forte_number("3-13").is_err());
+    /// ```
+    pub fn from_forte_number(number: &str) -> Result<PitchClassSet, String> {
+        let error = || format!("Invalid Forte number '{}'.", number);
+        let (cardinality, ordinal) = number.split_once('-').ok_or_else(error)?;
+        let cardinality: usize = cardinality.parse().map_err(|_| error())?;
+        let ordinal: usize = ordinal
+            .trim_start_matches('Z')
+            .parse()
+            .map_err(|_| error())?;
+        let set = match (cardinality, ordinal) {
+            (0, 1) => PitchClassSet::new(),
+            (1, 1) => numbers(&[0]),
+            (2, 1..=6) => numbers(&[0, ordinal as u8]),
+            (3..=6, _) => PRIME_FORMS[cardinality - 3]
+                .get(ordinal.wrapping_sub(1))
+                .ok_or_else(error)?
+                .parse()?,
+            (7..=10, _) => {
+                PitchClassSet::from_forte_number(&format!("{}-{}", 12 - cardinality, ordinal))
+                    .map_err(|_| error())?
+                    .complement()
+                    .prime_form()
+            }
+            (11, 1) => numbers(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
+            (12, 1) => numbers(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]),
+            _ => return Err(error()),
+        };
+        Ok(set)
+    }
+    /// Add a pitch class to the set.
+    pub fn insert(&mut self, pitch_class: PitchClass) {
+        if let Err(index) = self.pitch_classes.binary_search(&pitch_class) {
+            self.pitch_classes.insert(index, pitch_class);
+        }
+    }
+    /// Return the pitch classes of the set in ascending order.
+    pub fn pitch_classes(&self) -> &[PitchClass] {
+        &self.pitch_classes
+    }
+    /// Return whether the set contains a pitch class.
+    pub fn contains(&self, pitch_class: PitchClass) -> bool {
+        self.pitch_classes.binary_search(&pitch_class).is_ok()
+    }
+    /// Return the number of pitch classes in the set.
+    pub fn len(&self) -> usize {
+        self.pitch_classes.len()
+    }
+    /// Return whether the set has no pitch classes.
+    pub fn is_empty(&self) -> bool {
+        self.pitch_classes.is_empty()
+    }
+    /// Return the set transposed by a number of semitones, T<sub>n</sub>.
+    pub fn transpose(&self, semitones: i32) -> PitchClassSet {
+        self.pitch_classes
+            .iter()
+            .map(|pitch_class| pitch_class.transpose(semitones))
+            .collect()
+    }
+    /// Return the set inverted around C, T<sub>0</sub>I.
+    pub fn invert(&self) -> PitchClassSet {
+        self.pitch_classes.iter().map(PitchClass::invert).collect()
+    }
+    /// Return the pitch classes that are not in the set.
+    pub fn complement(&self) -> PitchClassSet {
+        (0..12)
+            .map(PitchClass)
+            .filter(|pitch_class| !self.contains(*pitch_class))
+            .collect()
+    }
+    /// Return the set's normal form: the ordering of its pitch classes that
+    /// spans the smallest interval, with ties broken by Rahn's method of
+    /// comparing the intervals from the first pitch class to the
+    /// second-to-last, then to the third-to-last, and so on, and finally by
+    /// starting on the lowest pitch class.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::pitch_class::PitchClassSet;
+    ///
+    /// let set: PitchClassSet = "[9,0,4,7]".parse().unwrap();
+    /// let normal: Vec<String> = set.normal_form().iter().map(|pc| pc.to_string()).collect();
+    ///
+    /// assert_eq!(normal, vec!["4", "7", "9", "0"]);
+    /// ```
+    pub fn normal_form(&self) -> Vec<PitchClass> {
+        let mut best: Option<(Vec<u8>, Vec<PitchClass>)> = None;
+        for start in 0..self.len() {
+            let rotation: Vec<PitchClass> = self.pitch_classes[start..]
+                .iter()
+                .chain(&self.pitch_classes[..start])
+                .cloned()
+                .collect();
+            let first = rotation[0].number();
+            let key: Vec<u8> = rotation
+                .iter()
+                .rev()
+                .map(|pitch_class| (pitch_class.number() + 12 - first) % 12)
+                .chain(std::iter::once(first))
+                .collect();
+            let better = match &best {
+                Some((best_key, _)) => key < *best_key,
+                None => true,
+            };
+            if better {
+                best = Some((key, rotation));
+            }
+        }
+        best.map(|(_, rotation)| rotation).unwrap_or_default()
+    }
+    /// Return the set's prime form (after Rahn): whichever of the normal
+    /// forms of the set and its inversion is more packed to the left, when
+    /// both are transposed to start on 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::pitch_class::PitchClassSet;
+    ///
+    /// let major: PitchClassSet = "[0,4,7]".parse().unwrap();
+    /// assert_eq!(major.prime_form().to_string(), "[0,3,7]");
+    /// ```
+    pub fn prime_form(&self) -> PitchClassSet {
+        let zeroed = |set: &PitchClassSet| -> Vec<PitchClass> {
+            let normal = set.normal_form();
+            let first = normal.first().map_or(0, |pitch_class| pitch_class.number());
+            normal
+                .iter()
+                .map(|pitch_class| pitch_class.transpose(-i32::from(first)))
+                .collect()
+        };
+        let (prime, inverted) = (zeroed(self), zeroed(&self.invert()));
+        if inverted.iter().rev().lt(prime.iter().rev()) {
+            inverted.into_iter().collect()
+        } else {
+            prime.into_iter().collect()
+        }
+    }
+    /// Return the set's Forte number, e.g. `3-11` or `4-Z15`, also numbering
+    /// the sets of 0--2 and 10--12 pitch classes as in
+    /// [`from_forte_number`][PitchClassSet::from_forte_number].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::pitch_class::PitchClassSet;
+    ///
+    /// let all_interval: PitchClassSet = "[0,1,4,6]".parse().unwrap();
+    /// assert_eq!(all_interval.forte_number(), "4-Z15");
+    ///
+    /// let diatonic: PitchClassSet = "[0,2,4,5,7,9,11]".parse().unwrap();
+    /// assert_eq!(diatonic.forte_number(), "7-35");
+    /// ```
+    pub fn forte_number(&self) -> String {
+        let cardinality = self.len();
+        let (table, prime) = match cardinality {
+            0 | 1 | 11 | 12 => return format!("{}-1", cardinality),
+            2 => {
+                let class = self.interval_vector().iter().position(|&count| count == 1);
+                return format!("2-{}", class.unwrap_or(0) + 1);
+            }
+            10 => return self.complement().forte_number().replacen('2', "10", 1),
+            3..=6 => (cardinality - 3, self.prime_form()),
+            _ => (9 - cardinality, self.complement().prime_form()),
+        };
+        let prime = prime
+            .pitch_classes
+            .iter()
+            .map(|pc| pc.to_string())
+            .collect::<String>();
+        let ordinal = PRIME_FORMS[table]
+            .iter()
+            .position(|form| *form == prime)
+            .expect("every prime form is in the table")
+            + 1;
+        let z = if Z_RELATED[table].contains(&(ordinal as u8)) {
+            "Z"
+        } else {
+            ""
+        };
+        format!("{}-{}{}", cardinality, z, ordinal)
+    }
+    /// Return the set's interval vector: the number of pairs of its pitch
+    /// classes an interval class of 1 to 6 semitones apart.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::pitch_class::PitchClassSet;
+    ///
+    /// let diatonic: PitchClassSet = "[0,2,4,5,7,9,11]".parse().unwrap();
+    /// assert_eq!(diatonic.interval_vector(), [2, 5, 4, 3, 6, 1]);
+    /// ```
+    pub fn interval_vector(&self) -> [usize; 6] {
+        let mut vector = [0; 6];
+        for (index, a) in self.pitch_classes.iter().enumerate() {
+            for b in &self.pitch_classes[index + 1..] {
+                let interval = b.number() - a.number();
+                vector[usize::from(interval.min(12 - interval)) - 1] += 1;
+            }
+        }
+        vector
+    }
+}
+
+/// Construct a set of pitch classes that are known to be valid.
+fn numbers(numbers: &[u8]) -> PitchClassSet {
+    numbers.iter().map(|&number| PitchClass(number)).collect()
+}
+
+impl std::iter::FromIterator<PitchClass> for PitchClassSet {
+    fn from_iter<I: IntoIterator<Item = PitchClass>>(iter: I) -> Self {
+        let mut set = PitchClassSet::new();
+        for pitch_class in iter {
+            set.insert(pitch_class);
+        }
+        set
+    }
+}
+
+impl std::fmt::Display for PitchClassSet {
+    /// Write the pitch classes in ascending order, e.g. `[0,4,7,t]`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pitch_classes: Vec<String> =
+            self.pitch_classes.iter().map(|pc| pc.to_string()).collect();
+        write!(f, "[{}]", pitch_classes.join(","))
+    }
+}
+
+impl std::str::FromStr for PitchClassSet {
+    type Err = String;
+
+    /// Parse a set of pitch classes, optionally in brackets or braces, and
+    /// either separated by commas or spaces, e.g. `[0,4,7]`, `{0 4 7}`, or
+    /// written together, e.g. `047t`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .trim_start_matches(['[', '{', '('])
+            .trim_end_matches([']', '}', ')']);
+        if inner.contains([',', ' ']) {
+            inner
+                .split([',', ' '])
+                .filter(|pitch_class| !pitch_class.is_empty())
+                .map(str::parse)
+                .collect()
+        } else {
+            inner.chars().map(|c| c.to_string().parse()).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::pitch_class::*;
+    use crate::notation::pitch::{Accidental, NoteName, Octave};
+    fn set(s: &str) -> PitchClassSet {
+        s.parse().unwrap()
+    }
+    #[test]
+    fn test_pitch_class() {
+        let mut pitch = Pitch::new(NoteName::B);
+        pitch.accidental(Accidental::Sharp);
+        assert_eq!(PitchClass::try_from(&pitch), Ok(PitchClass(0)));
+        pitch.accidental(Accidental::QuarterSharp);
+        assert!(PitchClass::try_from(&pitch).is_err());
+        assert!(PitchClass::try_from(&Pitch::new(NoteName::None)).is_err());
+        assert_eq!(
+            PitchClass::from(&MidiNote::new(70).unwrap()),
+            PitchClass(10)
+        );
+        let pitch = PitchClass(3).pitch();
+        assert_eq!(pitch.note_name, NoteName::E);
+        assert_eq!(pitch.accidental, Accidental::Flat);
+        assert_eq!(pitch.octave, Octave::S4);
+        assert_eq!("T".parse(), Ok(PitchClass(10)));
+        assert!("x".parse::<PitchClass>().is_err());
+    }
+    #[test]
+    fn test_parse_display() {
+        assert_eq!(set("{e, 0, 4}").to_string(), "[0,4,e]");
+        assert_eq!(set("0t4"), set("[0,4,10]"));
+        assert_eq!(set("[]"), PitchClassSet::new());
+    }
+    #[test]
+    fn test_normal_form() {
+        // ties on the outer interval are broken from the right, where Forte
+        // would choose [0,1,3,7,8]
+        let normal: Vec<u8> = set("[0,1,3,7,8]")
+            .normal_form()
+            .iter()
+            .map(PitchClass::number)
+            .collect();
+        assert_eq!(normal, vec![7, 8, 0, 1, 3]);
+        // ties that remain start on the lowest pitch class
+        let normal: Vec<u8> = set("[0,3,6,9]")
+            .normal_form()
+            .iter()
+            .map(PitchClass::number)
+            .collect();
+        assert_eq!(normal, vec![0, 3, 6, 9]);
+    }
+    #[test]
+    fn test_prime_form() {
+        assert_eq!(set("[4,7,11]").prime_form(), set("037"));
+        assert_eq!(set("[1,2,4,8,9]").forte_number(), "5-20");
+        // Rahn's prime form, rather than Forte's [0,1,3,7,8]
+        assert_eq!(set("[1,2,4,8,9]").prime_form(), set("01568"));
+    }
+    #[test]
+    fn test_forte_table() {
+        // every set class has a Forte number that leads back to it
+        let mut classes = std::collections::HashSet::new();
+        for bits in 0..4096u16 {
+            let set: PitchClassSet = (0..12)
+                .filter(|n| bits & (1 << n) != 0)
+                .map(PitchClass)
+                .collect();
+            let prime = set.prime_form();
+            let number = set.forte_number();
+            assert_eq!(PitchClassSet::from_forte_number(&number), Ok(prime.clone()));
+            classes.insert(prime);
+        }
+        assert_eq!(classes.len(), 224);
+        for table in &PRIME_FORMS {
+            for form in table.iter() {
+                assert_eq!(set(form).prime_form(), set(form));
+            }
+        }
+    }
+    #[test]
+    fn test_z_relation() {
+        for (a, b) in [
+            ("4-Z15", "4-Z29"),
+            ("5-Z12", "5-Z36"),
+            ("6-Z3", "6-Z36"),
+            ("6-Z29", "6-Z50"),
+        ] {
+            let a = PitchClassSet::from_forte_number(a).unwrap();
+            let b = PitchClassSet::from_forte_number(b).unwrap();
+            assert_eq!(a.interval_vector(), b.interval_vector());
+            assert_ne!(a, b);
+        }
+        assert_eq!(
+            PitchClassSet::from_forte_number("8-Z15")
+                .unwrap()
+                .forte_number(),
+            "8-Z15"
+        );
+        assert_eq!(PitchClassSet::from_forte_number("2-5").unwrap(), set("05"));
+        assert_eq!(set("[0,2,3,4,5,6,7,8,9,10]").forte_number(), "10-2");
+    }
+}
@@ -0,0 +1,421 @@
+//! Roman-numeral analysis of chords in a key.
+
+use crate::notation::chord::{Chord, ChordQuality};
+use crate::notation::interval::Interval;
+use crate::notation::key::{Mode, TonalKey};
+use crate::notation::pitch::Pitch;
+use crate::notation::rhythm::Rhythm;
+
+/// Roman numerals for the scale degrees 1--7.
+static NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+/// The Roman-numeral analysis of a chord in a key, e.g. `V65/V`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RomanNumeral {
+    /// The scale degree of the root, from 1 to 7, in the key or, for
+    /// secondary chords, in the tonicized key.
+    pub degree: u8,
+    /// The semitones the root is raised (positive) or lowered (negative)
+    /// from the scale degree, e.g. -1 for `♭VI`.
+    pub alteration: i32,
+    pub quality: ChordQuality,
+    /// 0 for root position, 1 for first inversion, and so on.
+    pub inversion: u8,
+    /// The chord a secondary dominant or leading-tone chord leads to, e.g.
+    /// `V` for `V7/V`.
+    pub tonicized: Option<Box<RomanNumeral>>,
+    /// Whether the chord is borrowed from the parallel key.
+    pub borrowed: bool,
+}
+
+impl RomanNumeral {
+    /// Attempt to analyse a chord in a key.
+    ///
+    /// Chords outside of the key are analysed, in order of preference, as
+    /// chords borrowed from the parallel key, as secondary dominants or
+    /// leading-tone chords of a major or minor triad of the key, or as
+    /// chromatic chords on an altered scale degree. In minor keys, chords may use
+    /// either the lowered or the raised seventh degree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the chord is not a triad or seventh chord
+    /// (see [`Chord::root`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::roman::RomanNumeral;
+    /// use lilypond::notation::chord::Chord;
+    /// use lilypond::notation::key::{Mode, TonalKey};
+    /// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+    ///
+    /// let key = TonalKey::new(&Pitch::new(NoteName::C), Mode::Major).unwrap();
+    /// let mut f_sharp = Pitch::new(NoteName::F);
+    /// f_sharp.sharpen();
+    /// let mut c = Pitch::new(NoteName::C);
+    /// c.octave(Octave::S4);
+    /// let chord = Chord::new(vec![f_sharp, Pitch::new(NoteName::A), c, Pitch::new(NoteName::D)]);
+    ///
+    /// let numeral = RomanNumeral::analyze(&chord, &key).unwrap();
+    /// assert_eq!(numeral.to_string(), "V7/V");
+    /// ```
+    pub fn analyze(chord: &Chord, key: &TonalKey) -> Result<RomanNumeral, String> {
+        let root = chord.root()?;
+        let quality = chord.quality()?;
+        let (degree, alteration) = degree(root, key)?;
+        let mut numeral = RomanNumeral {
+            degree: degree + 1,
+            alteration,
+            quality,
+            inversion: chord.inversion()?,
+            tonicized: None,
+            borrowed: false,
+        };
+        if is_diatonic(chord, key)? {
+            return Ok(numeral);
+        }
+        if is_diatonic(chord, &key.parallel())? {
+            numeral.borrowed = true;
+        } else if let Some((degree, target)) = tonicized(root, quality, key)? {
+            numeral.degree = degree;
+            numeral.alteration = 0;
+            numeral.tonicized = Some(Box::new(target));
+        }
+        Ok(numeral)
+    }
+    /// Return the figures of the chord's inversion as LilyPond figured bass,
+    /// e.g. `6 4` for a second-inversion triad, or `_` for a root-position
+    /// triad.
+    pub fn figures(&self) -> &'static str {
+        let figures = if self.quality.is_seventh() {
+            ["7", "6 5", "4 3", "4 2"]
+        } else {
+            ["_", "6", "6 4", "_"]
+        };
+        figures[usize::from(self.inversion.min(3))]
+    }
+}
+
+impl std::fmt::Display for RomanNumeral {
+    /// Write the numeral, e.g. `♭VI`, `viiø7` or `V43/IV`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let accidental = if self.alteration < 0 { "♭" } else { "♯" };
+        write!(
+            f,
+            "{}",
+            accidental.repeat(self.alteration.unsigned_abs() as usize)
+        )?;
+        let numeral = NUMERALS[usize::from(self.degree.clamp(1, 7) - 1)];
+        match self.quality {
+            ChordQuality::Major
+            | ChordQuality::Augmented
+            | ChordQuality::DominantSeventh
+            | ChordQuality::MajorSeventh => write!(f, "{}", numeral)?,
+            _ => write!(f, "{}", numeral.to_lowercase())?,
+        }
+        write!(
+            f,
+            "{}",
+            match self.quality {
+                ChordQuality::Diminished | ChordQuality::DiminishedSeventh => "°",
+                ChordQuality::HalfDiminishedSeventh => "ø",
+                ChordQuality::Augmented => "+",
+                ChordQuality::MajorSeventh | ChordQuality::MinorMajorSeventh => "M",
+                _ => "",
+            }
+        )?;
+        let figures = if self.quality.is_seventh() {
+            ["7", "65", "43", "42"]
+        } else {
+            ["", "6", "64", ""]
+        };
+        write!(f, "{}", figures[usize::from(self.inversion.min(3))])?;
+        if let Some(tonicized) = &self.tonicized {
+            write!(f, "/{}", tonicized)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the scale degree (0--6) of a pitch in a key, and how many semitones
+/// it is altered from the key's scale. In minor keys, the raised seventh
+/// degree counts as unaltered.
+fn degree(pitch: &Pitch, key: &TonalKey) -> Result<(u8, i32), String> {
+    let steps = (pitch.diatonic_number()? - key.tonic().diatonic_number()?).rem_euclid(7);
+    let difference = pitch.semitones()? - key.scale().pitch(steps)?.semitones()?;
+    if !difference.is_integer() {
+        return Err(format!(
+            "Pitch is {} semitones from the scale of the key.",
+            difference
+        ));
+    }
+    let alteration = (difference.to_integer() + 6).rem_euclid(12) - 6;
+    let leading_tone = key.mode() == Mode::Minor && steps == 6 && alteration == 1;
+    Ok((steps as u8, if leading_tone { 0 } else { alteration }))
+}
+
+/// Check whether all pitches of a chord belong to a key.
+fn is_diatonic(chord: &Chord, key: &TonalKey) -> Result<bool, String> {
+    for pitch in &chord.pitches {
+        if degree(pitch, key)?.1 != 0 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Find the degree (5 or 7) and target triad of a chord that is a secondary
+/// dominant or leading-tone chord in a key.
+fn tonicized(
+    root: &Pitch,
+    quality: ChordQuality,
+    key: &TonalKey,
+) -> Result<Option<(u8, RomanNumeral)>, String> {
+    let (numeral_degree, interval) = match quality {
+        ChordQuality::Major | ChordQuality::DominantSeventh => (5, "P4"),
+        ChordQuality::Diminished
+        | ChordQuality::HalfDiminishedSeventh
+        | ChordQuality::DiminishedSeventh => (7, "m2"),
+        _ => return Ok(None),
+    };
+    let interval: Interval = interval.parse()?;
+    let target = (root + &interval)?;
+    let (target_degree, alteration) = match degree(&target, key) {
+        Ok(found) => found,
+        Err(_) => return Ok(None),
+    };
+    if alteration != 0 || target_degree == 0 {
+        return Ok(None);
+    }
+    let triad = diatonic_triad(target_degree, key)?;
+    match triad.quality()? {
+        ChordQuality::Major | ChordQuality::Minor => {
+            Ok(Some((numeral_degree, RomanNumeral::analyze(&triad, key)?)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Build the root-position triad on a scale degree (0--6) of a key. In minor
+/// keys, the dominant and leading-tone triads use the raised seventh.
+fn diatonic_triad(degree: u8, key: &TonalKey) -> Result<Chord, String> {
+    let scale = key.scale();
+    let mut pitches = vec![];
+    for step in [0, 2, 4] {
+        let index = i32::from(degree) + step;
+        let mut pitch = scale.pitch(index)?;
+        if key.mode() == Mode::Minor && index.rem_euclid(7) == 6 && (degree == 4 || degree == 6) {
+            pitch = (&pitch + &"A1".parse::<Interval>()?)?;
+        }
+        pitches.push(pitch);
+    }
+    Ok(Chord::new(pitches))
+}
+
+/// Write a LilyPond duration for a rhythm, e.g. `4.`.
+fn duration(rhythm: &Rhythm) -> String {
+    format!("{}{}", rhythm.length, rhythm.dots)
+}
+
+/// Attempt to write the Roman numerals of a chord progression as LilyPond
+/// lyrics, for a `\new Lyrics` context under the staff.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if a chord cannot be analysed (see
+/// [`RomanNumeral::analyze`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::analysis::roman::lyricmode;
+/// use lilypond::notation::chord::Chord;
+/// use lilypond::notation::key::{Mode, TonalKey};
+/// use lilypond::notation::pitch::{Pitch, NoteName};
+/// use lilypond::notation::rhythm::Length;
+///
+/// let key = TonalKey::new(&Pitch::new(NoteName::G), Mode::Major).unwrap();
+/// let mut chord = Chord::new(vec![
+///     Pitch::new(NoteName::C),
+///     Pitch::new(NoteName::E),
+///     Pitch::new(NoteName::G),
+/// ]);
+/// chord.rhythm.length(Length::Half);
+///
+/// assert_eq!(lyricmode(&[chord], &key).unwrap(), "\\lyricmode { \"IV\"2 }");
+/// ```
+pub fn lyricmode(chords: &[Chord], key: &TonalKey) -> Result<String, String> {
+    let mut lyrics = vec![];
+    for chord in chords {
+        let numeral = RomanNumeral::analyze(chord, key)?;
+        lyrics.push(format!("\"{}\"{}", numeral, duration(&chord.rhythm)));
+    }
+    Ok(format!("\\lyricmode {{ {} }}", lyrics.join(" ")))
+}
+
+/// Attempt to write the inversions of a chord progression as LilyPond
+/// figured bass, for a `\new FiguredBass` context under the staff.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if a chord cannot be analysed (see
+/// [`RomanNumeral::analyze`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::analysis::roman::figuremode;
+/// use lilypond::notation::chord::Chord;
+/// use lilypond::notation::key::{Mode, TonalKey};
+/// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+///
+/// let key = TonalKey::new(&Pitch::new(NoteName::C), Mode::Major).unwrap();
+/// let mut c = Pitch::new(NoteName::C);
+/// c.octave(Octave::S4);
+/// let chord = Chord::new(vec![Pitch::new(NoteName::G), c, Pitch::new(NoteName::E)]);
+///
+/// assert_eq!(figuremode(&[chord], &key).unwrap(), "\\figuremode { <6>4 }");
+/// ```
+pub fn figuremode(chords: &[Chord], key: &TonalKey) -> Result<String, String> {
+    let mut figures = vec![];
+    for chord in chords {
+        let numeral = RomanNumeral::analyze(chord, key)?;
+        figures.push(format!(
+            "<{}>{}",
+            numeral.figures(),
+            duration(&chord.rhythm)
+        ));
+    }
+    Ok(format!("\\figuremode {{ {} }}", figures.join(" ")))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::roman::*;
+    use crate::notation::pitch::{Accidental, NoteName, Octave};
+    use crate::parser::parse_notes;
+    use crate::NoteNameLanguage;
+    /// Make a chord from English LilyPond notes.
+    fn chord(notes: &str) -> Chord {
+        let notes = parse_notes(notes, NoteNameLanguage::English).unwrap();
+        Chord::new(notes.into_iter().map(|note| note.pitch).collect())
+    }
+    fn key(note_name: NoteName, accidental: Accidental, mode: Mode) -> TonalKey {
+        let mut tonic = Pitch::new(note_name);
+        tonic.accidental(accidental);
+        TonalKey::new(&tonic, mode).unwrap()
+    }
+    fn numeral(notes: &str, key: &TonalKey) -> String {
+        RomanNumeral::analyze(&chord(notes), key)
+            .unwrap()
+            .to_string()
+    }
+    #[test]
+    fn test_major() {
+        let c = key(NoteName::C, Accidental::None, Mode::Major);
+        let cases = [
+            ("c e g", "I"),
+            ("f a c'", "IV"),
+            ("b, d f", "vii°"),
+            ("e g c'", "I6"),
+            ("g c' e'", "I64"),
+            ("g, b, d f", "V7"),
+            ("b, d f g", "V65"),
+            ("d f g b", "V43"),
+            ("f g b d'", "V42"),
+            ("d f a c'", "ii7"),
+            ("b, d f a", "viiø7"),
+            ("c e g b", "IM7"),
+        ];
+        for (notes, expected) in cases {
+            assert_eq!(numeral(notes, &c), expected, "{}", notes);
+        }
+    }
+    #[test]
+    fn test_minor() {
+        let a = key(NoteName::A, Accidental::None, Mode::Minor);
+        let cases = [
+            ("a c' e'", "i"),
+            ("e gs b", "V"),
+            ("e g b", "v"),
+            ("gs b d' f'", "vii°7"),
+            ("g b d'", "VII"),
+            ("c e gs", "III+"),
+            ("b d' f'", "ii°"),
+            ("d f a", "iv"),
+        ];
+        for (notes, expected) in cases {
+            assert_eq!(numeral(notes, &a), expected, "{}", notes);
+        }
+    }
+    #[test]
+    fn test_secondary() {
+        let c = key(NoteName::C, Accidental::None, Mode::Major);
+        let cases = [
+            ("d fs a", "V/V"),
+            ("fs a c' d'", "V65/V"),
+            ("a cs' e' g'", "V7/ii"),
+            ("c e g bf", "V7/IV"),
+            ("fs a c' ef'", "vii°7/V"),
+            ("cs e g", "vii°/ii"),
+        ];
+        for (notes, expected) in cases {
+            assert_eq!(numeral(notes, &c), expected, "{}", notes);
+        }
+        let e_flat = key(NoteName::E, Accidental::Flat, Mode::Major);
+        assert_eq!(numeral("f a c' ef'", &e_flat), "V7/V");
+    }
+    #[test]
+    fn test_borrowed() {
+        let c = key(NoteName::C, Accidental::None, Mode::Major);
+        let cases = [
+            ("af c' ef'", "♭VI", true),
+            ("f af c'", "iv", true),
+            ("d f af", "ii°", true),
+            ("bf d' f'", "♭VII", true),
+            ("df f af", "♭II", false),
+            ("fs as cs'", "♯IV", false),
+        ];
+        for (notes, expected, borrowed) in cases {
+            let numeral = RomanNumeral::analyze(&chord(notes), &c).unwrap();
+            assert_eq!(numeral.to_string(), expected, "{}", notes);
+            assert_eq!(numeral.borrowed, borrowed, "{}", notes);
+        }
+        let a = key(NoteName::A, Accidental::None, Mode::Minor);
+        let picardy = RomanNumeral::analyze(&chord("a cs' e'"), &a).unwrap();
+        assert_eq!(picardy.to_string(), "I");
+        assert!(picardy.borrowed);
+    }
+    #[test]
+    fn test_errors() {
+        let c = key(NoteName::C, Accidental::None, Mode::Major);
+        assert!(RomanNumeral::analyze(&chord("c d e"), &c).is_err());
+        let mut quarter = chord("c e g");
+        quarter.pitches[0].accidental(Accidental::QuarterSharp);
+        assert!(RomanNumeral::analyze(&quarter, &c).is_err());
+        let low = Pitch {
+            note_name: NoteName::C,
+            accidental: Accidental::None,
+            octave: Octave::S0,
+        };
+        assert!(RomanNumeral::analyze(&Chord::new(vec![low]), &c).is_err());
+    }
+    #[test]
+    fn test_lilypond() {
+        let g = key(NoteName::G, Accidental::None, Mode::Major);
+        let mut chords = vec![chord("g b d'"), chord("c e a"), chord("d fs a c'")];
+        chords[2]
+            .rhythm
+            .length(crate::notation::rhythm::Length::Half);
+        chords[2].rhythm.dots(1);
+        assert_eq!(
+            lyricmode(&chords, &g).unwrap(),
+            "\\lyricmode { \"I\"4 \"ii6\"4 \"V7\"2. }"
+        );
+        assert_eq!(
+            figuremode(&chords, &g).unwrap(),
+            "\\figuremode { <_>4 <6>4 <7>2. }"
+        );
+    }
+}
@@ -0,0 +1,415 @@
+//! Twelve-tone rows, their transformations and their matrices.
+
+use std::convert::TryFrom;
+
+use crate::analysis::pitch_class::PitchClass;
+use crate::notation::note::Note;
+use crate::notation::pitch::Pitch;
+use crate::notation::rhythm::Rhythm;
+use crate::NoteNameLanguage;
+
+/// A form of a twelve-tone row, transposed by a number of semitones from 0
+/// to 11.
+///
+/// Forms are labelled relative to the row itself, so P<sub>0</sub> is the
+/// row as given, I<sub>0</sub> is its inversion starting on the same pitch
+/// class, and the retrogrades R<sub>n</sub> and RI<sub>n</sub> are P<sub>n</sub>
+/// and I<sub>n</sub> backwards.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum RowForm {
+    Prime(u8),
+    Inversion(u8),
+    Retrograde(u8),
+    RetrogradeInversion(u8),
+}
+
+impl RowForm {
+    /// Return the number of semitones the form is transposed by.
+    pub fn transposition(&self) -> u8 {
+        match self {
+            RowForm::Prime(n)
+            | RowForm::Inversion(n)
+            | RowForm::Retrograde(n)
+            | RowForm::RetrogradeInversion(n) => *n,
+        }
+    }
+}
+
+impl std::fmt::Display for RowForm {
+    /// Write the form's label, e.g. `P0`, `I7`, `R11` or `RI3`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RowForm::Prime(n) => write!(f, "P{}", n),
+            RowForm::Inversion(n) => write!(f, "I{}", n),
+            RowForm::Retrograde(n) => write!(f, "R{}", n),
+            RowForm::RetrogradeInversion(n) => write!(f, "RI{}", n),
+        }
+    }
+}
+
+impl std::str::FromStr for RowForm {
+    type Err = String;
+
+    /// Parse a form's label, e.g. `P0`, `I7`, `R11` or `RI3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid row form '{}'.", s);
+        let (constructor, n): (fn(u8) -> RowForm, &str) = if let Some(n) = s.strip_prefix("RI") {
+            (RowForm::RetrogradeInversion, n)
+        } else if let Some(n) = s.strip_prefix('P') {
+            (RowForm::Prime, n)
+        } else if let Some(n) = s.strip_prefix('I') {
+            (RowForm::Inversion, n)
+        } else if let Some(n) = s.strip_prefix('R') {
+            (RowForm::Retrograde, n)
+        } else {
+            return Err(error());
+        };
+        match n.parse() {
+            Ok(n) if n < 12 => Ok(constructor(n)),
+            _ => Err(error()),
+        }
+    }
+}
+
+/// A twelve-tone row: an ordering of all twelve pitch classes.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Row {
+    pitch_classes: Vec<PitchClass>,
+}
+
+impl Row {
+    /// Attempt to construct the row with an ordering of the pitch classes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` unless each of the twelve pitch classes appears
+    /// exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::serial::Row;
+    ///
+    /// // Webern, Symphony op. 21
+    /// let row: Row = "9 6 7 8 4 5 e t 2 1 0 3".parse().unwrap();
+    ///
+    /// assert_eq!(row.to_string(), "9 6 7 8 4 5 e t 2 1 0 3");
+    /// assert_eq!(
+    ///     "0 1 2 3 4 5 6 7 8 9 t".parse::<Row>(),
+    ///     Err(String::from("A twelve-tone row must have 12 pitch classes, found 11."))
+    /// );
+    /// ```
+    pub fn new(pitch_classes: &[PitchClass]) -> Result<Row, String> {
+        if pitch_classes.len() != 12 {
+            return Err(format!(
+                "A twelve-tone row must have 12 pitch classes, found {}.",
+                pitch_classes.len()
+            ));
+        }
+        for (index, pitch_class) in pitch_classes.iter().enumerate() {
+            if pitch_classes[..index].contains(pitch_class) {
+                return Err(format!(
+                    "Pitch class {} appears twice in the row.",
+                    pitch_class
+                ));
+            }
+        }
+        Ok(Row {
+            pitch_classes: pitch_classes.to_vec(),
+        })
+    }
+    /// Attempt to construct the row with the pitch classes of some pitches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a pitch is a rest or microtonal, or unless
+    /// the pitches contain each pitch class exactly once.
+    pub fn from_pitches(pitches: &[Pitch]) -> Result<Row, String> {
+        let pitch_classes = pitches
+            .iter()
+            .map(PitchClass::try_from)
+            .collect::<Result<Vec<PitchClass>, String>>()?;
+        Row::new(&pitch_classes)
+    }
+    /// Return the pitch classes of the row in order.
+    pub fn pitch_classes(&self) -> &[PitchClass] {
+        &self.pitch_classes
+    }
+    /// Return the row transposed by a number of semitones.
+    pub fn transpose(&self, semitones: i32) -> Row {
+        Row {
+            pitch_classes: self
+                .pitch_classes
+                .iter()
+                .map(|pitch_class| pitch_class.transpose(semitones))
+                .collect(),
+        }
+    }
+    /// Return the row inverted around its first pitch class.
+    pub fn invert(&self) -> Row {
+        let first = i32::from(self.pitch_classes[0].number());
+        Row {
+            pitch_classes: self
+                .pitch_classes
+                .iter()
+                .map(|pitch_class| pitch_class.invert().transpose(2 * first))
+                .collect(),
+        }
+    }
+    /// Return the row backwards.
+    pub fn retrograde(&self) -> Row {
+        Row {
+            pitch_classes: self.pitch_classes.iter().rev().cloned().collect(),
+        }
+    }
+    /// Return a form of the row.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::serial::{Row, RowForm};
+    ///
+    /// let row: Row = "0 e 7 8 3 1 2 t 6 5 4 9".parse().unwrap();
+    ///
+    /// assert_eq!(row.form(RowForm::Inversion(0)).to_string(), "0 1 5 4 9 e t 2 6 7 8 3");
+    /// assert_eq!(row.form(RowForm::RetrogradeInversion(2)).to_string(), "5 t 9 8 4 0 1 e 6 7 3 2");
+    /// ```
+    pub fn form(&self, form: RowForm) -> Row {
+        let semitones = i32::from(form.transposition());
+        match form {
+            RowForm::Prime(_) => self.transpose(semitones),
+            RowForm::Inversion(_) => self.invert().transpose(semitones),
+            RowForm::Retrograde(_) => self.transpose(semitones).retrograde(),
+            RowForm::RetrogradeInversion(_) => self.invert().transpose(semitones).retrograde(),
+        }
+    }
+    /// Return the row's twelve-tone matrix.
+    pub fn matrix(&self) -> Matrix {
+        Matrix { row: self.clone() }
+    }
+    /// Return the notes of the row, from middle C up, with a rhythm.
+    pub fn notes(&self, rhythm: &Rhythm) -> Vec<Note> {
+        self.pitch_classes
+            .iter()
+            .map(|pitch_class| Note {
+                pitch: pitch_class.pitch(),
+                rhythm: rhythm.clone(),
+                tie: false,
+                articulations: vec![],
+                beam: None,
+                dynamic: None,
+            })
+            .collect()
+    }
+    /// Write the row as LilyPond quarter notes from middle C up, in a
+    /// language.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::serial::Row;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let row: Row = "0 e 7 8 3 1 2 t 6 5 4 9".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     row.to_lilypond(NoteNameLanguage::English),
+    ///     "{ c'4 b'4 g'4 af'4 ef'4 cs'4 d'4 bf'4 fs'4 f'4 e'4 a'4 }"
+    /// );
+    /// ```
+    pub fn to_lilypond(&self, language: NoteNameLanguage) -> String {
+        format!("{{ {} }}", self.lilypond_notes(language))
+    }
+    /// Write the notes of the row in a language, separated by spaces.
+    fn lilypond_notes(&self, language: NoteNameLanguage) -> String {
+        let notes: Vec<String> = self
+            .notes(&Rhythm::new())
+            .iter()
+            .map(|note| language.lilypond_from_note(note))
+            .collect();
+        notes.join(" ")
+    }
+}
+
+impl std::fmt::Display for Row {
+    /// Write the row's pitch classes separated by spaces, with `t` and `e`
+    /// for 10 and 11.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pitch_classes: Vec<String> = self
+            .pitch_classes
+            .iter()
+            .map(|pitch_class| pitch_class.to_string())
+            .collect();
+        write!(f, "{}", pitch_classes.join(" "))
+    }
+}
+
+impl std::str::FromStr for Row {
+    type Err = String;
+
+    /// Parse a row's pitch classes separated by spaces or commas, e.g.
+    /// `0 e 7 8 3 1 2 t 6 5 4 9`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pitch_classes = s
+            .split([',', ' '])
+            .filter(|pitch_class| !pitch_class.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<PitchClass>, String>>()?;
+        Row::new(&pitch_classes)
+    }
+}
+
+/// The twelve-tone matrix of a row.
+///
+/// Its rows, read left to right, are the prime forms of the row, starting on
+/// the pitch classes of I<sub>0</sub>; read right to left, they are the
+/// retrogrades. Its columns, read top to bottom, are the inversions, and
+/// read bottom to top, the retrograde inversions.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Matrix {
+    row: Row,
+}
+
+impl Matrix {
+    /// Return the pitch class at a row and column from 0 to 11.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row or column is 12 or more.
+    pub fn get(&self, row: usize, column: usize) -> PitchClass {
+        self.prime(row).pitch_classes[column]
+    }
+    /// Return the row form read left to right along a row of the matrix.
+    pub fn prime(&self, row: usize) -> Row {
+        self.row.form(self.label(row))
+    }
+    /// Return the prime form along a row of the matrix.
+    fn label(&self, row: usize) -> RowForm {
+        let first = self.row.pitch_classes[0].number();
+        let semitones = self.row.invert().pitch_classes[row].number() + 12 - first;
+        RowForm::Prime(semitones % 12)
+    }
+    /// Write the matrix as LilyPond in a language: one row form per line,
+    /// labelled with its name, from middle C up.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::analysis::serial::Row;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let row: Row = "0 e 7 8 3 1 2 t 6 5 4 9".parse().unwrap();
+    /// let lilypond = row.matrix().to_lilypond(NoteNameLanguage::English);
+    /// let mut lines = lilypond.lines();
+    ///
+    /// assert_eq!(lines.next(), Some("{"));
+    /// assert_eq!(lines.next(), Some("  \\cadenzaOn"));
+    /// assert_eq!(
+    ///     lines.next(),
+    ///     Some("  c'4^\"P0\" b'4 g'4 af'4 ef'4 cs'4 d'4 bf'4 fs'4 f'4 e'4 a'4 \\bar \"|\" \\break")
+    /// );
+    /// assert_eq!(
+    ///     lines.next(),
+    ///     Some("  cs'4^\"P1\" c'4 af'4 a'4 e'4 d'4 ef'4 b'4 g'4 fs'4 f'4 bf'4 \\bar \"|\" \\break")
+    /// );
+    /// ```
+    pub fn to_lilypond(&self, language: NoteNameLanguage) -> String {
+        let mut lilypond = String::from("{\n  \\cadenzaOn\n");
+        for row in 0..12 {
+            let notes = self.prime(row).lilypond_notes(language);
+            let (first, rest) = notes.split_once(' ').unwrap_or((&notes, ""));
+            lilypond.push_str(&format!(
+                "  {}^\"{}\" {} \\bar \"|\" \\break\n",
+                first,
+                self.label(row),
+                rest
+            ));
+        }
+        lilypond.push('}');
+        lilypond
+    }
+}
+
+impl std::fmt::Display for Matrix {
+    /// Write the matrix's pitch classes, one row per line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<String> = (0..12).map(|row| self.prime(row).to_string()).collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::serial::*;
+    fn row(s: &str) -> Row {
+        s.parse().unwrap()
+    }
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            "0 1 2 3 4 5 6 7 8 9 t 0".parse::<Row>(),
+            Err(String::from("Pitch class 0 appears twice in the row."))
+        );
+        assert!("0 1 2 3 4 5 6 7 8 9 t x".parse::<Row>().is_err());
+        let pitches: Vec<Pitch> = row("0 e 7 8 3 1 2 t 6 5 4 9")
+            .notes(&Rhythm::new())
+            .into_iter()
+            .map(|note| note.pitch)
+            .collect();
+        assert_eq!(
+            Row::from_pitches(&pitches),
+            Ok(row("0 e 7 8 3 1 2 t 6 5 4 9"))
+        );
+    }
+    #[test]
+    fn test_forms() {
+        let p0 = row("0 e 7 8 3 1 2 t 6 5 4 9");
+        assert_eq!(p0.form(RowForm::Prime(0)), p0);
+        assert_eq!(p0.form(RowForm::Prime(3)), row("3 2 t e 6 4 5 1 9 8 7 0"));
+        assert_eq!(
+            p0.form(RowForm::Retrograde(0)),
+            row("9 4 5 6 t 2 1 3 8 7 e 0")
+        );
+        assert_eq!(
+            p0.form(RowForm::Inversion(0)),
+            row("0 1 5 4 9 e t 2 6 7 8 3")
+        );
+        // inversions are around the first pitch class, not C
+        let p5 = p0.transpose(5);
+        assert_eq!(
+            p5.form(RowForm::Inversion(0)).pitch_classes()[0].number(),
+            5
+        );
+    }
+    #[test]
+    fn test_row_form() {
+        for label in ["P0", "I11", "R5", "RI7"] {
+            assert_eq!(label.parse::<RowForm>().unwrap().to_string(), label);
+        }
+        assert!("P12".parse::<RowForm>().is_err());
+        assert!("Q1".parse::<RowForm>().is_err());
+    }
+    #[test]
+    fn test_matrix() {
+        let p0 = row("0 e 7 8 3 1 2 t 6 5 4 9");
+        let matrix = p0.matrix();
+        // the diagonal is the first pitch class of the row
+        for index in 0..12 {
+            assert_eq!(matrix.get(index, index).number(), 0);
+        }
+        let column: Vec<PitchClass> = (0..12).map(|row| matrix.get(row, 0)).collect();
+        assert_eq!(column, p0.invert().pitch_classes());
+        assert_eq!(matrix.prime(1), p0.form(RowForm::Prime(1)));
+        assert_eq!(
+            matrix.to_string().lines().last(),
+            Some("3 2 t e 6 4 5 1 9 8 7 0")
+        );
+        assert_eq!(
+            matrix
+                .to_lilypond(NoteNameLanguage::English)
+                .lines()
+                .count(),
+            15
+        );
+    }
+}
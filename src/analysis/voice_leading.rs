@@ -0,0 +1,670 @@
+//! Voice-leading rules for four-part (SATB) writing.
+
+use std::collections::BTreeSet;
+
+use crate::analysis::roman::RomanNumeral;
+use crate::notation::chord::{Chord, ChordQuality};
+use crate::notation::interval::{Interval, Quality};
+use crate::notation::key::TonalKey;
+use crate::notation::measure::check_partial;
+use crate::notation::pitch::{Alteration, NoteName, Pitch};
+use crate::notation::rhythm::Duration;
+use crate::notation::score::{Score, Staff, StaffChange};
+use crate::notation::voice::{Event, Voice};
+
+/// The four voices of four-part writing, from top to bottom.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Part {
+    Soprano,
+    Alto,
+    Tenor,
+    Bass,
+}
+
+static PARTS: [Part; 4] = [Part::Soprano, Part::Alto, Part::Tenor, Part::Bass];
+
+impl Part {
+    /// Return the lowest and highest MIDI note numbers of the part's usual
+    /// range: C4--G5 for sopranos, G3--D5 for altos, C3--G4 for tenors, and
+    /// E2--C4 for basses.
+    pub fn range(&self) -> (i32, i32) {
+        match self {
+            Part::Soprano => (60, 79),
+            Part::Alto => (55, 74),
+            Part::Tenor => (48, 67),
+            Part::Bass => (40, 60),
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Part::Soprano => "soprano",
+                Part::Alto => "alto",
+                Part::Tenor => "tenor",
+                Part::Bass => "bass",
+            }
+        )
+    }
+}
+
+/// A broken voice-leading rule, with the parts that break it.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Rule {
+    /// Two parts move from a perfect fifth to another perfect fifth.
+    ParallelFifths(Part, Part),
+    /// Two parts move from a perfect octave or unison to another.
+    ParallelOctaves(Part, Part),
+    /// The soprano leaps and the bass moves in the same direction into a
+    /// perfect fifth.
+    HiddenFifths(Part, Part),
+    /// The soprano leaps and the bass moves in the same direction into a
+    /// perfect octave.
+    HiddenOctaves(Part, Part),
+    /// The first part is below the second part, the one below it.
+    Crossing(Part, Part),
+    /// One of two adjacent parts moves past where the other just was.
+    Overlap(Part, Part),
+    /// Adjacent upper parts are more than an octave apart.
+    Spacing(Part, Part),
+    /// The part is outside of its [range][Part::range].
+    Range(Part),
+    /// The leading tone of a dominant chord does not rise to the tonic of
+    /// the chord after it.
+    UnresolvedLeadingTone(Part),
+    /// The seventh of a seventh chord does not fall by step when the chord
+    /// changes.
+    UnresolvedSeventh(Part),
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rule::ParallelFifths(a, b) => write!(f, "parallel fifths between {} and {}", a, b),
+            Rule::ParallelOctaves(a, b) => write!(f, "parallel octaves between {} and {}", a, b),
+            Rule::HiddenFifths(a, b) => write!(f, "hidden fifths between {} and {}", a, b),
+            Rule::HiddenOctaves(a, b) => write!(f, "hidden octaves between {} and {}", a, b),
+            Rule::Crossing(a, b) => write!(f, "{} crosses below {}", a, b),
+            Rule::Overlap(a, b) => write!(f, "{} and {} overlap", a, b),
+            Rule::Spacing(a, b) => write!(f, "more than an octave between {} and {}", a, b),
+            Rule::Range(a) => write!(f, "{} out of range", a),
+            Rule::UnresolvedLeadingTone(a) => write!(f, "unresolved leading tone in {}", a),
+            Rule::UnresolvedSeventh(a) => write!(f, "unresolved seventh in {}", a),
+        }
+    }
+}
+
+/// A position in a score, counted from measure 1, beat 1, or from measure 0
+/// if the score starts with a pickup.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Location {
+    pub measure: u32,
+    /// The beat within the measure, counted by the groups of the measure's
+    /// [beat structure](crate::notation::time::TimeSignature::beats), which may be a fraction for
+    /// positions between beats, e.g. `5/2` halfway through beat 2.
+    pub beat: Duration,
+}
+
+impl Location {
+    /// Find the location of a time from the start of a staff, in the
+    /// measures of its time signatures and pickup.
+    fn new(onset: Duration, staff: &Staff) -> Location {
+        let full = |index| staff.time_signature_at(index).measure_duration();
+        // a pickup measure is the end of a full measure
+        let mut start = match staff.partial {
+            Some(partial) => partial - full(0),
+            None => Duration::from_integer(0),
+        };
+        let mut index = 0;
+        while start + full(index) <= onset {
+            start += full(index);
+            index += 1;
+        }
+        let mut offset = onset - start;
+        let mut beat = Duration::from_integer(1);
+        for length in staff.time_signature_at(index).beats() {
+            if offset < length {
+                beat += offset / length;
+                break;
+            }
+            offset -= length;
+            beat += 1;
+        }
+        Location {
+            measure: index as u32 + if staff.partial.is_some() { 0 } else { 1 },
+            beat,
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "measure {}, beat {}", self.measure, self.beat)
+    }
+}
+
+/// A broken rule at a location.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Finding {
+    pub rule: Rule,
+    pub location: Location,
+}
+
+impl std::fmt::Display for Finding {
+    /// Write the finding, e.g. `measure 2, beat 3: parallel fifths between
+    /// alto and bass`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.rule)
+    }
+}
+
+/// The pitches sounding at one time, one for each part (`None` for rests),
+/// and which of them start at that time.
+struct Sonority {
+    onset: Duration,
+    pitches: Vec<Option<Pitch>>,
+    attacks: Vec<bool>,
+}
+
+impl Sonority {
+    /// Analyse the pitches of the sonority as a chord, if possible.
+    fn numeral(&self, key: &TonalKey) -> Option<RomanNumeral> {
+        RomanNumeral::analyze(&self.chord(), key).ok()
+    }
+    fn chord(&self) -> Chord {
+        Chord::new(self.pitches.iter().flatten().cloned().collect())
+    }
+}
+
+/// Attempt to check the voice-leading of a four-part score.
+///
+/// The voices of all staves, from top to bottom, are the soprano, alto,
+/// tenor and bass. Motion is checked between each pair of successive
+/// sonorities, i.e. whenever any part starts a new note. Leading tones are
+/// only required to resolve in the outer parts, as inner parts may leap
+/// away from them.
+///
+/// Findings are located in the measures of the top staff, following its
+/// pickup and time signature changes, as [`Measures`] splits it.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the score does not have exactly four voices, if a
+/// voice contains a chord, if a pitch is not a whole number of semitones
+/// away from the others, or if a time signature or the pickup of the top
+/// staff is invalid.
+///
+/// [`Measures`]: crate::notation::measure::Measures
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::analysis::voice_leading::{check, Part, Rule};
+/// use lilypond::notation::key::{Mode, TonalKey};
+/// use lilypond::notation::pitch::{Pitch, NoteName};
+/// use lilypond::notation::score::{Score, Staff};
+/// use lilypond::notation::voice::Voice;
+/// use lilypond::parser::parse_notes;
+/// use lilypond::NoteNameLanguage;
+///
+/// let mut score = Score::new();
+/// for notes in ["e'' f''", "g' a'", "c' d'", "c d"] {
+///     let notes = parse_notes(notes, NoteNameLanguage::English).unwrap();
+///     score.staves.push(Staff::from(Voice::from(notes)));
+/// }
+/// let key = TonalKey::new(&Pitch::new(NoteName::C), Mode::Major).unwrap();
+///
+/// let findings = check(&score, &key).unwrap();
+///
+/// assert_eq!(findings[0].rule, Rule::ParallelFifths(Part::Alto, Part::Tenor));
+/// assert_eq!(
+///     findings[0].to_string(),
+///     "measure 1, beat 2: parallel fifths between alto and tenor"
+/// );
+/// ```
+pub fn check(score: &Score, key: &TonalKey) -> Result<Vec<Finding>, String> {
+    let voices: Vec<&Voice> = score
+        .staves
+        .iter()
+        .flat_map(|staff| staff.voices.iter())
+        .collect();
+    if voices.len() != PARTS.len() {
+        return Err(format!("Expected 4 voices, found {}.", voices.len()));
+    }
+    // locations are counted in the measures of the top staff
+    let staff = &score.staves[0];
+    staff.time_signature.validate()?;
+    for (_, change) in &staff.changes {
+        if let StaffChange::TimeSignature(time_signature) = change {
+            time_signature.validate()?;
+        }
+    }
+    if let Some(partial) = staff.partial {
+        check_partial(partial, staff.time_signature_at(0).measure_duration())?;
+    }
+    let sonorities = sonorities(&voices)?;
+    let mut findings = vec![];
+    for (index, sonority) in sonorities.iter().enumerate() {
+        let location = Location::new(sonority.onset, staff);
+        let mut add = |rule| findings.push(Finding { rule, location });
+        if let Some(previous) = index.checked_sub(1).map(|i| &sonorities[i]) {
+            for rule in motion(previous, sonority)? {
+                add(rule);
+            }
+            for rule in resolution(previous, sonority, key) {
+                add(rule);
+            }
+        }
+        for rule in vertical(sonority)? {
+            add(rule);
+        }
+    }
+    Ok(findings)
+}
+
+/// Find the sonorities of the voices, at every time a voice starts a note.
+fn sonorities(voices: &[&Voice]) -> Result<Vec<Sonority>, String> {
+    // the onset and pitch of each note of each voice
+    let mut lines = vec![];
+    let mut onsets = BTreeSet::new();
+    for voice in voices {
+        let mut line = vec![];
+        let mut onset = Duration::from_integer(0);
+        for event in &voice.events {
+            let note = match event {
+                Event::Note(note) => note,
+                Event::Chord(_) => {
+                    return Err(String::from(
+                        "Voices for voice-leading must not contain chords.",
+                    ))
+                }
+            };
+            let pitch = match note.pitch.note_name {
+                NoteName::None => None,
+                _ => Some(note.pitch.clone()),
+            };
+            onsets.insert(onset);
+            line.push((onset, pitch));
+            onset += note.rhythm.duration();
+        }
+        line.push((onset, None));
+        lines.push(line);
+    }
+    Ok(onsets
+        .into_iter()
+        .map(|onset| {
+            let mut pitches = vec![];
+            let mut attacks = vec![];
+            for line in &lines {
+                let current = line.iter().rev().find(|(start, _)| *start <= onset);
+                pitches.push(current.and_then(|(_, pitch)| pitch.clone()));
+                attacks.push(current.is_some_and(|(start, _)| *start == onset));
+            }
+            Sonority {
+                onset,
+                pitches,
+                attacks,
+            }
+        })
+        .collect())
+}
+
+/// Return 5 if two pitches are a perfect fifth apart, 8 if they are a
+/// perfect unison or octave apart, and 0 otherwise, ignoring compounds.
+fn perfect(a: &Pitch, b: &Pitch) -> Result<u8, String> {
+    let simple = Interval::between(a, b)?.simple();
+    Ok(match (simple.quality(), simple.number()) {
+        (Quality::Perfect, 5) => 5,
+        (Quality::Perfect, 1) | (Quality::Perfect, 8) => 8,
+        _ => 0,
+    })
+}
+
+/// Find the rules broken by the motion from one sonority to the next.
+fn motion(previous: &Sonority, next: &Sonority) -> Result<Vec<Rule>, String> {
+    let mut rules = vec![];
+    for (upper, upper_part) in PARTS.iter().enumerate() {
+        for (lower, lower_part) in PARTS.iter().enumerate().skip(upper + 1) {
+            let (a1, b1, a2, b2) = match (
+                &previous.pitches[upper],
+                &previous.pitches[lower],
+                &next.pitches[upper],
+                &next.pitches[lower],
+            ) {
+                (Some(a1), Some(b1), Some(a2), Some(b2)) => (a1, b1, a2, b2),
+                _ => continue,
+            };
+            let (from, to) = (perfect(a1, b1)?, perfect(a2, b2)?);
+            let parts = (*upper_part, *lower_part);
+            if a1 != a2 && b1 != b2 && from == to {
+                match to {
+                    5 => rules.push(Rule::ParallelFifths(parts.0, parts.1)),
+                    8 => rules.push(Rule::ParallelOctaves(parts.0, parts.1)),
+                    _ => {}
+                }
+            }
+            let a_motion = a2.semitones()? - a1.semitones()?;
+            let b_motion = b2.semitones()? - b1.semitones()?;
+            let similar = a_motion * b_motion > Alteration::from_integer(0);
+            let leap = (a2.diatonic_number()? - a1.diatonic_number()?).abs() > 1;
+            if (upper, lower) == (0, PARTS.len() - 1) && similar && leap && from != to {
+                match to {
+                    5 => rules.push(Rule::HiddenFifths(parts.0, parts.1)),
+                    8 => rules.push(Rule::HiddenOctaves(parts.0, parts.1)),
+                    _ => {}
+                }
+            }
+            if lower == upper + 1
+                && (a1 != a2 || b1 != b2)
+                && (b2.semitones()? > a1.semitones()? || a2.semitones()? < b1.semitones()?)
+            {
+                rules.push(Rule::Overlap(parts.0, parts.1));
+            }
+        }
+    }
+    Ok(rules)
+}
+
+/// Find the rules broken within a sonority by parts that start a note.
+fn vertical(sonority: &Sonority) -> Result<Vec<Rule>, String> {
+    let mut rules = vec![];
+    for (index, part) in PARTS.iter().enumerate() {
+        let pitch = match (&sonority.pitches[index], sonority.attacks[index]) {
+            (Some(pitch), true) => pitch,
+            _ => continue,
+        };
+        let (low, high) = part.range();
+        let semitones = pitch.semitones()?;
+        if semitones < Alteration::from(low) || semitones > Alteration::from(high) {
+            rules.push(Rule::Range(*part));
+        }
+    }
+    for (upper, parts) in PARTS.windows(2).enumerate() {
+        let lower = upper + 1;
+        if !sonority.attacks[upper] && !sonority.attacks[lower] {
+            continue;
+        }
+        if let (Some(a), Some(b)) = (&sonority.pitches[upper], &sonority.pitches[lower]) {
+            let distance = a.semitones()? - b.semitones()?;
+            if distance < Alteration::from_integer(0) {
+                rules.push(Rule::Crossing(parts[0], parts[1]));
+            }
+            // the tenor and bass may be further apart
+            if lower < PARTS.len() - 1 && distance > Alteration::from_integer(12) {
+                rules.push(Rule::Spacing(parts[0], parts[1]));
+            }
+        }
+    }
+    Ok(rules)
+}
+
+/// Find leading tones and sevenths of one sonority's chord that do not
+/// resolve in the next.
+fn resolution(previous: &Sonority, next: &Sonority, key: &TonalKey) -> Vec<Rule> {
+    let mut rules = vec![];
+    let (numeral, next_numeral) = match (previous.numeral(key), next.numeral(key)) {
+        (Some(numeral), next_numeral) => (numeral, next_numeral),
+        _ => return rules,
+    };
+    let steps = |from: &Pitch, to: &Pitch| -> Option<(i32, Alteration)> {
+        Some((
+            to.diatonic_number().ok()? - from.diatonic_number().ok()?,
+            to.semitones().ok()? - from.semitones().ok()?,
+        ))
+    };
+    let dominant = numeral.tonicized.is_none()
+        && numeral.alteration == 0
+        && match numeral.degree {
+            5 => matches!(
+                numeral.quality,
+                ChordQuality::Major | ChordQuality::DominantSeventh
+            ),
+            7 => matches!(
+                numeral.quality,
+                ChordQuality::Diminished
+                    | ChordQuality::HalfDiminishedSeventh
+                    | ChordQuality::DiminishedSeventh
+            ),
+            _ => false,
+        };
+    let to_tonic = next_numeral
+        .as_ref()
+        .is_some_and(|next| next.tonicized.is_none() && (next.degree == 1 || next.degree == 6));
+    if dominant && to_tonic {
+        for index in [0, PARTS.len() - 1] {
+            if let (Some(pitch), Some(next_pitch)) =
+                (&previous.pitches[index], &next.pitches[index])
+            {
+                let leading_tone = steps(pitch, key.tonic()).is_some_and(|(steps, semitones)| {
+                    steps.rem_euclid(7) == 1
+                        && semitones.is_integer()
+                        && semitones.to_integer().rem_euclid(12) == 1
+                });
+                let resolved = steps(pitch, next_pitch) == Some((1, Alteration::from_integer(1)));
+                if leading_tone && !resolved {
+                    rules.push(Rule::UnresolvedLeadingTone(PARTS[index]));
+                }
+            }
+        }
+    }
+    if numeral.quality.is_seventh() {
+        let chord = previous.chord();
+        let root = match chord.root() {
+            Ok(root) => root,
+            Err(_) => return rules,
+        };
+        let next_chord = next.chord();
+        let same_chord = next_chord.quality() == chord.quality()
+            && next_chord.root().is_ok_and(|next_root| {
+                next_root.note_name == root.note_name && next_root.accidental == root.accidental
+            });
+        if same_chord {
+            return rules;
+        }
+        for (index, part) in PARTS.iter().enumerate() {
+            if let (Some(pitch), Some(next_pitch)) =
+                (&previous.pitches[index], &next.pitches[index])
+            {
+                let seventh = steps(root, pitch).is_some_and(|(steps, _)| steps.rem_euclid(7) == 6);
+                let resolved = steps(pitch, next_pitch).is_some_and(|(steps, _)| steps == -1);
+                if seventh && !resolved {
+                    rules.push(Rule::UnresolvedSeventh(*part));
+                }
+            }
+        }
+    }
+    rules
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::voice_leading::*;
+    use crate::notation::key::Mode;
+    use crate::notation::time::TimeSignature;
+    use crate::parser::parse_notes;
+    use crate::NoteNameLanguage;
+    /// Make a four-part score from English LilyPond notes.
+    fn score(voices: [&str; 4]) -> Score {
+        let mut score = Score::new();
+        for notes in voices {
+            let notes = parse_notes(notes, NoteNameLanguage::English).unwrap();
+            score.staves.push(Staff::from(Voice::from(notes)));
+        }
+        score
+    }
+    fn rules(voices: [&str; 4], mode: Mode) -> Vec<Rule> {
+        let tonic = match mode {
+            Mode::Major => Pitch::new(NoteName::C),
+            Mode::Minor => Pitch::new(NoteName::A),
+        };
+        let key = TonalKey::new(&tonic, mode).unwrap();
+        check(&score(voices), &key)
+            .unwrap()
+            .into_iter()
+            .map(|finding| finding.rule)
+            .collect()
+    }
+    #[test]
+    fn test_good() {
+        // I IV V7 I in C major
+        let voices = ["e'' f'' f'' e''", "g' a' g' g'", "c' c' b c'", "c f g c"];
+        assert_eq!(rules(voices, Mode::Major), vec![]);
+    }
+    #[test]
+    fn test_parallels() {
+        let voices = ["c'' d''", "g' a'", "e' f'", "c d"];
+        assert_eq!(
+            rules(voices, Mode::Major),
+            vec![
+                Rule::ParallelOctaves(Part::Soprano, Part::Bass),
+                Rule::ParallelFifths(Part::Alto, Part::Bass),
+            ]
+        );
+        // contrary fifths are still consecutive fifths
+        let voices = ["g'' d''", "c'' d''", "e' f'", "c g,"];
+        assert!(
+            rules(voices, Mode::Major).contains(&Rule::ParallelFifths(Part::Soprano, Part::Bass))
+        );
+    }
+    #[test]
+    fn test_hidden() {
+        let voices = ["e'' g''", "g' b'", "c' d'", "c g"];
+        assert_eq!(
+            rules(voices, Mode::Major),
+            vec![Rule::HiddenOctaves(Part::Soprano, Part::Bass)]
+        );
+        let voices = ["g'' a''", "e'' f''", "c'' c''", "c d"];
+        assert!(
+            !rules(voices, Mode::Major).contains(&Rule::HiddenFifths(Part::Soprano, Part::Bass))
+        );
+        let voices = ["e'' a''", "c'' c''", "g' f'", "c d"];
+        assert!(rules(voices, Mode::Major).contains(&Rule::HiddenFifths(Part::Soprano, Part::Bass)));
+    }
+    #[test]
+    fn test_crossing_overlap_spacing() {
+        let voices = ["e'' e''", "g' g''", "c' c'", "c c"];
+        let found = rules(voices, Mode::Major);
+        assert!(found.contains(&Rule::Crossing(Part::Soprano, Part::Alto)));
+        assert!(found.contains(&Rule::Overlap(Part::Soprano, Part::Alto)));
+        let voices = ["g'' g''", "c' c'", "e c'", "c c"];
+        let found = rules(voices, Mode::Major);
+        assert!(found.contains(&Rule::Spacing(Part::Soprano, Part::Alto)));
+        assert!(!found.contains(&Rule::Spacing(Part::Tenor, Part::Bass)));
+    }
+    #[test]
+    fn test_range() {
+        let voices = ["c''' c'''2", "c'' c'' c''", "g' g' g'", "c c c"];
+        let key = TonalKey::new(&Pitch::new(NoteName::C), Mode::Major).unwrap();
+        let findings = check(&score(voices), &key).unwrap();
+        // the held note is only reported once
+        assert_eq!(
+            findings,
+            vec![
+                Finding {
+                    rule: Rule::Range(Part::Soprano),
+                    location: Location {
+                        measure: 1,
+                        beat: Duration::from_integer(1),
+                    },
+                },
+                Finding {
+                    rule: Rule::Range(Part::Soprano),
+                    location: Location {
+                        measure: 1,
+                        beat: Duration::from_integer(2),
+                    },
+                },
+            ]
+        );
+    }
+    #[test]
+    fn test_leading_tone() {
+        // V to i in A minor with the leading tone falling in the soprano
+        let voices = ["gs' e'", "e' e'", "b c'", "e a"];
+        assert_eq!(
+            rules(voices, Mode::Minor),
+            vec![Rule::UnresolvedLeadingTone(Part::Soprano)]
+        );
+        // inner voices may leave the leading tone
+        let voices = ["d'' c''", "b' g'", "g' e'", "g c"];
+        assert_eq!(rules(voices, Mode::Major), vec![]);
+    }
+    #[test]
+    fn test_seventh() {
+        let voices = ["f'' g''", "d'' c''", "b' c''", "g c"];
+        let found = rules(voices, Mode::Major);
+        assert!(found.contains(&Rule::UnresolvedSeventh(Part::Soprano)));
+        // a held seventh resolves later
+        let voices = ["f'' f'' e''", "d'' b' c''", "g' g' g'", "g g c"];
+        assert_eq!(rules(voices, Mode::Major), vec![]);
+    }
+    #[test]
+    fn test_location() {
+        let mut staff = Staff::new();
+        staff.time_signature = TimeSignature::from_fraction(3, 4).unwrap();
+        let location = Location::new(Duration::new(7, 8), &staff);
+        assert_eq!(location.measure, 2);
+        assert_eq!(location.beat, Duration::new(3, 2));
+        assert_eq!(location.to_string(), "measure 2, beat 3/2");
+        // a pickup is the end of measure 0
+        staff.partial = Some(Duration::new(1, 4));
+        assert_eq!(
+            Location::new(Duration::from_integer(0), &staff).to_string(),
+            "measure 0, beat 3"
+        );
+        assert_eq!(
+            Location::new(Duration::new(1, 2), &staff).to_string(),
+            "measure 1, beat 2"
+        );
+        // beats follow the beat structure, and the meter can change
+        let time_signature = TimeSignature::additive(&[3, 2], 8).unwrap();
+        staff.change(2, StaffChange::TimeSignature(time_signature));
+        assert_eq!(
+            Location::new(Duration::from_integer(1), &staff).to_string(),
+            "measure 2, beat 1"
+        );
+        assert_eq!(
+            Location::new(Duration::new(11, 8), &staff).to_string(),
+            "measure 2, beat 2"
+        );
+        assert_eq!(
+            Location::new(Duration::new(29, 16), &staff).to_string(),
+            "measure 3, beat 3/2"
+        );
+    }
+    #[test]
+    fn test_check_pickup() {
+        // after a quarter-note pickup, the third quarter is beat 2 of measure 1
+        let mut score = score(["c'' e'' f''", "g' g' a'", "e' c' d'", "c c d"]);
+        score.staves[0].time_signature = TimeSignature::from_fraction(3, 4).unwrap();
+        score.staves[0].partial = Some(Duration::new(1, 4));
+        let key = TonalKey::new(&Pitch::new(NoteName::C), Mode::Major).unwrap();
+        let findings = check(&score, &key).unwrap();
+        let findings: Vec<String> = findings.iter().map(Finding::to_string).collect();
+        assert_eq!(
+            findings,
+            vec![
+                "measure 1, beat 2: parallel fifths between alto and tenor",
+                "measure 1, beat 2: parallel fifths between alto and bass",
+                "measure 1, beat 2: parallel octaves between tenor and bass",
+            ]
+        );
+    }
+    #[test]
+    fn test_errors() {
+        let key = TonalKey::new(&Pitch::new(NoteName::C), Mode::Major).unwrap();
+        let mut three = score(["c''", "g'", "e'", "c"]);
+        three.staves.pop();
+        assert_eq!(
+            check(&three, &key),
+            Err(String::from("Expected 4 voices, found 3."))
+        );
+        let mut chords = score(["c''", "g'", "e'", "c"]);
+        chords.staves[0].voices[0].push(Chord::new(vec![Pitch::new(NoteName::C)]));
+        assert!(check(&chords, &key).is_err());
+    }
+}
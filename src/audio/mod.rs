@@ -0,0 +1,4 @@
+//! Rendering of notes to audio, for previews without a synthesizer.
+
+pub mod synth;
+pub mod wav;
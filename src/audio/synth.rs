@@ -0,0 +1,389 @@
+//! A simple synthesizer that renders notes to samples.
+
+use std::f64::consts::TAU;
+use std::path::Path;
+
+use crate::audio::wav::write_wav;
+use crate::export::is_rest;
+use crate::midi::midi_note::MidiNote;
+use crate::notation::rhythm::{Duration, DurationType};
+use crate::notation::voice::{Event, Voice};
+use crate::tuning::temperament::Tuning;
+
+/// The shape of the wave that the synthesizer plays each note with.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum Waveform {
+    /// A pure tone.
+    #[default]
+    Sine,
+    /// A hollow tone with only odd harmonics.
+    Square,
+    /// A sum of sine waves at the harmonics of the note, with the amplitude
+    /// of each harmonic from the fundamental up.
+    Additive(Vec<f64>),
+}
+
+impl Waveform {
+    /// Return the value of the wave at a time in seconds, for a note with a
+    /// frequency in hertz, between -1 and 1. Harmonics at or above
+    /// `nyquist` are left out.
+    fn sample(&self, frequency: f64, time: f64, nyquist: f64) -> f64 {
+        let phase = TAU * frequency * time;
+        match self {
+            Waveform::Sine => phase.sin(),
+            Waveform::Square => {
+                if phase.sin() >= 0.0 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Additive(harmonics) => {
+                let total: f64 = harmonics.iter().map(|amplitude| amplitude.abs()).sum();
+                if total == 0.0 {
+                    return 0.0;
+                }
+                harmonics
+                    .iter()
+                    .enumerate()
+                    .map(|(index, amplitude)| (index as f64 + 1.0, amplitude))
+                    .filter(|(harmonic, _)| harmonic * frequency < nyquist)
+                    .map(|(harmonic, amplitude)| amplitude * (harmonic * phase).sin())
+                    .sum::<f64>()
+                    / total
+            }
+        }
+    }
+}
+
+/// A synthesizer that renders notes to mono 16-bit samples.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Synth {
+    pub waveform: Waveform,
+    /// The number of samples per second.
+    pub sample_rate: u32,
+    /// The duration of a beat in whole notes, e.g. 1/4 for a quarter note.
+    pub beat: Duration,
+    /// The number of beats per minute.
+    pub tempo: f64,
+    /// The tuning of MIDI notes to frequencies.
+    pub tuning: Tuning,
+    /// The peak amplitude, from 0 for silence to 1 for full scale.
+    pub volume: f64,
+}
+
+impl Default for Synth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The time in seconds that each note fades in and out over, so that the
+/// notes do not click.
+const FADE: f64 = 0.005;
+
+impl Synth {
+    /// Construct a new synthesizer, which plays sine waves at half volume
+    /// at 44100 samples per second, with 120 quarter notes per minute, in
+    /// twelve-tone equal temperament with A4 at 440 Hz.
+    pub fn new() -> Synth {
+        Synth {
+            waveform: Waveform::Sine,
+            sample_rate: 44100,
+            beat: Duration::new(1, 4),
+            tempo: 120.0,
+            tuning: Tuning::default(),
+            volume: 0.5,
+        }
+    }
+    /// Attempt to render a sequence of chords to samples. Each chord is the
+    /// MIDI notes to play at once, or none for a rest, and a duration in
+    /// whole notes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the sample rate is zero, if the tempo or the
+    /// beat is not positive, or if a duration is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::audio::synth::Synth;
+    /// use lilypond::midi::midi_note::MidiNote;
+    /// use lilypond::notation::rhythm::Duration;
+    ///
+    /// let mut synth = Synth::new();
+    /// synth.sample_rate = 8000;
+    /// let a4 = MidiNote::new(69).unwrap();
+    ///
+    /// // a quarter note and a quarter rest at 120 beats per minute
+    /// let samples = synth
+    ///     .render(&[(vec![a4], Duration::new(1, 4)), (vec![], Duration::new(1, 4))])
+    ///     .unwrap();
+    /// assert_eq!(samples.len(), 8000);
+    /// assert!(samples[4000..].iter().all(|sample| *sample == 0));
+    /// ```
+    pub fn render(&self, chords: &[(Vec<MidiNote>, Duration)]) -> Result<Vec<i16>, String> {
+        let chords: Vec<(Vec<f64>, Duration)> = chords
+            .iter()
+            .map(|(notes, duration)| {
+                let frequencies = notes
+                    .iter()
+                    .map(|note| note.frequency(&self.tuning))
+                    .collect();
+                (frequencies, *duration)
+            })
+            .collect();
+        self.render_frequencies(&chords)
+    }
+    /// Attempt to render a sequence of chords, given as the frequencies to
+    /// play at once and a duration in whole notes, to samples.
+    fn render_frequencies(&self, chords: &[(Vec<f64>, Duration)]) -> Result<Vec<i16>, String> {
+        if self.sample_rate == 0 {
+            return Err(String::from("Invalid sample rate 0."));
+        }
+        if !(self.tempo.is_finite() && self.tempo > 0.0) {
+            return Err(format!("Invalid tempo {}.", self.tempo));
+        }
+        if self.beat <= Duration::from_integer(0) {
+            return Err(format!("Invalid beat {}.", self.beat));
+        }
+        let rate = f64::from(self.sample_rate);
+        // the seconds per whole note
+        let whole =
+            60.0 / self.tempo / (f64::from(*self.beat.numer()) / f64::from(*self.beat.denom()));
+        let mut samples = vec![];
+        let mut time = Duration::from_integer(0);
+        for (frequencies, duration) in chords {
+            if *duration < Duration::from_integer(0) {
+                return Err(format!("Invalid duration {}.", duration));
+            }
+            time += duration;
+            // round the end of each chord rather than its length, so that
+            // the rounding errors do not add up
+            let seconds = f64::from(*time.numer()) / f64::from(*time.denom()) * whole;
+            let end = (seconds * rate).round() as usize;
+            let length = end.saturating_sub(samples.len());
+            for index in 0..length {
+                let time = index as f64 / rate;
+                let fade = (time / FADE)
+                    .min((length - index - 1) as f64 / rate / FADE)
+                    .min(1.0);
+                let sample: f64 = frequencies
+                    .iter()
+                    .map(|frequency| self.waveform.sample(*frequency, time, rate / 2.0))
+                    .sum();
+                let sample = sample / frequencies.len().max(1) as f64 * self.volume * fade;
+                samples.push((sample.clamp(-1.0, 1.0) * f64::from(i16::MAX)).round() as i16);
+            }
+        }
+        Ok(samples)
+    }
+    /// Attempt to render the notes and chords of a voice to samples. Tied
+    /// notes are played as one note.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a pitch has no
+    /// [frequency](crate::notation::pitch::Pitch::frequency) in the synthesizer's tuning, or for the
+    /// reasons [`render`](Synth::render) does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::audio::synth::{Synth, Waveform};
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let notes = parse_notes("c'4~ c'8 r8 g'2", NoteNameLanguage::English).unwrap();
+    /// let synth = Synth {
+    ///     waveform: Waveform::Square,
+    ///     sample_rate: 8000,
+    ///     ..Synth::new()
+    /// };
+    ///
+    /// let samples = synth.render_voice(&Voice::from(notes)).unwrap();
+    /// assert_eq!(samples.len(), 16000);
+    /// ```
+    pub fn render_voice(&self, voice: &Voice) -> Result<Vec<i16>, String> {
+        let mut chords: Vec<(Vec<f64>, Duration)> = vec![];
+        let mut tied = false;
+        for event in &voice.events {
+            let (pitches, rhythm, tie) = match event {
+                Event::Note(note) if is_rest(&note.rhythm, &note.pitch) => {
+                    (vec![], &note.rhythm, false)
+                }
+                Event::Note(note) => (vec![&note.pitch], &note.rhythm, note.tie),
+                Event::Chord(chord) if chord.rhythm.duration_type == DurationType::Rest => {
+                    (vec![], &chord.rhythm, false)
+                }
+                Event::Chord(chord) => (chord.pitches.iter().collect(), &chord.rhythm, chord.tie),
+            };
+            let frequencies = pitches
+                .into_iter()
+                .map(|pitch| pitch.frequency(&self.tuning))
+                .collect::<Result<Vec<_>, String>>()?;
+            match chords.last_mut() {
+                Some((last, duration)) if tied && *last == frequencies => {
+                    *duration += rhythm.duration()
+                }
+                _ => chords.push((frequencies, rhythm.duration())),
+            }
+            tied = tie;
+        }
+        self.render_frequencies(&chords)
+    }
+    /// Attempt to render the notes and chords of a voice to a WAV file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the voice cannot be
+    /// [rendered](Synth::render_voice), or if the file cannot be written.
+    pub fn write_voice<P: AsRef<Path>>(&self, path: P, voice: &Voice) -> Result<(), String> {
+        write_wav(path, &self.render_voice(voice)?, self.sample_rate)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::audio::synth::*;
+    use crate::audio::wav::to_wav;
+    use crate::notation::chord::Chord;
+    use crate::notation::note::Note;
+    use crate::notation::pitch::NoteName;
+    use crate::notation::rhythm::Length;
+    use crate::parser::{parse_notes, voice};
+    use crate::tuning::temperament::Temperament;
+    use crate::NoteNameLanguage;
+    use num_rational::Rational32;
+    /// Count the times the samples cross from negative to positive.
+    fn crossings(samples: &[i16]) -> usize {
+        samples
+            .windows(2)
+            .filter(|pair| pair[0] < 0 && pair[1] >= 0)
+            .count()
+    }
+    #[test]
+    fn test_render() {
+        let synth = Synth {
+            sample_rate: 8000,
+            tempo: 60.0,
+            ..Synth::new()
+        };
+        let a4 = MidiNote::new(69).unwrap();
+        // a second of A4 at 440 Hz
+        let samples = synth.render(&[(vec![a4], Duration::new(1, 4))]).unwrap();
+        assert_eq!(samples.len(), 8000);
+        assert_eq!(crossings(&samples), 440);
+        let peak = samples.iter().map(|sample| sample.abs()).max().unwrap();
+        assert_eq!(peak, i16::MAX / 2 + 1);
+        // the note fades in and out
+        assert_eq!(samples[0], 0);
+        assert_eq!(samples[7999], 0);
+
+        // thirds of a beat add up to a whole beat
+        let third = (vec![a4], Duration::new(1, 12));
+        let samples = synth
+            .render(&[third.clone(), third.clone(), third])
+            .unwrap();
+        assert_eq!(samples.len(), 8000);
+    }
+    #[test]
+    fn test_waveforms() {
+        let nyquist = 22050.0;
+        assert_eq!(Waveform::Square.sample(1.0, 0.25, nyquist), 1.0);
+        assert_eq!(Waveform::Square.sample(1.0, 0.75, nyquist), -1.0);
+        assert!((Waveform::Sine.sample(1.0, 0.25, nyquist) - 1.0).abs() < 1e-12);
+        let additive = Waveform::Additive(vec![1.0, 0.0, 1.0]);
+        assert!((additive.sample(1.0, 0.25, nyquist) - 0.0).abs() < 1e-12);
+        assert!((additive.sample(1.0, 1.0 / 12.0, nyquist) - 0.75).abs() < 1e-12);
+        // the third harmonic is too high to be heard
+        assert!((additive.sample(10000.0, 1.0 / 40000.0, nyquist) - 0.5).abs() < 1e-12);
+        assert_eq!(Waveform::Additive(vec![]).sample(1.0, 0.25, nyquist), 0.0);
+    }
+    #[test]
+    fn test_render_voice() {
+        let synth = Synth {
+            sample_rate: 8000,
+            ..Synth::new()
+        };
+        let tied = synth.render_voice(&voice("a'4~ a'4")).unwrap();
+        assert_eq!(tied, synth.render_voice(&voice("a'2")).unwrap());
+        assert_ne!(tied, synth.render_voice(&voice("a'4 a'4")).unwrap());
+        let rests = synth.render_voice(&voice("r4 r8")).unwrap();
+        assert_eq!(rests, vec![0; 6000]);
+        let mut rest = Note::new(NoteName::None);
+        rest.rhythm.duration_type(DurationType::Rest);
+        let mut pitched = Note::new(NoteName::A);
+        pitched.rhythm.duration_type(DurationType::Rest);
+        let mut rest_chord = Chord::new(vec!["A4".parse().unwrap()]);
+        rest_chord.rhythm.duration_type(DurationType::Rest);
+        let mut rests = Voice::new();
+        rests.push(rest);
+        rests.push(pitched);
+        rests.push(rest_chord);
+        assert_eq!(synth.render_voice(&rests).unwrap(), vec![0; 12000]);
+
+        let mut chord = Chord::new(vec!["A4".parse().unwrap(), "A5".parse().unwrap()]);
+        chord.rhythm.length(Length::Half);
+        let mut voice = Voice::new();
+        voice.push(chord);
+        let samples = synth.render_voice(&voice).unwrap();
+        assert_eq!(samples.len(), 8000);
+        assert_eq!(crossings(&samples), 880);
+    }
+    #[test]
+    fn test_render_voice_spelling() {
+        // enharmonic pitches differ in meantone, and quarter tones are kept
+        let tuning = Tuning::new(440.0, Temperament::Meantone(Rational32::new(1, 4))).unwrap();
+        let synth = Synth {
+            sample_rate: 8000,
+            tempo: 60.0,
+            tuning: tuning.clone(),
+            ..Synth::new()
+        };
+        let mut frequencies = vec![];
+        for notes in ["cs'4", "df'4", "c'4", "cqs'4"] {
+            let samples = synth.render_voice(&voice(notes)).unwrap();
+            let pitch = &parse_notes(notes, NoteNameLanguage::English).unwrap()[0].pitch;
+            let frequency = pitch.frequency(&tuning).unwrap();
+            assert!((crossings(&samples) as f64 - frequency).abs() <= 1.0);
+            frequencies.push(crossings(&samples));
+        }
+        assert!(frequencies[0] < frequencies[1]);
+        assert!(frequencies[2] < frequencies[3]);
+    }
+    #[test]
+    fn test_write_voice() {
+        let path = std::env::temp_dir().join("lilypond_test_write_voice.wav");
+        let synth = Synth {
+            sample_rate: 8000,
+            ..Synth::new()
+        };
+        synth.write_voice(&path, &voice("c'4 e' g'2")).unwrap();
+        let samples = synth.render_voice(&voice("c'4 e' g'2")).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), to_wav(&samples, 8000));
+        std::fs::remove_file(&path).unwrap();
+    }
+    #[test]
+    fn test_render_errors() {
+        let mut synth = Synth::new();
+        let a4 = MidiNote::new(69).unwrap();
+        assert_eq!(
+            synth.render(&[(vec![a4], Duration::new(-1, 4))]),
+            Err(String::from("Invalid duration -1/4."))
+        );
+        synth.beat = Duration::from_integer(0);
+        assert_eq!(synth.render(&[]), Err(String::from("Invalid beat 0.")));
+        synth.beat = Duration::new(-1, 4);
+        assert_eq!(synth.render(&[]), Err(String::from("Invalid beat -1/4.")));
+        synth.tempo = 0.0;
+        assert_eq!(synth.render(&[]), Err(String::from("Invalid tempo 0.")));
+        synth.sample_rate = 0;
+        assert_eq!(
+            synth.render(&[]),
+            Err(String::from("Invalid sample rate 0."))
+        );
+    }
+}
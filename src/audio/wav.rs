@@ -0,0 +1,90 @@
+//! Encoding of audio as 16-bit PCM [WAV](https://en.wikipedia.org/wiki/WAV)
+//! files.
+
+use std::path::Path;
+
+/// Encode mono 16-bit samples as the bytes of a WAV file.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::audio::wav::to_wav;
+///
+/// let wav = to_wav(&[0, i16::MAX, i16::MIN], 8000);
+///
+/// assert_eq!(&wav[..4], b"RIFF");
+/// assert_eq!(wav.len(), 44 + 6);
+/// assert_eq!(&wav[44..], [0x00, 0x00, 0xff, 0x7f, 0x00, 0x80]);
+/// ```
+pub fn to_wav(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + samples.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // PCM, in one channel
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    // the bytes per second, the bytes per sample and the bits per sample
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+/// Attempt to write mono 16-bit samples to a WAV file.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the file cannot be written.
+pub fn write_wav<P: AsRef<Path>>(path: P, samples: &[i16], sample_rate: u32) -> Result<(), String> {
+    std::fs::write(&path, to_wav(samples, sample_rate)).map_err(|e| {
+        format!(
+            "Could not write WAV file {}: {}.",
+            path.as_ref().display(),
+            e
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::audio::wav::*;
+    #[test]
+    fn test_to_wav() {
+        let wav = to_wav(&[1, -2], 44100);
+        assert_eq!(
+            wav,
+            [
+                b"RIFF".as_ref(),
+                &[40, 0, 0, 0],
+                b"WAVE",
+                b"fmt ",
+                &[16, 0, 0, 0, 1, 0, 1, 0],
+                &[0x44, 0xac, 0, 0, 0x88, 0x58, 0x01, 0, 2, 0, 16, 0],
+                b"data",
+                &[4, 0, 0, 0, 1, 0, 0xfe, 0xff],
+            ]
+            .concat()
+        );
+    }
+    #[test]
+    fn test_write_wav() {
+        let path = std::env::temp_dir().join("lilypond_test_write_wav.wav");
+        write_wav(&path, &[0, 100, -100], 8000).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), to_wav(&[0, 100, -100], 8000));
+        std::fs::remove_file(&path).unwrap();
+        let missing = std::env::temp_dir().join("lilypond_missing_directory/preview.wav");
+        assert!(write_wav(missing, &[], 8000)
+            .unwrap_err()
+            .starts_with("Could not write WAV file"));
+    }
+}
@@ -0,0 +1,299 @@
+//! Export of tunes as [ABC notation](https://abcnotation.com/wiki/abc:standard:v2.1).
+
+use crate::export::{layout, Alterations, Placed};
+use crate::import::abc::Tune;
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::clef::Clef;
+use crate::notation::key::KeySignature;
+use crate::notation::pitch::Pitch;
+use crate::notation::rhythm::Duration;
+use crate::notation::score::{Staff, StaffChange};
+use crate::notation::voice::Event;
+
+/// Attempt to write a tune as ABC notation, with a unit note length of an
+/// eighth. Changes of clef, key signature or time signature are written as
+/// inline fields, and beamed notes are written without spaces between them.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the tune's staff does not have exactly one
+/// voice, or if it cannot be [laid out](crate::notation::score::Score::to_lilypond)
+/// in measures.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::export::abc::to_abc;
+/// use lilypond::import::abc::Tune;
+/// use lilypond::notation::score::Staff;
+/// use lilypond::notation::voice::Voice;
+/// use lilypond::parser::parse_notes;
+/// use lilypond::NoteNameLanguage;
+///
+/// let notes = parse_notes("c'8[ d'] ef'4 g'2", NoteNameLanguage::English).unwrap();
+/// let tune = Tune {
+///     number: 1,
+///     title: Some(String::from("Scale")),
+///     staff: Staff::from(Voice::from(notes)),
+/// };
+///
+/// assert_eq!(
+///     to_abc(&tune).unwrap(),
+///     "X:1\nT:Scale\nM:4/4\nL:1/8\nK:C\nCD _E2 G4 |]\n"
+/// );
+/// ```
+pub fn to_abc(tune: &Tune) -> Result<String, String> {
+    let staff = &tune.staff;
+    if staff.voices.len() != 1 {
+        return Err(format!(
+            "Expected one voice to write as ABC, found {}.",
+            staff.voices.len()
+        ));
+    }
+    let mut output = format!("X:{}\n", tune.number);
+    if let Some(title) = &tune.title {
+        output.push_str(&format!("T:{}\n", title));
+    }
+    output.push_str(&format!("M:{}\nL:1/8\n", staff.time_signature));
+    output.push_str(&format!("K:{}", key_name(&staff.key_signature)));
+    if staff.clef != Clef::Treble {
+        output.push_str(&format!(" clef={}", clef_name(staff.clef)));
+    }
+    output.push('\n');
+
+    let measures = layout(staff)?;
+    for (index, measure) in measures.iter().enumerate() {
+        let mut items = vec![];
+        if index > 0 {
+            items.extend(inline_fields(staff, index));
+        }
+        let events = &measure[0];
+        if events.is_empty() {
+            let length = match staff.partial {
+                Some(partial) if index == 0 => partial,
+                _ => staff.time_signature_at(index).measure_duration(),
+            };
+            if length == staff.time_signature_at(index).measure_duration() {
+                items.push(String::from("Z"));
+            } else {
+                items.push(format!("z{}", abc_length(length)));
+            }
+        }
+        let mut alterations = Alterations::new(staff.key_signature_at(index));
+        let mut beamed = false;
+        for placed in events {
+            let event = abc_event(placed, &mut alterations);
+            // notes in a beam are written without spaces between them
+            match items.last_mut() {
+                Some(last) if beamed => last.push_str(&event),
+                _ => items.push(event),
+            }
+            match beam(&placed.event) {
+                Some(Beam::Start) => beamed = true,
+                Some(Beam::End) => beamed = false,
+                None => {}
+            }
+        }
+        output.push_str(&items.join(" "));
+        output.push_str(if index + 1 == measures.len() {
+            " |]\n"
+        } else if (index + 1) % 4 == 0 {
+            " |\n"
+        } else {
+            " | "
+        });
+    }
+    Ok(output)
+}
+
+/// Write the tonic of the major key with a key signature, e.g. `Bb`.
+fn key_name(key_signature: &KeySignature) -> String {
+    let tonic = key_signature.major_tonic();
+    let letter = abc_letter(&tonic).to_ascii_uppercase();
+    match tonic.accidental.alteration() {
+        alteration if alteration > Duration::from_integer(0) => format!("{}#", letter),
+        alteration if alteration < Duration::from_integer(0) => format!("{}b", letter),
+        _ => letter.to_string(),
+    }
+}
+
+/// Return the ABC name of a clef, e.g. `treble-8`.
+fn clef_name(clef: Clef) -> &'static str {
+    match clef {
+        Clef::TrebleOctaveDown => "treble-8",
+        Clef::Percussion => "perc",
+        clef => clef.name(),
+    }
+}
+
+/// Write the changes starting a measure as inline fields like `[M:3/4]`.
+fn inline_fields(staff: &Staff, index: usize) -> Vec<String> {
+    let mut fields = vec![];
+    let mut key = None;
+    for (_, change) in staff.changes.iter().filter(|(at, _)| *at == index) {
+        match change {
+            StaffChange::TimeSignature(time_signature) => {
+                fields.push(format!("[M:{}]", time_signature))
+            }
+            StaffChange::KeySignature(_) => {
+                key.get_or_insert_with(|| key_name(staff.key_signature_at(index)));
+            }
+            StaffChange::Clef(clef) => {
+                let key = key.get_or_insert_with(|| key_name(staff.key_signature_at(index)));
+                key.push_str(&format!(" clef={}", clef_name(*clef)));
+            }
+        }
+    }
+    if let Some(key) = key {
+        fields.push(format!("[K:{}]", key));
+    }
+    fields
+}
+
+fn beam(event: &Event) -> Option<Beam> {
+    match event {
+        Event::Note(note) => note.beam,
+        Event::Chord(chord) => chord.beam,
+    }
+}
+
+/// Write a note, chord or rest with its decorations and tie.
+fn abc_event(placed: &Placed, alterations: &mut Alterations) -> String {
+    let (articulations, dynamic, tie) = match &placed.event {
+        Event::Note(note) => (&note.articulations, note.dynamic, note.tie),
+        Event::Chord(chord) => (&chord.articulations, chord.dynamic, chord.tie),
+    };
+    let mut output = String::new();
+    if let Some(dynamic) = dynamic {
+        output.push_str(&format!("!{}!", dynamic.name()));
+    }
+    for articulation in articulations {
+        output.push_str(decoration(*articulation));
+    }
+    let length = abc_length(placed.rhythm().duration());
+    let pitches = placed.pitches();
+    match pitches.as_slice() {
+        [] => output.push('z'),
+        [pitch] => output.push_str(&abc_pitch(pitch, alterations)),
+        pitches => {
+            output.push('[');
+            for pitch in pitches {
+                output.push_str(&abc_pitch(pitch, alterations));
+            }
+            output.push(']');
+        }
+    }
+    output.push_str(&length);
+    if tie {
+        output.push('-');
+    }
+    output
+}
+
+fn decoration(articulation: Articulation) -> &'static str {
+    match articulation {
+        Articulation::Accent => "!accent!",
+        Articulation::Marcato => "!marcato!",
+        Articulation::Staccatissimo => "!wedge!",
+        Articulation::Staccato => ".",
+        Articulation::Tenuto => "!tenuto!",
+        Articulation::Portato => "!tenuto!.",
+        Articulation::Fermata => "!fermata!",
+        Articulation::Trill => "!trill!",
+    }
+}
+
+fn abc_letter(pitch: &Pitch) -> char {
+    let index = pitch.note_name.diatonic_index().unwrap_or(0);
+    "cdefgab".chars().nth(index as usize).unwrap_or('c')
+}
+
+/// Write a pitch with an accidental if the measure needs one, e.g. `^f'`.
+fn abc_pitch(pitch: &Pitch, alterations: &mut Alterations) -> String {
+    let mut output = String::new();
+    if alterations.write(pitch) {
+        let alteration = pitch.accidental.alteration();
+        let sign = if alteration < Duration::from_integer(0) {
+            '_'
+        } else {
+            '^'
+        };
+        let size = if alteration < Duration::from_integer(0) {
+            -alteration
+        } else {
+            alteration
+        };
+        if size == Duration::from_integer(0) {
+            output.push('=');
+        } else if size.is_integer() {
+            for _ in 0..*size.numer() {
+                output.push(sign);
+            }
+        } else {
+            output.push_str(&format!("{}{}/{}", sign, size.numer(), size.denom()));
+        }
+    }
+    let letter = abc_letter(pitch);
+    let octave = pitch.octave.number().unwrap_or(4);
+    if octave >= 5 {
+        output.push(letter);
+        output.push_str(&"'".repeat((octave - 5) as usize));
+    } else {
+        output.push(letter.to_ascii_uppercase());
+        output.push_str(&",".repeat((4 - octave) as usize));
+    }
+    output
+}
+
+/// Write a duration as a multiple of the unit note length, an eighth.
+fn abc_length(duration: Duration) -> String {
+    let length = duration * 8;
+    match (*length.numer(), *length.denom()) {
+        (1, 1) => String::new(),
+        (numerator, 1) => numerator.to_string(),
+        (1, 2) => String::from("/"),
+        (1, denominator) => format!("/{}", denominator),
+        (numerator, denominator) => format!("{}/{}", numerator, denominator),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::export::abc::*;
+    use crate::import::abc::from_abc;
+    use crate::notation::voice::Voice;
+    #[test]
+    fn test_to_abc() {
+        let tunes = from_abc(include_str!("../../tests/fixtures/tunes.abc")).unwrap();
+        let abc: Vec<String> = tunes.iter().map(|tune| to_abc(tune).unwrap()).collect();
+        assert_eq!(
+            abc.join("\n"),
+            include_str!("../../tests/fixtures/tunes_export.abc")
+        );
+        let round_trip = from_abc(&abc.join("\n")).unwrap();
+        assert_eq!(round_trip, tunes);
+    }
+    #[test]
+    fn test_to_abc_errors() {
+        let mut tune = Tune {
+            number: 1,
+            title: None,
+            staff: Staff::new(),
+        };
+        assert_eq!(
+            to_abc(&tune),
+            Err(String::from("Expected one voice to write as ABC, found 0."))
+        );
+        tune.staff.voices = vec![Voice::new(), Voice::new()];
+        assert!(to_abc(&tune).is_err());
+    }
+    #[test]
+    fn test_abc_length() {
+        assert_eq!(abc_length(Duration::new(1, 8)), "");
+        assert_eq!(abc_length(Duration::new(3, 4)), "6");
+        assert_eq!(abc_length(Duration::new(1, 16)), "/");
+        assert_eq!(abc_length(Duration::new(1, 32)), "/4");
+        assert_eq!(abc_length(Duration::new(3, 16)), "3/2");
+    }
+}
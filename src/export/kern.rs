@@ -0,0 +1,349 @@
+//! Export of scores as [Humdrum](https://www.humdrum.org/) `**kern` spines.
+
+use std::collections::BTreeMap;
+
+use crate::export::{layout, Placed};
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::clef::Clef;
+use crate::notation::key::KeySignature;
+use crate::notation::pitch::Pitch;
+use crate::notation::rhythm::{Duration, Rhythm};
+use crate::notation::score::{Score, Staff, StaffChange};
+use crate::notation::voice::Event;
+
+/// Attempt to write a score as Humdrum `**kern`, with a spine for each staff
+/// from the lowest staff on the left to the highest on the right. A staff
+/// with more than one voice is split into sub-spines with `*^` at the start
+/// and joined with `*v` at the end. Dynamics are left out, since Humdrum
+/// writes them in separate `**dynam` spines.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the score has no staves, if a staff cannot be
+/// [laid out](crate::notation::score::Score::to_lilypond) in measures, or if
+/// a pitch is microtonal.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::export::kern::to_kern;
+/// use lilypond::notation::score::{Score, Staff};
+/// use lilypond::notation::voice::Voice;
+/// use lilypond::parser::parse_notes;
+/// use lilypond::NoteNameLanguage;
+///
+/// let notes = parse_notes("cs''8[ bf,] r4 c2", NoteNameLanguage::English).unwrap();
+/// let score = Score::from(Staff::from(Voice::from(notes)));
+///
+/// assert_eq!(
+///     to_kern(&score).unwrap(),
+///     "**kern\n*staff1\n*clefG2\n*k[]\n*M4/4\n8cc#L\n8BB-J\n4r\n2C\n==\n*-\n"
+/// );
+/// ```
+pub fn to_kern(score: &Score) -> Result<String, String> {
+    if score.staves.is_empty() {
+        return Err(String::from(
+            "Expected at least one staff to write as **kern.",
+        ));
+    }
+    let staves: Vec<&Staff> = score.staves.iter().rev().collect();
+    let layouts = staves
+        .iter()
+        .map(|staff| layout(staff))
+        .collect::<Result<Vec<_>, String>>()?;
+    let widths: Vec<usize> = staves
+        .iter()
+        .map(|staff| staff.voices.len().max(1))
+        .collect();
+    let mut columns = vec![1; staves.len()];
+    let mut records = vec![];
+    let each = |field: &dyn Fn(usize, &Staff) -> String, columns: &[usize]| {
+        let mut fields = vec![];
+        for (index, staff) in staves.iter().enumerate() {
+            for _ in 0..columns[index] {
+                fields.push(field(index, staff));
+            }
+        }
+        fields.join("\t")
+    };
+
+    records.push(each(&|_, _| String::from("**kern"), &columns));
+    records.push(each(
+        &|index, _| format!("*staff{}", staves.len() - index),
+        &columns,
+    ));
+    records.push(each(
+        &|_, staff| format!("*clef{}", kern_clef(staff.clef)),
+        &columns,
+    ));
+    records.push(each(
+        &|_, staff| format!("*k[{}]", kern_key(&staff.key_signature)),
+        &columns,
+    ));
+    records.push(each(
+        &|_, staff| format!("*M{}", staff.time_signature),
+        &columns,
+    ));
+    // split the last sub-spine of each staff until it has a spine per voice
+    while columns != widths {
+        let mut fields = vec![];
+        for (index, count) in columns.iter_mut().enumerate() {
+            for column in 0..*count {
+                let split = *count < widths[index] && column + 1 == *count;
+                fields.push(if split { "*^" } else { "*" });
+            }
+            if *count < widths[index] {
+                *count += 1;
+            }
+        }
+        records.push(fields.join("\t"));
+    }
+
+    let count = layouts.iter().map(Vec::len).max().unwrap_or(0);
+    for measure in 0..count {
+        if measure > 0 {
+            records.push(each(
+                &|_, staff| {
+                    let number = if staff.partial.is_some() {
+                        measure
+                    } else {
+                        measure + 1
+                    };
+                    format!("={}", number)
+                },
+                &columns,
+            ));
+            for kind in 0..3 {
+                let record = each(
+                    &|_, staff| {
+                        staff
+                            .changes
+                            .iter()
+                            .filter(|(index, _)| *index == measure)
+                            .find_map(|(_, change)| match (kind, change) {
+                                (0, StaffChange::Clef(clef)) => {
+                                    Some(format!("*clef{}", kern_clef(*clef)))
+                                }
+                                (1, StaffChange::KeySignature(key_signature)) => {
+                                    Some(format!("*k[{}]", kern_key(key_signature)))
+                                }
+                                (2, StaffChange::TimeSignature(time_signature)) => {
+                                    Some(format!("*M{}", time_signature))
+                                }
+                                _ => None,
+                            })
+                            .unwrap_or_else(|| String::from("*"))
+                    },
+                    &columns,
+                );
+                if record.split('\t').any(|field| field != "*") {
+                    records.push(record);
+                }
+            }
+        }
+
+        // the tokens of each column by their time in the measure
+        let mut tokens: Vec<BTreeMap<Duration, String>> = vec![];
+        for (index, staff) in staves.iter().enumerate() {
+            let length = match staff.partial {
+                Some(partial) if measure == 0 => partial,
+                _ => staff.time_signature_at(measure).measure_duration(),
+            };
+            for voice in 0..widths[index] {
+                let events = layouts[index]
+                    .get(measure)
+                    .and_then(|voices| voices.get(voice))
+                    .cloned()
+                    .unwrap_or_default();
+                let mut column = BTreeMap::new();
+                let mut time = Duration::from_integer(0);
+                if events.is_empty() {
+                    for rhythm in Rhythm::tied(length)? {
+                        column.insert(time, format!("{}r", kern_duration(&rhythm)));
+                        time += rhythm.duration();
+                    }
+                }
+                for placed in &events {
+                    column.insert(time, kern_token(placed)?);
+                    time += placed.rhythm().duration();
+                }
+                tokens.push(column);
+            }
+        }
+        let mut times: Vec<&Duration> = tokens.iter().flat_map(|column| column.keys()).collect();
+        times.sort();
+        times.dedup();
+        for time in times {
+            let fields: Vec<&str> = tokens
+                .iter()
+                .map(|column| column.get(time).map_or(".", String::as_str))
+                .collect();
+            records.push(fields.join("\t"));
+        }
+    }
+
+    records.push(each(&|_, _| String::from("=="), &columns));
+    // join the sub-spines of one staff at a time, since adjacent `*v` join
+    // into one spine
+    for index in 0..staves.len() {
+        if columns[index] > 1 {
+            records.push(each(
+                &|other, _| String::from(if other == index { "*v" } else { "*" }),
+                &columns,
+            ));
+            columns[index] = 1;
+        }
+    }
+    records.push(each(&|_, _| String::from("*-"), &columns));
+    Ok(records
+        .iter()
+        .map(|record| format!("{}\n", record))
+        .collect())
+}
+
+/// Write a clef like `G2`, `Gv2` or `F4`, or `X` for a percussion clef.
+fn kern_clef(clef: Clef) -> String {
+    if clef == Clef::Percussion {
+        return String::from("X");
+    }
+    let octave = match clef.octave_change() {
+        -1 => "v",
+        1 => "^",
+        _ => "",
+    };
+    format!("{}{}{}", clef.sign(), octave, clef.line())
+}
+
+/// Write the altered notes of a key signature in order, like `f#c#`.
+fn kern_key(key_signature: &KeySignature) -> String {
+    let fifths = key_signature.fifths();
+    if fifths >= 0 {
+        let sharps = ["f#", "c#", "g#", "d#", "a#", "e#", "b#"];
+        sharps[..fifths as usize].concat()
+    } else {
+        let flats = ["b-", "e-", "a-", "d-", "g-", "c-", "f-"];
+        flats[..(-fifths) as usize].concat()
+    }
+}
+
+/// Write a rhythm as the reciprocal of its length and its dots, like `4.`.
+fn kern_duration(rhythm: &Rhythm) -> String {
+    format!(
+        "{}{}",
+        rhythm.length.as_u16(),
+        ".".repeat(usize::from(rhythm.dots.get_num_dots()))
+    )
+}
+
+/// Write a pitch with its letter repeated for each octave away from middle
+/// C, like `cc#` for cs'' or `BB-` for bf,.
+fn kern_pitch(pitch: &Pitch) -> Result<String, String> {
+    let alteration = pitch.accidental.alteration();
+    if !alteration.is_integer() {
+        return Err(String::from(
+            "Microtonal pitches cannot be written as **kern.",
+        ));
+    }
+    let index = pitch.note_name.diatonic_index().unwrap_or(0);
+    let letter = "cdefgab".chars().nth(index as usize).unwrap_or('c');
+    let octave = pitch.octave.number().unwrap_or(4);
+    let mut output = if octave >= 4 {
+        letter.to_string().repeat((octave - 3) as usize)
+    } else {
+        letter
+            .to_ascii_uppercase()
+            .to_string()
+            .repeat((4 - octave) as usize)
+    };
+    let alteration = *alteration.numer();
+    let sign = if alteration > 0 { "#" } else { "-" };
+    output.push_str(&sign.repeat(alteration.unsigned_abs() as usize));
+    Ok(output)
+}
+
+/// Write a note, chord or rest with its tie, articulations and beam. In a
+/// chord, the articulations and beam go on the first note.
+fn kern_token(placed: &Placed) -> Result<String, String> {
+    let duration = kern_duration(placed.rhythm());
+    let pitches = placed.pitches();
+    if pitches.is_empty() {
+        return Ok(format!("{}r", duration));
+    }
+    let (prefix, suffix) = match (placed.tie_stop, placed.tie_start()) {
+        (false, true) => ("[", ""),
+        (true, true) => ("", "_"),
+        (true, false) => ("", "]"),
+        (false, false) => ("", ""),
+    };
+    let (articulations, beam) = match &placed.event {
+        Event::Note(note) => (&note.articulations, note.beam),
+        Event::Chord(chord) => (&chord.articulations, chord.beam),
+    };
+    let mut notes = vec![];
+    for (index, pitch) in pitches.iter().enumerate() {
+        let mut note = format!("{}{}{}", prefix, duration, kern_pitch(pitch)?);
+        if index == 0 {
+            for articulation in articulations {
+                note.push_str(kern_articulation(*articulation));
+            }
+            match beam {
+                Some(Beam::Start) => note.push('L'),
+                Some(Beam::End) => note.push('J'),
+                None => {}
+            }
+        }
+        note.push_str(suffix);
+        notes.push(note);
+    }
+    Ok(notes.join(" "))
+}
+
+fn kern_articulation(articulation: Articulation) -> &'static str {
+    match articulation {
+        Articulation::Accent => "^",
+        Articulation::Marcato => "^^",
+        Articulation::Staccatissimo => "`",
+        Articulation::Staccato => "'",
+        Articulation::Tenuto => "~",
+        Articulation::Portato => "~'",
+        Articulation::Fermata => ";",
+        Articulation::Trill => "t",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::export::kern::*;
+    use crate::import::kern::from_kern;
+    use crate::notation::pitch::{Accidental, NoteName, Octave};
+    #[test]
+    fn test_to_kern() {
+        let score = from_kern(include_str!("../../tests/fixtures/minuet.krn")).unwrap();
+        let kern = to_kern(&score).unwrap();
+        assert_eq!(kern, include_str!("../../tests/fixtures/minuet_export.krn"));
+        assert_eq!(to_kern(&from_kern(&kern).unwrap()).unwrap(), kern);
+    }
+    #[test]
+    fn test_kern_pitch() {
+        let mut pitch = Pitch::new(NoteName::C);
+        pitch.octave(Octave::S5);
+        pitch.accidental(Accidental::Sharp);
+        assert_eq!(kern_pitch(&pitch), Ok(String::from("cc#")));
+        pitch.octave(Octave::S2);
+        pitch.accidental(Accidental::DoubleFlat);
+        assert_eq!(kern_pitch(&pitch), Ok(String::from("CC--")));
+        pitch.accidental(Accidental::QuarterSharp);
+        assert!(kern_pitch(&pitch).is_err());
+    }
+    #[test]
+    fn test_kern_key() {
+        assert_eq!(kern_key(&KeySignature::from_fifths(2).unwrap()), "f#c#");
+        assert_eq!(kern_key(&KeySignature::from_fifths(-3).unwrap()), "b-e-a-");
+        assert_eq!(kern_key(&KeySignature::default()), "");
+    }
+    #[test]
+    fn test_to_kern_errors() {
+        assert!(to_kern(&Score::new()).is_err());
+    }
+}
@@ -0,0 +1,463 @@
+//! Export of scores as [MEI 5](https://music-encoding.org/guidelines/v5/content/).
+//!
+//! Each staff of a score gets a `<staffDef>` in the score's `<scoreDef>`,
+//! and each voice of a staff is written as a `<layer>` of the staff in every
+//! measure.
+
+use crate::export::{layout, Alterations, Placed, Xml};
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::clef::Clef;
+use crate::notation::key::KeySignature;
+use crate::notation::pitch::{Accidental, Alteration, Pitch};
+use crate::notation::rhythm::Duration;
+use crate::notation::score::{Score, Staff, StaffChange};
+use crate::notation::time::TimeSignature;
+use crate::notation::voice::Event;
+
+const SCHEMA: &str = "<?xml-model href=\"https://music-encoding.org/schema/5.0/mei-all.rng\" \
+                      type=\"application/xml\" \
+                      schematypens=\"http://relaxng.org/ns/structure/1.0\"?>";
+
+const NAMESPACE: &str = "http://www.music-encoding.org/ns/mei";
+
+/// Attempt to write a score as an MEI 5 document, with a staff definition
+/// for each staff and a layer for each of its voices.
+///
+/// The score definition sets each staff's clef, key signature and time
+/// signature, and a new score definition before a measure writes the
+/// changes starting in it. Notes that cross a barline are split into tied
+/// notes, and dynamics and trills are written as control events with the
+/// beat they start on. A pickup is written as measure 0, marked as not
+/// conforming to the meter.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the score has no staves, if a staff's time
+/// signature is invalid, or if a pitch has no octave.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::export::mei::to_mei;
+/// use lilypond::notation::score::{Score, Staff};
+/// use lilypond::notation::voice::Voice;
+/// use lilypond::parser::parse_notes;
+/// use lilypond::NoteNameLanguage;
+///
+/// let voice = Voice::from(parse_notes("c'4 ef' g'2", NoteNameLanguage::English).unwrap());
+/// let mei = to_mei(&Score::from(Staff::from(voice))).unwrap();
+///
+/// assert!(mei.contains("<mei xmlns=\"http://www.music-encoding.org/ns/mei\" meiversion=\"5.0\">"));
+/// assert!(mei.contains("<note pname=\"e\" oct=\"4\" dur=\"4\" accid=\"f\"/>"));
+/// ```
+pub fn to_mei(score: &Score) -> Result<String, String> {
+    if score.staves.is_empty() {
+        return Err(String::from("Cannot write a score without staves."));
+    }
+    let layouts = score
+        .staves
+        .iter()
+        .map(layout)
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut xml = Xml::new();
+    xml.line(SCHEMA);
+    xml.open("mei", &[("xmlns", NAMESPACE), ("meiversion", "5.0")]);
+    xml.open("meiHead", &[]);
+    xml.open("fileDesc", &[]);
+    xml.open("titleStmt", &[]);
+    xml.empty("title", &[]);
+    xml.close("titleStmt");
+    xml.empty("pubStmt", &[]);
+    xml.close("fileDesc");
+    xml.close("meiHead");
+    xml.open("music", &[]);
+    xml.open("body", &[]);
+    xml.open("mdiv", &[]);
+    xml.open("score", &[]);
+    xml.open("scoreDef", &[]);
+    xml.open("staffGrp", &[]);
+    for (index, staff) in score.staves.iter().enumerate() {
+        write_staff_def(
+            &mut xml,
+            index,
+            Some(staff.clef),
+            Some(&staff.key_signature),
+            Some(&staff.time_signature),
+        );
+    }
+    xml.close("staffGrp");
+    xml.close("scoreDef");
+    xml.open("section", &[]);
+    let count = layouts.iter().map(Vec::len).max().unwrap_or(0);
+    let pickup = score.staves[0].partial.is_some();
+    for measure in 0..count {
+        if measure > 0 {
+            write_changes(&mut xml, score, measure);
+        }
+        // a pickup is measure 0, so that the first full measure is measure 1
+        let number = if pickup { measure } else { measure + 1 }.to_string();
+        if pickup && measure == 0 {
+            xml.open("measure", &[("n", &number), ("metcon", "false")]);
+        } else {
+            xml.open("measure", &[("n", &number)]);
+        }
+        let mut controls = vec![];
+        for (index, staff) in score.staves.iter().enumerate() {
+            let n = (index + 1).to_string();
+            xml.open("staff", &[("n", &n)]);
+            let key_signature = staff.key_signature_at(measure);
+            let mut alterations = Alterations::new(key_signature);
+            for voice in 0..staff.voices.len().max(1) {
+                let events = layouts[index]
+                    .get(measure)
+                    .and_then(|voices| voices.get(voice))
+                    .map_or(&[][..], Vec::as_slice);
+                let mut layer = LayerWriter {
+                    xml: &mut xml,
+                    key_signature,
+                    alterations: &mut alterations,
+                    controls: &mut controls,
+                    staff: index + 1,
+                    unit: staff.time_signature_at(measure).denominator,
+                };
+                layer.write(events, voice)?;
+            }
+            xml.close("staff");
+        }
+        for (name, staff, tstamp, text) in controls {
+            let staff = staff.to_string();
+            let attributes = [("staff", staff.as_str()), ("tstamp", tstamp.as_str())];
+            match text {
+                Some(text) => xml.leaf_with(name, &attributes, text),
+                None => xml.empty(name, &attributes),
+            }
+        }
+        xml.close("measure");
+    }
+    xml.close("section");
+    xml.close("score");
+    xml.close("mdiv");
+    xml.close("body");
+    xml.close("music");
+    xml.close("mei");
+    Ok(xml.finish())
+}
+
+/// Write a staff definition with the attributes that are set.
+fn write_staff_def(
+    xml: &mut Xml,
+    index: usize,
+    clef: Option<Clef>,
+    key_signature: Option<&KeySignature>,
+    time_signature: Option<&TimeSignature>,
+) {
+    let mut attributes = vec![("n", (index + 1).to_string())];
+    if let Some(clef) = clef {
+        attributes.push(("lines", String::from("5")));
+        if clef == Clef::Percussion {
+            attributes.push(("clef.shape", String::from("perc")));
+        } else {
+            attributes.push(("clef.shape", clef.sign().to_string()));
+            attributes.push(("clef.line", clef.line().to_string()));
+        }
+        if clef.octave_change() != 0 {
+            let place = if clef.octave_change() < 0 {
+                "below"
+            } else {
+                "above"
+            };
+            attributes.push(("clef.dis", String::from("8")));
+            attributes.push(("clef.dis.place", String::from(place)));
+        }
+    }
+    if let Some(key_signature) = key_signature {
+        let keysig = match key_signature.fifths() {
+            0 => String::from("0"),
+            fifths if fifths > 0 => format!("{}s", fifths),
+            fifths => format!("{}f", -fifths),
+        };
+        attributes.push(("keysig", keysig));
+    }
+    if let Some(time) = time_signature {
+        let count = if time.additive {
+            let groups: Vec<String> = time.groups().iter().map(u8::to_string).collect();
+            groups.join("+")
+        } else {
+            time.numerator.to_string()
+        };
+        attributes.push(("meter.count", count));
+        attributes.push(("meter.unit", time.denominator.to_string()));
+    }
+    xml.empty("staffDef", &borrow(&attributes));
+}
+
+/// Write a score definition with the changes starting in a measure, if any
+/// staff has one.
+fn write_changes(xml: &mut Xml, score: &Score, measure: usize) {
+    let changed: Vec<(usize, &Staff)> = score
+        .staves
+        .iter()
+        .enumerate()
+        .filter(|(_, staff)| staff.changes.iter().any(|(index, _)| *index == measure))
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+    xml.open("scoreDef", &[]);
+    xml.open("staffGrp", &[]);
+    for (index, staff) in changed {
+        let (mut clef, mut key_signature, mut time_signature) = (None, None, None);
+        for (_, change) in staff.changes.iter().filter(|(at, _)| *at == measure) {
+            match change {
+                StaffChange::Clef(new) => clef = Some(*new),
+                StaffChange::KeySignature(new) => key_signature = Some(new),
+                StaffChange::TimeSignature(new) => time_signature = Some(new),
+            }
+        }
+        write_staff_def(xml, index, clef, key_signature, time_signature);
+    }
+    xml.close("staffGrp");
+    xml.close("scoreDef");
+}
+
+fn borrow<'a>(attributes: &'a [(&'a str, String)]) -> Vec<(&'a str, &'a str)> {
+    attributes
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect()
+}
+
+/// A control event of a measure: its element, staff, beat and text.
+type Control = (&'static str, usize, String, Option<&'static str>);
+
+/// A writer of one layer of a staff in a measure.
+struct LayerWriter<'a, 'b> {
+    xml: &'a mut Xml,
+    key_signature: &'b KeySignature,
+    alterations: &'a mut Alterations<'b>,
+    controls: &'a mut Vec<Control>,
+    staff: usize,
+    /// The note value of the meter's beat, to number beats from.
+    unit: u8,
+}
+
+impl LayerWriter<'_, '_> {
+    fn write(&mut self, events: &[Placed], voice: usize) -> Result<(), String> {
+        self.xml.open("layer", &[("n", &(voice + 1).to_string())]);
+        if events.is_empty() {
+            self.xml
+                .empty(if voice == 0 { "mRest" } else { "mSpace" }, &[]);
+        }
+        let mut time = Duration::from_integer(0);
+        let mut beamed = false;
+        for placed in events {
+            let (beam, dynamic, trill) = match &placed.event {
+                Event::Note(note) => (
+                    note.beam,
+                    note.dynamic,
+                    note.articulations.contains(&Articulation::Trill),
+                ),
+                Event::Chord(chord) => (
+                    chord.beam,
+                    chord.dynamic,
+                    chord.articulations.contains(&Articulation::Trill),
+                ),
+            };
+            if beam == Some(Beam::Start) && !beamed {
+                self.xml.open("beam", &[]);
+                beamed = true;
+            }
+            self.write_event(placed)?;
+            if beam == Some(Beam::End) && beamed {
+                self.xml.close("beam");
+                beamed = false;
+            }
+            // beats are numbered from 1 in the meter's unit
+            let beat = time * i32::from(self.unit) + 1;
+            let tstamp = if beat.is_integer() {
+                beat.to_integer().to_string()
+            } else {
+                (f64::from(*beat.numer()) / f64::from(*beat.denom())).to_string()
+            };
+            if let Some(dynamic) = dynamic {
+                let control = ("dynam", self.staff, tstamp.clone(), Some(dynamic.name()));
+                self.controls.push(control);
+            }
+            if trill {
+                self.controls.push(("trill", self.staff, tstamp, None));
+            }
+            time += placed.rhythm().duration();
+        }
+        if beamed {
+            self.xml.close("beam");
+        }
+        self.xml.close("layer");
+        Ok(())
+    }
+    fn write_event(&mut self, placed: &Placed) -> Result<(), String> {
+        let rhythm = placed.rhythm();
+        let mut attributes = vec![("dur", rhythm.length.as_u16().to_string())];
+        if rhythm.dots.get_num_dots() > 0 {
+            attributes.push(("dots", rhythm.dots.get_num_dots().to_string()));
+        }
+        let pitches = placed.pitches();
+        if pitches.is_empty() {
+            self.xml.empty("rest", &borrow(&attributes));
+            return Ok(());
+        }
+        let tie = match (placed.tie_stop, placed.tie_start()) {
+            (false, true) => Some("i"),
+            (true, true) => Some("m"),
+            (true, false) => Some("t"),
+            (false, false) => None,
+        };
+        let articulations = match &placed.event {
+            Event::Note(note) => &note.articulations,
+            Event::Chord(chord) => &chord.articulations,
+        };
+        let artic: Vec<&str> = articulations.iter().filter_map(artic_name).collect();
+        let mut marks = vec![];
+        if !artic.is_empty() {
+            marks.push(("artic", artic.join(" ")));
+        }
+        if articulations.contains(&Articulation::Fermata) {
+            marks.push(("fermata", String::from("above")));
+        }
+        if let [pitch] = pitches.as_slice() {
+            let mut note = self.pitch_attributes(pitch)?;
+            note.extend(attributes);
+            note.extend(self.accidental_attributes(pitch, placed.tie_stop));
+            if let Some(tie) = tie {
+                note.push(("tie", tie.to_string()));
+            }
+            note.extend(marks);
+            self.xml.empty("note", &borrow(&note));
+            return Ok(());
+        }
+        attributes.extend(marks);
+        self.xml.open("chord", &borrow(&attributes));
+        for pitch in pitches {
+            let mut note = self.pitch_attributes(pitch)?;
+            note.extend(self.accidental_attributes(pitch, placed.tie_stop));
+            if let Some(tie) = tie {
+                note.push(("tie", tie.to_string()));
+            }
+            self.xml.empty("note", &borrow(&note));
+        }
+        self.xml.close("chord");
+        Ok(())
+    }
+    fn pitch_attributes(&self, pitch: &Pitch) -> Result<Vec<(&'static str, String)>, String> {
+        let pname = pitch
+            .note_name
+            .diatonic_index()
+            .and_then(|index| "cdefgab".chars().nth(index as usize))
+            .ok_or_else(|| String::from("Rests have no pitch."))?;
+        let oct = pitch
+            .octave
+            .number()
+            .ok_or_else(|| format!("Pitch {}{} has no octave.", pname, pitch.octave))?;
+        Ok(vec![("pname", pname.to_string()), ("oct", oct.to_string())])
+    }
+    /// Return the written accidental of a pitch if the measure needs one, or
+    /// otherwise its sounding accidental if it is altered or the key
+    /// signature alters its note. A tied note keeps the accidental of the
+    /// note it is tied from.
+    fn accidental_attributes(
+        &mut self,
+        pitch: &Pitch,
+        tie_stop: bool,
+    ) -> Option<(&'static str, String)> {
+        let alteration = pitch.accidental.alteration();
+        if self.alterations.write(pitch) && !tie_stop {
+            return accid_name(alteration, true).map(|name| ("accid", name.to_string()));
+        }
+        let zero = Alteration::from_integer(0);
+        if alteration != zero || self.key_signature.alteration(pitch.note_name) != zero {
+            return accid_name(alteration, false).map(|name| ("accid.ges", name.to_string()));
+        }
+        None
+    }
+}
+
+/// Return the MEI value of an alteration, written or sounding, e.g. `ff`, or
+/// `None` if MEI has no value for it.
+fn accid_name(alteration: Alteration, written: bool) -> Option<&'static str> {
+    match Accidental::from_alteration(alteration) {
+        Accidental::None => Some("n"),
+        Accidental::Sharp => Some("s"),
+        Accidental::DoubleSharp if written => Some("x"),
+        Accidental::DoubleSharp => Some("ss"),
+        Accidental::Flat => Some("f"),
+        Accidental::DoubleFlat => Some("ff"),
+        Accidental::QuarterSharp => Some("1qs"),
+        Accidental::ThreeQuarterSharp => Some("3qs"),
+        Accidental::QuarterFlat => Some("1qf"),
+        Accidental::ThreeQuarterFlat => Some("3qf"),
+        Accidental::Other(_) => None,
+    }
+}
+
+/// Return the MEI `artic` value of an articulation, or `None` if it is
+/// written elsewhere.
+fn artic_name(articulation: &Articulation) -> Option<&'static str> {
+    match articulation {
+        Articulation::Accent => Some("acc"),
+        Articulation::Marcato => Some("marc"),
+        Articulation::Staccatissimo => Some("stacciss"),
+        Articulation::Staccato => Some("stacc"),
+        Articulation::Tenuto => Some("ten"),
+        Articulation::Portato => Some("ten-stacc"),
+        Articulation::Fermata | Articulation::Trill => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::export::mei::*;
+    use crate::notation::chord::Chord;
+    use crate::notation::key::Key;
+    use crate::notation::rhythm::Length;
+    use crate::parser::{parse_notes, voice};
+    use crate::NoteNameLanguage;
+    #[test]
+    fn test_melody() {
+        let mut melody = voice("r8 bf'8[\\mf c''8 d''8] ef''4-> b'4.. r16 fs'4~ fs'4");
+        let pitches = parse_notes("d' f' bf'", NoteNameLanguage::English).unwrap();
+        let mut chord = Chord::new(pitches.into_iter().map(|note| note.pitch).collect());
+        chord.rhythm.length(Length::Half);
+        chord.articulations.push(Articulation::Fermata);
+        melody.push(chord);
+        let mut staff = Staff::from(melody);
+        staff.key_signature = KeySignature::new(Key::Flats(2)).unwrap();
+        staff.time_signature = TimeSignature::from_fraction(3, 4).unwrap();
+        let expected = include_str!("../../tests/fixtures/melody.mei");
+        assert_eq!(to_mei(&Score::from(staff)), Ok(expected.to_string()));
+    }
+    #[test]
+    fn test_parts() {
+        let mut upper = Staff::from(voice("g'8 c''2 b'4.\\trill"));
+        upper.voices.push(voice("e'4 f'2 g'1 a'4"));
+        upper.partial = Some(Duration::new(1, 8));
+        let mut lower = Staff::from(voice("r8 c,1"));
+        lower.clef = Clef::Bass;
+        lower.partial = Some(Duration::new(1, 8));
+        lower.change(2, StaffChange::Clef(Clef::TrebleOctaveDown));
+        lower.change(
+            2,
+            StaffChange::KeySignature(KeySignature::from_fifths(3).unwrap()),
+        );
+        let mut score = Score::from(upper);
+        score.staves.push(lower);
+        let expected = include_str!("../../tests/fixtures/parts.mei");
+        assert_eq!(to_mei(&score), Ok(expected.to_string()));
+    }
+    #[test]
+    fn test_errors() {
+        assert!(to_mei(&Score::new()).is_err());
+        let mut staff = Staff::from(voice("c4"));
+        staff.time_signature.denominator = 3;
+        assert!(to_mei(&Score::from(staff)).is_err());
+    }
+}
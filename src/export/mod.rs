@@ -0,0 +1,254 @@
+//! Writers for other music notation formats.
+//!
+//! The writers share one traversal of a score: each staff's voices are split
+//! into measures by the staff's time signature, and the measures of all the
+//! voices are lined up so that a format can write them measure by measure.
+
+pub mod abc;
+pub mod kern;
+pub mod mei;
+pub mod musicxml;
+
+use std::collections::HashMap;
+
+use crate::notation::key::KeySignature;
+use crate::notation::measure::{check_partial, split_measures};
+use crate::notation::pitch::{Alteration, NoteName, Octave, Pitch};
+use crate::notation::rhythm::{DurationType, Rhythm};
+use crate::notation::score::{Staff, StaffChange};
+use crate::notation::transform::rhythm;
+use crate::notation::voice::Event;
+
+/// An event placed in a measure of a voice.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Placed {
+    pub event: Event,
+    /// Whether a tie from the previous event of the voice ends on this one.
+    pub tie_stop: bool,
+}
+
+impl Placed {
+    /// Return the rhythm of the event.
+    pub fn rhythm(&self) -> &Rhythm {
+        rhythm(&self.event)
+    }
+    /// Return the pitches of the event, or none if it is a rest.
+    pub fn pitches(&self) -> Vec<&Pitch> {
+        match &self.event {
+            Event::Note(note) if !is_rest(&note.rhythm, &note.pitch) => vec![&note.pitch],
+            Event::Note(_) => vec![],
+            Event::Chord(chord) if chord.rhythm.duration_type == DurationType::Rest => vec![],
+            Event::Chord(chord) => chord.pitches.iter().collect(),
+        }
+    }
+    /// Return whether a tie starts on this event.
+    pub fn tie_start(&self) -> bool {
+        match &self.event {
+            Event::Note(note) => note.tie,
+            Event::Chord(chord) => chord.tie,
+        }
+    }
+}
+
+pub(crate) fn is_rest(rhythm: &Rhythm, pitch: &Pitch) -> bool {
+    rhythm.duration_type == DurationType::Rest || pitch.note_name == NoteName::None
+}
+
+/// Attempt to split the voices of a staff into measures by the staff's time
+/// signatures, returning the events of each voice in each measure. Voices
+/// that end early get empty measures, so every voice has the same number of
+/// measures.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if one of the staff's time signatures or its pickup
+/// is invalid, or if an event crossing a barline cannot be split into notes.
+pub(crate) fn layout(staff: &Staff) -> Result<Vec<Vec<Vec<Placed>>>, String> {
+    staff.time_signature.validate()?;
+    for (_, change) in &staff.changes {
+        if let StaffChange::TimeSignature(time_signature) = change {
+            time_signature.validate()?;
+        }
+    }
+    let length = |index| staff.time_signature_at(index).measure_duration();
+    let first = match staff.partial {
+        Some(partial) => {
+            check_partial(partial, length(0))?;
+            partial
+        }
+        None => length(0),
+    };
+    let mut voices = vec![];
+    for voice in &staff.voices {
+        let measures = split_measures(voice, first, length)?;
+        let mut tie = false;
+        let mut placed = vec![];
+        for measure in measures {
+            let mut events = vec![];
+            for event in measure.events {
+                let tie_stop = tie;
+                tie = match &event {
+                    Event::Note(note) => note.tie,
+                    Event::Chord(chord) => chord.tie,
+                };
+                events.push(Placed { event, tie_stop });
+            }
+            placed.push(events);
+        }
+        voices.push(placed);
+    }
+    let count = voices.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    Ok((0..count)
+        .map(|index| {
+            voices
+                .iter()
+                .map(|measures| measures.get(index).cloned().unwrap_or_default())
+                .collect()
+        })
+        .collect())
+}
+
+/// The alterations written so far in a measure, to decide which notes need
+/// an accidental.
+#[derive(Debug)]
+pub(crate) struct Alterations<'a> {
+    key_signature: &'a KeySignature,
+    written: HashMap<(NoteName, Octave), Alteration>,
+}
+
+impl Alterations<'_> {
+    /// Start a measure in a key signature.
+    pub fn new(key_signature: &KeySignature) -> Alterations<'_> {
+        Alterations {
+            key_signature,
+            written: HashMap::new(),
+        }
+    }
+    /// Write a pitch, returning whether it needs an accidental because its
+    /// alteration differs from the key signature's, or from an earlier
+    /// accidental on the same staff position in the measure.
+    pub fn write(&mut self, pitch: &Pitch) -> bool {
+        let alteration = pitch.accidental.alteration();
+        let previous = self
+            .written
+            .insert((pitch.note_name, pitch.octave), alteration)
+            .unwrap_or_else(|| self.key_signature.alteration(pitch.note_name));
+        previous != alteration
+    }
+}
+
+/// A writer of indented XML.
+#[derive(Debug, Default)]
+pub(crate) struct Xml {
+    output: String,
+    depth: usize,
+}
+
+impl Xml {
+    /// Construct a new writer starting with an XML declaration.
+    pub fn new() -> Xml {
+        Xml {
+            output: String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"),
+            depth: 0,
+        }
+    }
+    /// Write a line at the current depth.
+    pub fn line(&mut self, line: &str) {
+        for _ in 0..self.depth {
+            self.output.push_str("  ");
+        }
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+    /// Open an element with attributes.
+    pub fn open(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        let line = format!("<{}{}>", name, attributes_to_string(attributes));
+        self.line(&line);
+        self.depth += 1;
+    }
+    /// Close the most recently opened element.
+    pub fn close(&mut self, name: &str) {
+        self.depth = self.depth.saturating_sub(1);
+        self.line(&format!("</{}>", name));
+    }
+    /// Write an element containing only text.
+    pub fn leaf<T: ToString>(&mut self, name: &str, text: T) {
+        self.leaf_with(name, &[], text);
+    }
+    /// Write an element with attributes containing only text.
+    pub fn leaf_with<T: ToString>(&mut self, name: &str, attributes: &[(&str, &str)], text: T) {
+        let line = format!(
+            "<{}{}>{}</{}>",
+            name,
+            attributes_to_string(attributes),
+            escape(&text.to_string()),
+            name
+        );
+        self.line(&line);
+    }
+    /// Write an element without content.
+    pub fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        let line = format!("<{}{}/>", name, attributes_to_string(attributes));
+        self.line(&line);
+    }
+    /// Return the XML written so far.
+    pub fn finish(self) -> String {
+        self.output
+    }
+}
+
+fn attributes_to_string(attributes: &[(&str, &str)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, escape(value)))
+        .collect()
+}
+
+/// Escape the characters that are special in XML text and attributes.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::export::*;
+    use crate::notation::voice::Voice;
+    use crate::parser::parse_notes;
+    use crate::NoteNameLanguage;
+    #[test]
+    fn test_layout() {
+        let mut staff = Staff::from(Voice::from(
+            parse_notes("c'2. d'2 e'4", NoteNameLanguage::English).unwrap(),
+        ));
+        staff.voices.push(Voice::from(
+            parse_notes("c1", NoteNameLanguage::English).unwrap(),
+        ));
+        let measures = layout(&staff).unwrap();
+        assert_eq!(measures.len(), 2);
+        assert_eq!(measures[0][0].len(), 2);
+        assert!(measures[0][0][1].tie_start());
+        assert!(measures[1][0][0].tie_stop);
+        assert!(!measures[1][0][1].tie_stop);
+        assert_eq!(measures[0][1].len(), 1);
+        assert!(measures[1][1].is_empty());
+    }
+    #[test]
+    fn test_xml() {
+        let mut xml = Xml::new();
+        xml.open("part", &[("id", "P1")]);
+        xml.leaf("part-name", "Flute & \"Oboe\"");
+        xml.empty("chord", &[]);
+        xml.close("part");
+        assert_eq!(
+            xml.finish(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <part id=\"P1\">\n  \
+             <part-name>Flute &amp; &quot;Oboe&quot;</part-name>\n  \
+             <chord/>\n\
+             </part>\n"
+        );
+    }
+}
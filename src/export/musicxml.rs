@@ -0,0 +1,449 @@
+//! Export of scores as partwise [MusicXML 4.0](https://www.w3.org/2021/06/musicxml40/).
+//!
+//! Each staff of a score becomes a part, and each voice of a staff is
+//! written measure by measure, separated by `<backup>` elements.
+
+use crate::export::{layout, Alterations, Placed, Xml};
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::dynamic::Dynamic;
+use crate::notation::pitch::{Accidental, Alteration, Pitch};
+use crate::notation::rhythm::{Duration, Length};
+use crate::notation::score::{Score, Staff, StaffChange};
+use crate::notation::voice::Event;
+
+const DOCTYPE: &str = "<!DOCTYPE score-partwise PUBLIC \
+                       \"-//Recordare//DTD MusicXML 4.0 Partwise//EN\" \
+                       \"http://www.musicxml.org/dtds/partwise.dtd\">";
+
+/// Attempt to write a score as a partwise MusicXML 4.0 document, with one
+/// part for each staff.
+///
+/// The first measure of each part sets the staff's clef, key signature and
+/// time signature, later measures write the staff's changes, and notes that
+/// cross a barline are split into tied notes. A pickup is written as an
+/// implicit measure numbered 0.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the score has no staves, if a staff's time
+/// signature is invalid, or if a pitch has no octave.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::export::musicxml::to_musicxml;
+/// use lilypond::notation::score::{Score, Staff};
+/// use lilypond::notation::voice::Voice;
+/// use lilypond::parser::parse_notes;
+/// use lilypond::NoteNameLanguage;
+///
+/// let voice = Voice::from(parse_notes("c'4 e' g'2", NoteNameLanguage::English).unwrap());
+/// let musicxml = to_musicxml(&Score::from(Staff::from(voice))).unwrap();
+///
+/// assert!(musicxml.contains("<score-partwise version=\"4.0\">"));
+/// assert!(musicxml.contains("<step>E</step>"));
+/// ```
+pub fn to_musicxml(score: &Score) -> Result<String, String> {
+    if score.staves.is_empty() {
+        return Err(String::from("Cannot write a score without staves."));
+    }
+    let mut xml = Xml::new();
+    xml.line(DOCTYPE);
+    xml.open("score-partwise", &[("version", "4.0")]);
+    xml.open("part-list", &[]);
+    for index in 0..score.staves.len() {
+        xml.open("score-part", &[("id", &format!("P{}", index + 1))]);
+        xml.leaf("part-name", format!("Staff {}", index + 1));
+        xml.close("score-part");
+    }
+    xml.close("part-list");
+    for (index, staff) in score.staves.iter().enumerate() {
+        write_part(&mut xml, staff, index)?;
+    }
+    xml.close("score-partwise");
+    Ok(xml.finish())
+}
+
+fn write_part(xml: &mut Xml, staff: &Staff, index: usize) -> Result<(), String> {
+    let measures = layout(staff)?;
+    let divisions = divisions(&measures);
+    xml.open("part", &[("id", &format!("P{}", index + 1))]);
+    for (index, voices) in measures.iter().enumerate() {
+        // a pickup is measure 0, so that the first full measure is measure 1
+        let number = match staff.partial {
+            Some(_) => index.to_string(),
+            None => (index + 1).to_string(),
+        };
+        let pickup = index == 0 && staff.partial.is_some();
+        if pickup {
+            xml.open("measure", &[("number", &number), ("implicit", "yes")]);
+        } else {
+            xml.open("measure", &[("number", &number)]);
+        }
+        write_attributes(xml, staff, index, divisions);
+        let measure_length = match staff.partial {
+            Some(partial) if pickup => ticks(partial, divisions),
+            _ => ticks(staff.time_signature_at(index).measure_duration(), divisions),
+        };
+        let mut alterations = Alterations::new(staff.key_signature_at(index));
+        let mut position = 0;
+        for (voice, events) in voices.iter().enumerate() {
+            if events.is_empty() {
+                if voice == 0 {
+                    xml.open("note", &[]);
+                    xml.empty("rest", &[("measure", "yes")]);
+                    xml.leaf("duration", measure_length);
+                    xml.leaf("voice", 1);
+                    xml.close("note");
+                    position = measure_length;
+                }
+                continue;
+            }
+            if position > 0 {
+                xml.open("backup", &[]);
+                xml.leaf("duration", position);
+                xml.close("backup");
+                position = 0;
+            }
+            let mut beamed = false;
+            for placed in events {
+                let mut note = NoteWriter {
+                    xml,
+                    placed,
+                    voice: voice + 1,
+                    duration: ticks(placed.rhythm().duration(), divisions),
+                };
+                note.write(&mut alterations, &mut beamed)?;
+                position += note.duration;
+            }
+        }
+        xml.close("measure");
+    }
+    xml.close("part");
+    Ok(())
+}
+
+/// Write the attributes of the staff that are set in a measure: all of them
+/// in the first measure, and only the changes in later ones.
+fn write_attributes(xml: &mut Xml, staff: &Staff, measure: usize, divisions: i32) {
+    let (clef, key_signature, time_signature) = if measure == 0 {
+        (
+            Some(staff.clef),
+            Some(&staff.key_signature),
+            Some(&staff.time_signature),
+        )
+    } else {
+        let mut changed = (None, None, None);
+        for (_, change) in staff.changes.iter().filter(|(index, _)| *index == measure) {
+            match change {
+                StaffChange::Clef(clef) => changed.0 = Some(*clef),
+                StaffChange::KeySignature(key_signature) => changed.1 = Some(key_signature),
+                StaffChange::TimeSignature(time_signature) => changed.2 = Some(time_signature),
+            }
+        }
+        changed
+    };
+    if measure > 0 && clef.is_none() && key_signature.is_none() && time_signature.is_none() {
+        return;
+    }
+    xml.open("attributes", &[]);
+    if measure == 0 {
+        xml.leaf("divisions", divisions);
+    }
+    if let Some(key_signature) = key_signature {
+        xml.open("key", &[]);
+        xml.leaf("fifths", key_signature.fifths());
+        xml.close("key");
+    }
+    if let Some(time) = time_signature {
+        xml.open("time", &[]);
+        if time.additive {
+            let groups: Vec<String> = time.groups().iter().map(u8::to_string).collect();
+            xml.leaf("beats", groups.join("+"));
+        } else {
+            xml.leaf("beats", time.numerator);
+        }
+        xml.leaf("beat-type", time.denominator);
+        xml.close("time");
+    }
+    if let Some(clef) = clef {
+        xml.open("clef", &[]);
+        xml.leaf("sign", clef.sign());
+        if clef.sign() != "percussion" {
+            xml.leaf("line", clef.line());
+        }
+        if clef.octave_change() != 0 {
+            xml.leaf("clef-octave-change", clef.octave_change());
+        }
+        xml.close("clef");
+    }
+    xml.close("attributes");
+}
+
+/// Return the number of divisions of a quarter note needed to write every
+/// duration in the measures as a whole number.
+fn divisions(measures: &[Vec<Vec<Placed>>]) -> i32 {
+    measures
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|placed| (placed.rhythm().duration() * 4).denom().abs())
+        .fold(1, |divisions, denominator| {
+            divisions / gcd(divisions, denominator) * denominator
+        })
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Return a duration in divisions of a quarter note.
+fn ticks(duration: Duration, divisions: i32) -> i32 {
+    (duration * 4 * divisions).to_integer()
+}
+
+/// A writer of the `<note>` elements of one event.
+struct NoteWriter<'a, 'b> {
+    xml: &'a mut Xml,
+    placed: &'b Placed,
+    voice: usize,
+    duration: i32,
+}
+
+impl NoteWriter<'_, '_> {
+    fn write(&mut self, alterations: &mut Alterations, beamed: &mut bool) -> Result<(), String> {
+        let (articulations, beam, dynamic) = match &self.placed.event {
+            Event::Note(note) => (&note.articulations, note.beam, note.dynamic),
+            Event::Chord(chord) => (&chord.articulations, chord.beam, chord.dynamic),
+        };
+        if let Some(dynamic) = dynamic {
+            self.write_dynamic(dynamic);
+        }
+        let beam = match beam {
+            Some(Beam::Start) => {
+                *beamed = true;
+                Some("begin")
+            }
+            Some(Beam::End) => {
+                *beamed = false;
+                Some("end")
+            }
+            None if *beamed => Some("continue"),
+            None => None,
+        };
+        let pitches = self.placed.pitches();
+        if pitches.is_empty() {
+            self.xml.open("note", &[]);
+            self.xml.empty("rest", &[]);
+            self.xml.leaf("duration", self.duration);
+            self.write_type();
+            self.write_notations(articulations, false);
+            self.xml.close("note");
+            return Ok(());
+        }
+        for (index, pitch) in pitches.into_iter().enumerate() {
+            // a tied note keeps the accidental of the note it is tied from
+            let accidental = if alterations.write(pitch) && !self.placed.tie_stop {
+                accidental_name(&pitch.accidental)
+            } else {
+                None
+            };
+
+            self.xml.open("note", &[]);
+            if index > 0 {
+                self.xml.empty("chord", &[]);
+            }
+            self.write_pitch(pitch)?;
+            self.xml.leaf("duration", self.duration);
+            if self.placed.tie_stop {
+                self.xml.empty("tie", &[("type", "stop")]);
+            }
+            if self.placed.tie_start() {
+                self.xml.empty("tie", &[("type", "start")]);
+            }
+            self.write_type();
+            if let Some(accidental) = accidental {
+                self.xml.leaf("accidental", accidental);
+            }
+            if let (Some(beam), 0) = (beam, index) {
+                self.xml.leaf_with("beam", &[("number", "1")], beam);
+            }
+            let articulations = if index == 0 { &articulations[..] } else { &[] };
+            self.write_notations(articulations, true);
+            self.xml.close("note");
+        }
+        Ok(())
+    }
+    fn write_dynamic(&mut self, dynamic: Dynamic) {
+        self.xml.open("direction", &[("placement", "below")]);
+        self.xml.open("direction-type", &[]);
+        self.xml.open("dynamics", &[]);
+        self.xml.empty(dynamic.name(), &[]);
+        self.xml.close("dynamics");
+        self.xml.close("direction-type");
+        self.xml.leaf("voice", self.voice);
+        self.xml.close("direction");
+    }
+    fn write_pitch(&mut self, pitch: &Pitch) -> Result<(), String> {
+        let step = pitch
+            .note_name
+            .diatonic_index()
+            .and_then(|index| "CDEFGAB".chars().nth(index as usize))
+            .ok_or_else(|| String::from("Rests have no pitch."))?;
+        let octave = pitch
+            .octave
+            .number()
+            .ok_or_else(|| format!("Pitch {}{} has no octave.", step, pitch.octave))?;
+        let alteration = pitch.accidental.alteration();
+        self.xml.open("pitch", &[]);
+        self.xml.leaf("step", step);
+        if alteration != Alteration::from_integer(0) {
+            let alter = *alteration.numer() as f64 / *alteration.denom() as f64;
+            self.xml.leaf("alter", alter);
+        }
+        self.xml.leaf("octave", octave);
+        self.xml.close("pitch");
+        Ok(())
+    }
+    fn write_type(&mut self) {
+        let rhythm = self.placed.rhythm();
+        self.xml.leaf("voice", self.voice);
+        self.xml.leaf("type", type_name(rhythm.length));
+        for _ in 0..rhythm.dots.get_num_dots() {
+            self.xml.empty("dot", &[]);
+        }
+    }
+    fn write_notations(&mut self, articulations: &[Articulation], ties: bool) {
+        let tie_stop = ties && self.placed.tie_stop;
+        let tie_start = ties && self.placed.tie_start();
+        let marks: Vec<&str> = articulations.iter().filter_map(articulation_name).collect();
+        let fermata = articulations.contains(&Articulation::Fermata);
+        let trill = articulations.contains(&Articulation::Trill);
+        if !(tie_stop || tie_start || fermata || trill || !marks.is_empty()) {
+            return;
+        }
+        self.xml.open("notations", &[]);
+        if tie_stop {
+            self.xml.empty("tied", &[("type", "stop")]);
+        }
+        if tie_start {
+            self.xml.empty("tied", &[("type", "start")]);
+        }
+        if trill {
+            self.xml.open("ornaments", &[]);
+            self.xml.empty("trill-mark", &[]);
+            self.xml.close("ornaments");
+        }
+        if !marks.is_empty() {
+            self.xml.open("articulations", &[]);
+            for mark in marks {
+                self.xml.empty(mark, &[]);
+            }
+            self.xml.close("articulations");
+        }
+        if fermata {
+            self.xml.empty("fermata", &[]);
+        }
+        self.xml.close("notations");
+    }
+}
+
+/// Return the MusicXML name of a note value, e.g. `eighth`.
+pub(crate) fn type_name(length: Length) -> &'static str {
+    match length {
+        Length::Whole => "whole",
+        Length::Half => "half",
+        Length::Quarter => "quarter",
+        Length::Eighth => "eighth",
+        Length::Sixteenth => "16th",
+        Length::ThirtySecond => "32nd",
+        Length::SixtyFourth => "64th",
+        Length::OneTwentyEighth => "128th",
+    }
+}
+
+/// Return the MusicXML name of a written accidental, e.g. `flat-flat`, or
+/// `None` if MusicXML has no symbol for it.
+pub(crate) fn accidental_name(accidental: &Accidental) -> Option<&'static str> {
+    match Accidental::from_alteration(accidental.alteration()) {
+        Accidental::None => Some("natural"),
+        Accidental::Sharp => Some("sharp"),
+        Accidental::DoubleSharp => Some("double-sharp"),
+        Accidental::Flat => Some("flat"),
+        Accidental::DoubleFlat => Some("flat-flat"),
+        Accidental::QuarterSharp => Some("quarter-sharp"),
+        Accidental::ThreeQuarterSharp => Some("three-quarters-sharp"),
+        Accidental::QuarterFlat => Some("quarter-flat"),
+        Accidental::ThreeQuarterFlat => Some("three-quarters-flat"),
+        Accidental::Other(_) => None,
+    }
+}
+
+/// Return the MusicXML `<articulations>` element of an articulation, or
+/// `None` if it is written elsewhere.
+fn articulation_name(articulation: &Articulation) -> Option<&'static str> {
+    match articulation {
+        Articulation::Accent => Some("accent"),
+        Articulation::Marcato => Some("strong-accent"),
+        Articulation::Staccatissimo => Some("staccatissimo"),
+        Articulation::Staccato => Some("staccato"),
+        Articulation::Tenuto => Some("tenuto"),
+        Articulation::Portato => Some("detached-legato"),
+        Articulation::Fermata | Articulation::Trill => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::export::musicxml::*;
+    use crate::notation::chord::Chord;
+    use crate::notation::clef::Clef;
+    use crate::notation::key::{Key, KeySignature};
+    use crate::notation::time::TimeSignature;
+    use crate::parser::{parse_notes, voice};
+    use crate::NoteNameLanguage;
+    #[test]
+    fn test_melody() {
+        let mut melody = voice("r8 bf'8[\\mf c''8 d''8] ef''4-> b'4.. r16 fs'4~ fs'4");
+        let pitches = parse_notes("d' f' bf'", NoteNameLanguage::English).unwrap();
+        let mut chord = Chord::new(pitches.into_iter().map(|note| note.pitch).collect());
+        chord.rhythm.length(Length::Half);
+        chord.articulations.push(Articulation::Fermata);
+        melody.push(chord);
+        let mut staff = Staff::from(melody);
+        staff.key_signature = KeySignature::new(Key::Flats(2)).unwrap();
+        staff.time_signature = TimeSignature::from_fraction(3, 4).unwrap();
+        let expected = include_str!("../../tests/fixtures/melody.musicxml");
+        assert_eq!(to_musicxml(&Score::from(staff)), Ok(expected.to_string()));
+    }
+    #[test]
+    fn test_parts() {
+        let mut upper = Staff::from(voice("c''2 b'2"));
+        upper.voices.push(voice("e'4 f'2 g'1 a'4"));
+        let mut lower = Staff::from(voice("c,1"));
+        lower.clef = Clef::Bass;
+        lower.time_signature = TimeSignature::from_fraction(2, 2).unwrap();
+        let mut score = Score::from(upper);
+        score.staves.push(lower);
+        let expected = include_str!("../../tests/fixtures/parts.musicxml");
+        assert_eq!(to_musicxml(&score), Ok(expected.to_string()));
+    }
+    #[test]
+    fn test_split_dynamic() {
+        // a note tied over the barline has one dynamic
+        let musicxml = to_musicxml(&Score::from(Staff::from(voice("c'4 d'1\\f")))).unwrap();
+        assert_eq!(musicxml.matches("<direction-type>").count(), 1);
+    }
+    #[test]
+    fn test_errors() {
+        assert!(to_musicxml(&Score::new()).is_err());
+        let mut staff = Staff::from(voice("c4"));
+        staff.time_signature.denominator = 3;
+        assert!(to_musicxml(&Score::from(staff)).is_err());
+    }
+}
@@ -0,0 +1,922 @@
+//! Import of tunes in [ABC notation](https://abcnotation.com/wiki/abc:standard:v2.1).
+//!
+//! A tune's header sets the staff's time signature (`M:`), unit note length
+//! (`L:`), key signature and clef (`K:`); the same fields in the body, or
+//! inline like `[K:D]`, change them from the measure they are in. Repeats,
+//! including first and second endings, are written out in full, since the
+//! notation model has no repeats. Chord symbols, grace notes and slurs are
+//! skipped.
+
+use std::collections::HashMap;
+
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::chord::Chord;
+use crate::notation::clef::Clef;
+use crate::notation::dynamic::Dynamic;
+use crate::notation::key::KeySignature;
+use crate::notation::note::Note;
+use crate::notation::pitch::{Accidental, Alteration, NoteName, Octave, Pitch};
+use crate::notation::rhythm::{Duration, DurationType, Length, Rhythm};
+use crate::notation::score::{Staff, StaffChange};
+use crate::notation::time::TimeSignature;
+use crate::notation::transform::split;
+use crate::notation::voice::{Event, Voice};
+
+/// A tune of an ABC file.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Tune {
+    /// The reference number, from the `X:` field.
+    pub number: u32,
+    /// The title, from the first `T:` field.
+    pub title: Option<String>,
+    /// The music, on a staff with one voice.
+    pub staff: Staff,
+}
+
+/// Attempt to read the tunes of an ABC file. Each tune starts with an `X:`
+/// field and ends at a blank line.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if a tune has no `K:` field, if a field has an
+/// invalid or unsupported value, or if the music has more than one voice,
+/// tuplets, or notes that cannot be represented.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::import::abc::from_abc;
+/// use lilypond::notation::score::Score;
+/// use lilypond::NoteNameLanguage;
+///
+/// let tunes = from_abc("X:1\nT:Scale\nM:2/4\nL:1/8\nK:D\nDEFG|A2 a2|]\n").unwrap();
+///
+/// assert_eq!(tunes[0].title, Some(String::from("Scale")));
+/// assert_eq!(
+///     Score::from(tunes[0].staff.clone()).to_lilypond(NoteNameLanguage::English).unwrap(),
+///     "\\language \"english\"\n\n<<\n  \\new Staff { \\clef treble \\key d \\major \\time 2/4 d'8[ e'8 fs'8 g'8] | a'4 a''4 }\n>>\n"
+/// );
+/// ```
+pub fn from_abc(input: &str) -> Result<Vec<Tune>, String> {
+    let mut tunes = vec![];
+    let mut lines: Vec<&str> = vec![];
+    for line in input.lines() {
+        if line.starts_with("X:") || line.trim().is_empty() {
+            if !lines.is_empty() {
+                tunes.push(read_tune(&lines)?);
+                lines.clear();
+            }
+            if line.starts_with("X:") {
+                lines.push(line);
+            }
+        } else if !lines.is_empty() {
+            lines.push(line);
+        }
+    }
+    if !lines.is_empty() {
+        tunes.push(read_tune(&lines)?);
+    }
+    Ok(tunes)
+}
+
+fn read_tune(lines: &[&str]) -> Result<Tune, String> {
+    let field = lines[0][2..].trim();
+    let number = field
+        .parse()
+        .map_err(|_| format!("Invalid ABC reference number '{}'.", field))?;
+    let mut reader = TuneReader::new(number);
+    let mut header = true;
+    for line in &lines[1..] {
+        let line = match line.find('%') {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut chars = line.chars();
+        if let (Some(name), Some(':')) = (chars.next(), chars.next()) {
+            // in the body, notes may start a line like `A:|`
+            if header && name.is_ascii_alphabetic() || "IKLMPQTUVWmrsw".contains(name) {
+                reader.field(name, &line[2..], header)?;
+                header = header && name != 'K';
+                continue;
+            }
+        }
+        if header {
+            return Err(format!(
+                "Expected a K: field before the music of ABC tune {}.",
+                number
+            ));
+        }
+        reader.music(line)?;
+    }
+    if header {
+        return Err(format!("Expected a K: field in ABC tune {}.", number));
+    }
+    reader.finish()
+}
+
+/// A note, chord or rest as written, before its length is made into
+/// rhythms.
+#[derive(Debug, Clone)]
+struct Written {
+    /// The pitches, or none for a rest.
+    pitches: Vec<Pitch>,
+    duration: Duration,
+    tie: bool,
+    articulations: Vec<Articulation>,
+    dynamic: Option<Dynamic>,
+    /// Whether the note follows another without a space, so that they are
+    /// beamed together.
+    joined: bool,
+}
+
+/// A measure as written between two bar lines.
+#[derive(Debug, Clone, Default)]
+struct WrittenMeasure {
+    events: Vec<Written>,
+    changes: Vec<StaffChange>,
+    start_repeat: bool,
+    end_repeat: bool,
+    /// The ending the measure is part of, e.g. 1 for a first ending.
+    ending: Option<u8>,
+}
+
+struct TuneReader {
+    tune: Tune,
+    unit: Option<Duration>,
+    time_signature: TimeSignature,
+    key_signature: KeySignature,
+    measures: Vec<WrittenMeasure>,
+    measure: WrittenMeasure,
+    /// The alterations of accidentals so far in the measure.
+    alterations: HashMap<(NoteName, Octave), Alteration>,
+    articulations: Vec<Articulation>,
+    dynamic: Option<Dynamic>,
+    /// How much a broken rhythm lengthens or shortens the next note.
+    broken: Option<Duration>,
+    /// Whether the last thing read was a note.
+    after_note: bool,
+    ending: Option<u8>,
+    voice: Option<String>,
+}
+
+impl TuneReader {
+    fn new(number: u32) -> TuneReader {
+        TuneReader {
+            tune: Tune {
+                number,
+                ..Default::default()
+            },
+            unit: None,
+            time_signature: TimeSignature::new(),
+            key_signature: KeySignature::default(),
+            measures: vec![],
+            measure: WrittenMeasure::default(),
+            alterations: HashMap::new(),
+            articulations: vec![],
+            dynamic: None,
+            broken: None,
+            after_note: false,
+            ending: None,
+            voice: None,
+        }
+    }
+    fn error(&self, message: &str) -> String {
+        format!("{} in ABC tune {}.", message, self.tune.number)
+    }
+    fn field(&mut self, name: char, value: &str, header: bool) -> Result<(), String> {
+        let value = value.trim();
+        match name {
+            'T' if header && self.tune.title.is_none() => {
+                self.tune.title = Some(value.to_string());
+            }
+            'M' => {
+                self.time_signature = read_meter(value).map_err(|e| self.error(&e))?;
+                if !header {
+                    let change = StaffChange::TimeSignature(self.time_signature.clone());
+                    self.measure.changes.push(change);
+                }
+            }
+            'L' => self.unit = Some(read_unit(value).map_err(|e| self.error(&e))?),
+            'K' => {
+                let (key_signature, clef) = read_key(value).map_err(|e| self.error(&e))?;
+                self.key_signature = key_signature.clone();
+                if header {
+                    self.tune.staff.key_signature = key_signature;
+                    self.tune.staff.time_signature = self.time_signature.clone();
+                    self.tune.staff.clef = clef.unwrap_or_default();
+                } else {
+                    self.measure
+                        .changes
+                        .push(StaffChange::KeySignature(key_signature));
+                    if let Some(clef) = clef {
+                        self.measure.changes.push(StaffChange::Clef(clef));
+                    }
+                }
+                if self.unit.is_none() {
+                    // the default unit is a sixteenth for meters shorter
+                    // than 3/4, and an eighth otherwise
+                    let meter = self.time_signature.measure_duration();
+                    self.unit = Some(if meter < Duration::new(3, 4) {
+                        Duration::new(1, 16)
+                    } else {
+                        Duration::new(1, 8)
+                    });
+                }
+            }
+            'V' => {
+                let id = value.split_whitespace().next().unwrap_or("").to_string();
+                if self.voice.get_or_insert_with(|| id.clone()) != &id {
+                    return Err(self.error("Multiple voices are not supported"));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    fn music(&mut self, line: &str) -> Result<(), String> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        self.after_note = false;
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).cloned().unwrap_or(' ');
+            match c {
+                '^' | '_' | '=' | 'A'..='G' | 'a'..='g' => {
+                    let (pitch, length) = self.note(&chars, &mut i)?;
+                    self.push(vec![pitch], length)?;
+                    continue;
+                }
+                'z' | 'x' => {
+                    i += 1;
+                    let length = read_length(&chars, &mut i).map_err(|e| self.error(&e))?;
+                    self.push(vec![], length)?;
+                    self.after_note = false;
+                    continue;
+                }
+                'Z' | 'X' => {
+                    i += 1;
+                    let count = read_number(&chars, &mut i).unwrap_or(1);
+                    let length = self.time_signature.measure_duration() / self.unit();
+                    for measure in 0..count {
+                        if measure > 0 {
+                            self.bar("|", None);
+                        }
+                        self.push(vec![], length)?;
+                    }
+                    self.after_note = false;
+                    continue;
+                }
+                '[' if next.is_ascii_alphabetic() && chars.get(i + 2) == Some(&':') => {
+                    let end = match chars[i..].iter().position(|c| *c == ']') {
+                        Some(end) => i + end,
+                        None => return Err(self.error("Expected ']' after an inline field")),
+                    };
+                    let value: String = chars[i + 3..end].iter().collect();
+                    self.field(next, &value, false)?;
+                    i = end + 1;
+                    self.after_note = false;
+                    continue;
+                }
+                '[' if next.is_ascii_digit() => {
+                    i += 1;
+                    let ending = read_number(&chars, &mut i);
+                    self.start_ending(ending);
+                    skip_ending_list(&chars, &mut i);
+                    self.after_note = false;
+                    continue;
+                }
+                '[' if next != '|' => {
+                    self.chord(&chars, &mut i)?;
+                    continue;
+                }
+                '|' | ':' | '[' | ']' => {
+                    let start = i;
+                    while i < chars.len()
+                        && ("|:]".contains(chars[i])
+                            || chars[i] == '[' && chars.get(i + 1) == Some(&'|'))
+                    {
+                        i += 1;
+                    }
+                    let bar: String = chars[start..i].iter().collect();
+                    let ending = read_number(&chars, &mut i);
+                    if ending.is_some() {
+                        skip_ending_list(&chars, &mut i);
+                    }
+                    self.bar(&bar, ending);
+                    self.after_note = false;
+                    continue;
+                }
+                '-' => match self.measure.events.last_mut() {
+                    Some(last) if !last.pitches.is_empty() => last.tie = true,
+                    _ => return Err(self.error("Expected a note before a tie")),
+                },
+                '>' | '<' => {
+                    let start = i;
+                    while i + 1 < chars.len() && chars[i + 1] == c {
+                        i += 1;
+                    }
+                    let shorter = Duration::new(1, 1 << (i - start + 1));
+                    let longer = Duration::from_integer(2) - shorter;
+                    let (before, after) = if c == '>' {
+                        (longer, shorter)
+                    } else {
+                        (shorter, longer)
+                    };
+                    match self.measure.events.last_mut() {
+                        Some(last) => last.duration *= before,
+                        None => return Err(self.error("Expected a note before a broken rhythm")),
+                    }
+                    self.broken = Some(after);
+                    // the next note is still beamed to the last one
+                    i += 1;
+                    continue;
+                }
+                '.' if next != '(' => self.articulations.push(Articulation::Staccato),
+                'H' => self.articulations.push(Articulation::Fermata),
+                'L' => self.articulations.push(Articulation::Accent),
+                'T' => self.articulations.push(Articulation::Trill),
+                '!' | '+' => {
+                    let end = match chars[i + 1..].iter().position(|d| *d == c) {
+                        Some(end) => i + 1 + end,
+                        None => {
+                            return Err(self.error(&format!("Expected '{}' after a decoration", c)))
+                        }
+                    };
+                    let name: String = chars[i + 1..end].iter().collect();
+                    self.decoration(&name);
+                    i = end + 1;
+                    continue;
+                }
+                '"' => {
+                    // chord symbols and annotations
+                    match chars[i + 1..].iter().position(|d| *d == '"') {
+                        Some(end) => i += end + 2,
+                        None => return Err(self.error("Expected '\"' after a chord symbol")),
+                    }
+                    continue;
+                }
+                '{' => {
+                    // grace notes
+                    match chars[i..].iter().position(|d| *d == '}') {
+                        Some(end) => i += end + 1,
+                        None => return Err(self.error("Expected '}' after grace notes")),
+                    }
+                    continue;
+                }
+                '(' if next.is_ascii_digit() => {
+                    return Err(self.error("Tuplets are not supported"));
+                }
+                '&' => return Err(self.error("Voice overlays are not supported")),
+                '(' | ')' | '.' | '~' | 'u' | 'v' | 'M' | 'P' | 'S' | 'O' | 'J' | 'R' | 'y'
+                | '`' | '$' | '\\' | '*' => {}
+                c if c.is_whitespace() => self.after_note = false,
+                c => return Err(self.error(&format!("Unexpected '{}'", c))),
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+    fn unit(&self) -> Duration {
+        self.unit.unwrap_or_else(|| Duration::new(1, 8))
+    }
+    fn decoration(&mut self, name: &str) {
+        if let Some(dynamic) = Dynamic::from_name(name) {
+            self.dynamic = Some(dynamic);
+            return;
+        }
+        let articulation = match name {
+            "accent" | ">" | "emphasis" => Articulation::Accent,
+            "marcato" | "^" => Articulation::Marcato,
+            "wedge" => Articulation::Staccatissimo,
+            "staccato" => Articulation::Staccato,
+            "tenuto" => Articulation::Tenuto,
+            "fermata" => Articulation::Fermata,
+            "trill" => Articulation::Trill,
+            _ => return,
+        };
+        self.articulations.push(articulation);
+    }
+    /// Read a note's pitch and length, starting at its accidental or letter.
+    fn note(&mut self, chars: &[char], i: &mut usize) -> Result<(Pitch, Duration), String> {
+        let mut accidental = None;
+        match chars[*i] {
+            '=' => {
+                accidental = Some(Alteration::from_integer(0));
+                *i += 1;
+            }
+            sign @ '^' | sign @ '_' => {
+                *i += 1;
+                let mut alteration = Alteration::from_integer(1);
+                if chars.get(*i) == Some(&sign) {
+                    alteration = Alteration::from_integer(2);
+                    *i += 1;
+                } else if chars[*i..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count()
+                    < 3
+                    && chars[*i..]
+                        .iter()
+                        .find(|c| !c.is_ascii_digit())
+                        .is_some_and(|c| *c == '/')
+                {
+                    // a microtonal accidental like `^/` or `_3/2`
+                    let numerator = read_number(chars, i).unwrap_or(1);
+                    *i += 1;
+                    let denominator = read_number(chars, i).unwrap_or(2);
+                    if denominator == 0 {
+                        return Err(self.error("Invalid accidental"));
+                    }
+                    alteration = Alteration::new(i32::from(numerator), i32::from(denominator));
+                }
+                accidental = Some(if sign == '^' { alteration } else { -alteration });
+            }
+            _ => {}
+        }
+        let letter = match chars.get(*i) {
+            Some(letter)
+                if letter.is_ascii_alphabetic()
+                    && "abcdefg".contains(letter.to_ascii_lowercase()) =>
+            {
+                *letter
+            }
+            _ => return Err(self.error("Expected a note after an accidental")),
+        };
+        *i += 1;
+        let note_name = NoteName::from_diatonic_index(
+            "cdefgab"
+                .find(letter.to_ascii_lowercase())
+                .expect("the letter is a note name") as i32,
+        );
+        let mut octave = if letter.is_ascii_uppercase() { 4 } else { 5 };
+        while let Some(mark) = chars.get(*i) {
+            match mark {
+                '\'' => octave += 1,
+                ',' => octave -= 1,
+                _ => break,
+            }
+            *i += 1;
+        }
+        let mut pitch = Pitch::new(note_name);
+        pitch.octave(Octave::from_number(octave).ok_or_else(|| self.error("Note out of range"))?);
+        let position = (note_name, pitch.octave);
+        let alteration = match accidental {
+            Some(alteration) => {
+                self.alterations.insert(position, alteration);
+                alteration
+            }
+            None => self
+                .alterations
+                .get(&position)
+                .cloned()
+                .unwrap_or_else(|| self.key_signature.alteration(note_name)),
+        };
+        pitch.accidental(Accidental::from_alteration(alteration));
+        let length = read_length(chars, i).map_err(|e| self.error(&e))?;
+        Ok((pitch, length))
+    }
+    /// Read a chord like `[CEG]2`, starting at its `[`.
+    fn chord(&mut self, chars: &[char], i: &mut usize) -> Result<(), String> {
+        *i += 1;
+        let mut pitches = vec![];
+        let mut length = None;
+        let mut tie = false;
+        loop {
+            match chars.get(*i) {
+                Some(']') => break,
+                Some('-') => {
+                    tie = true;
+                    *i += 1;
+                }
+                Some(c) if c.is_whitespace() || *c == '.' => *i += 1,
+                Some(_) => {
+                    let (pitch, note_length) = self.note(chars, i)?;
+                    pitches.push(pitch);
+                    length.get_or_insert(note_length);
+                }
+                None => return Err(self.error("Expected ']' after a chord")),
+            }
+        }
+        *i += 1;
+        if pitches.is_empty() {
+            return Err(self.error("Expected notes in a chord"));
+        }
+        let outer = read_length(chars, i).map_err(|e| self.error(&e))?;
+        let length = length.unwrap_or_else(|| Duration::from_integer(1));
+        self.push(pitches, length * outer)?;
+        if let Some(last) = self.measure.events.last_mut() {
+            last.tie |= tie;
+        }
+        Ok(())
+    }
+    /// Add a note, chord or rest lasting `length` units.
+    fn push(&mut self, pitches: Vec<Pitch>, length: Duration) -> Result<(), String> {
+        let mut duration = length * self.unit();
+        if let Some(broken) = self.broken.take() {
+            duration *= broken;
+        }
+        let joined = self.after_note && !pitches.is_empty();
+        self.after_note = !pitches.is_empty();
+        self.measure.events.push(Written {
+            pitches,
+            duration,
+            tie: false,
+            articulations: std::mem::take(&mut self.articulations),
+            dynamic: self.dynamic.take(),
+            joined,
+        });
+        Ok(())
+    }
+    fn start_ending(&mut self, ending: Option<u8>) {
+        self.ending = ending;
+        if self.measure.events.is_empty() {
+            self.measure.ending = ending;
+        }
+    }
+    /// End the measure at a bar line like `|`, `:|` or `|]`, starting an
+    /// ending if a number follows it.
+    fn bar(&mut self, bar: &str, ending: Option<u8>) {
+        let end_repeat = bar.starts_with(':');
+        let start_repeat = bar.ends_with(':');
+        if !self.measure.events.is_empty() {
+            let mut measure = std::mem::take(&mut self.measure);
+            measure.end_repeat = end_repeat;
+            self.measures.push(measure);
+        } else if end_repeat {
+            if let Some(last) = self.measures.last_mut() {
+                last.end_repeat = true;
+            }
+        }
+        self.alterations.clear();
+        // an ending lasts until a repeat or a double bar line
+        if end_repeat || start_repeat || bar.len() > 1 {
+            self.ending = None;
+        }
+        if ending.is_some() {
+            self.ending = ending;
+        }
+        self.measure.start_repeat |= start_repeat;
+        self.measure.ending = self.ending;
+    }
+    fn finish(mut self) -> Result<Tune, String> {
+        if !self.measure.events.is_empty() || !self.measure.changes.is_empty() {
+            let measure = std::mem::take(&mut self.measure);
+            self.measures.push(measure);
+        }
+        let mut events = vec![];
+        let mut durations = vec![];
+        for (index, measure) in unfold(&self.measures).into_iter().enumerate() {
+            for change in &measure.changes {
+                self.tune.staff.change(index, change.clone());
+            }
+            durations.push(measure.events.iter().map(|event| event.duration).sum());
+            events.extend(measure.events.iter().cloned());
+        }
+        let length = self.tune.staff.time_signature.measure_duration();
+        if durations.len() > 1 && durations[0] < length && durations[0] > Duration::from_integer(0)
+        {
+            self.tune.staff.partial = Some(durations[0]);
+        }
+        let mut voice = Voice::new();
+        let mut group: Vec<usize> = vec![];
+        for written in events {
+            let pieces = self.events(&written)?;
+            if !written.joined || pieces.len() > 1 || !is_beamable(&pieces[0]) {
+                beam(&mut voice, &group);
+                group.clear();
+            }
+            if pieces.len() == 1 && is_beamable(&pieces[0]) {
+                group.push(voice.events.len());
+            }
+            voice.events.extend(pieces);
+        }
+        beam(&mut voice, &group);
+        self.tune.staff.voices = vec![voice];
+        Ok(self.tune)
+    }
+    /// Make a written note, chord or rest into events, tying notes together
+    /// when no single note lasts as long.
+    fn events(&self, written: &Written) -> Result<Vec<Event>, String> {
+        let rhythms = match Rhythm::from_duration(written.duration) {
+            Ok(rhythm) => vec![rhythm],
+            Err(_) => Rhythm::tied(written.duration).map_err(|_| {
+                self.error(&format!("Unsupported note length {}", written.duration))
+            })?,
+        };
+        let event = match written.pitches.as_slice() {
+            [] => {
+                let mut rest = Note::new(NoteName::None);
+                rest.pitch.octave(Octave::None);
+                rest.rhythm.duration_type(DurationType::Rest);
+                Event::Note(rest)
+            }
+            [pitch] => {
+                let mut note = Note::new(pitch.note_name);
+                note.pitch = pitch.clone();
+                note.tie = written.tie;
+                note.articulations = written.articulations.clone();
+                note.dynamic = written.dynamic;
+                Event::Note(note)
+            }
+            pitches => {
+                let mut chord = Chord::new(pitches.to_vec());
+                chord.tie = written.tie;
+                chord.articulations = written.articulations.clone();
+                chord.dynamic = written.dynamic;
+                Event::Chord(chord)
+            }
+        };
+        Ok(split(&event, rhythms))
+    }
+}
+
+fn is_beamable(event: &Event) -> bool {
+    let rhythm = match event {
+        Event::Note(note) => &note.rhythm,
+        Event::Chord(chord) => &chord.rhythm,
+    };
+    rhythm.duration_type == DurationType::Note && rhythm.length.as_u16() >= Length::Eighth.as_u16()
+}
+
+/// Beam the events at `group` together, if there are at least two.
+fn beam(voice: &mut Voice, group: &[usize]) {
+    if let (Some(first), Some(last)) = (group.first(), group.last()) {
+        if first != last {
+            set_beam(&mut voice.events[*first], Beam::Start);
+            set_beam(&mut voice.events[*last], Beam::End);
+        }
+    }
+}
+
+fn set_beam(event: &mut Event, beam: Beam) {
+    match event {
+        Event::Note(note) => note.beam = Some(beam),
+        Event::Chord(chord) => chord.beam = Some(beam),
+    }
+}
+
+/// Write out repeats, playing first endings the first time through and
+/// second endings the second time.
+fn unfold(measures: &[WrittenMeasure]) -> Vec<&WrittenMeasure> {
+    let mut unfolded = vec![];
+    // the measure a repeat goes back to, and the repeat sign last jumped from
+    let mut start = 0;
+    let mut jumped_from: Option<usize> = None;
+    let mut pass = 1;
+    let mut i = 0;
+    while i < measures.len() {
+        let measure = &measures[i];
+        if jumped_from.is_some_and(|end| i > end) && measure.ending.is_none() {
+            pass = 1;
+            start = i;
+            jumped_from = None;
+        }
+        if measure.start_repeat && pass == 1 {
+            start = i;
+        }
+        if measure
+            .ending
+            .is_some_and(|ending| u32::from(ending) != pass)
+        {
+            i += 1;
+            continue;
+        }
+        unfolded.push(measure);
+        if measure.end_repeat {
+            if pass == 1 {
+                pass = 2;
+                jumped_from = Some(i);
+                i = start;
+                continue;
+            }
+            pass = 1;
+            start = i + 1;
+            jumped_from = None;
+        }
+        i += 1;
+    }
+    unfolded
+}
+
+fn read_number(chars: &[char], i: &mut usize) -> Option<u8> {
+    let start = *i;
+    while chars.get(*i).is_some_and(|c| c.is_ascii_digit()) {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect::<String>().parse().ok()
+}
+
+/// Skip the rest of a list of endings like `1,3` or `1-2`.
+fn skip_ending_list(chars: &[char], i: &mut usize) {
+    while chars
+        .get(*i)
+        .is_some_and(|c| c.is_ascii_digit() || *c == ',' || *c == '-')
+    {
+        *i += 1;
+    }
+}
+
+/// Read a note length multiplier like `2`, `/`, `//` or `3/2`.
+fn read_length(chars: &[char], i: &mut usize) -> Result<Duration, String> {
+    let numerator = read_number(chars, i).unwrap_or(1);
+    let mut denominator = 1;
+    while chars.get(*i) == Some(&'/') {
+        *i += 1;
+        denominator *= i32::from(read_number(chars, i).unwrap_or(2));
+    }
+    if numerator == 0 || denominator == 0 {
+        return Err(String::from("Invalid note length"));
+    }
+    Ok(Duration::new(i32::from(numerator), denominator))
+}
+
+/// Read an `M:` field like `6/8`, `C`, `C|` or `2+2+3/8`.
+fn read_meter(value: &str) -> Result<TimeSignature, String> {
+    let invalid = || format!("Unsupported meter '{}'", value);
+    let (beats, denominator) = match value {
+        "C" => return TimeSignature::from_fraction(4, 4),
+        "C|" => return TimeSignature::from_fraction(2, 2),
+        _ => value.split_once('/').ok_or_else(invalid)?,
+    };
+    let beats = beats.trim().trim_start_matches('(').trim_end_matches(')');
+    let denominator = denominator.trim().parse().map_err(|_| invalid())?;
+    if beats.contains('+') {
+        let groups = beats
+            .split('+')
+            .map(|group| group.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<Vec<u8>, String>>()?;
+        TimeSignature::additive(&groups, denominator)
+    } else {
+        TimeSignature::from_fraction(beats.parse().map_err(|_| invalid())?, denominator)
+    }
+}
+
+/// Read an `L:` field like `1/8`.
+fn read_unit(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid unit note length '{}'", value);
+    let (numerator, denominator) = value.split_once('/').ok_or_else(invalid)?;
+    match (numerator.trim().parse(), denominator.trim().parse()) {
+        (Ok(numerator), Ok(denominator)) if numerator > 0 && denominator > 0 => {
+            Ok(Duration::new(numerator, denominator))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Read a `K:` field like `G`, `F#m`, `Bb dorian` or `D clef=bass`.
+fn read_key(value: &str) -> Result<(KeySignature, Option<Clef>), String> {
+    let mut words = value.split_whitespace().peekable();
+    let mut clef = None;
+    let tonic = match words.peek() {
+        Some(word) if !word.contains('=') => words.next().unwrap_or(""),
+        _ => "",
+    };
+    let mut mode = String::new();
+    let mut tonic_chars = tonic.chars();
+    let fifths = match tonic_chars.next() {
+        None => 0,
+        Some(_) if tonic.eq_ignore_ascii_case("none") => 0,
+        Some(letter) => {
+            let mut fifths = match letter {
+                'F' => -1,
+                'C' => 0,
+                'G' => 1,
+                'D' => 2,
+                'A' => 3,
+                'E' => 4,
+                'B' => 5,
+                _ => return Err(format!("Unsupported key '{}'", value)),
+            };
+            let rest = tonic_chars.as_str();
+            mode = match rest.chars().next() {
+                Some('#') => {
+                    fifths += 7;
+                    rest[1..].to_string()
+                }
+                Some('b') => {
+                    fifths -= 7;
+                    rest[1..].to_string()
+                }
+                _ => rest.to_string(),
+            };
+            fifths
+        }
+    };
+    for word in words {
+        match word.split_once('=') {
+            Some(("clef", name)) => clef = Some(read_clef(name)?),
+            Some(_) => {}
+            None if mode.is_empty() && word.chars().all(|c| c.is_ascii_alphabetic()) => {
+                mode = word.to_string()
+            }
+            None => return Err(format!("Unsupported key '{}'", value)),
+        }
+    }
+    let mode = mode.to_ascii_lowercase();
+    let offset = match mode.get(..3).unwrap_or(&mode) {
+        "" | "maj" | "ion" => 0,
+        "m" | "min" | "aeo" => -3,
+        "mix" => -1,
+        "dor" => -2,
+        "phr" => -4,
+        "lyd" => 1,
+        "loc" => -5,
+        _ => return Err(format!("Unsupported key '{}'", value)),
+    };
+    Ok((KeySignature::from_fifths(fifths + offset)?, clef))
+}
+
+/// Read a clef name like `bass`, `treble-8` or `perc`.
+fn read_clef(name: &str) -> Result<Clef, String> {
+    match name {
+        "perc" => Ok(Clef::Percussion),
+        name => Clef::from_name(&name.replace('-', "_"))
+            .ok_or_else(|| format!("Unsupported clef '{}'", name)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::import::abc::*;
+    use crate::notation::key::Key;
+    use crate::notation::score::Score;
+    use crate::NoteNameLanguage;
+    #[test]
+    fn test_from_abc() {
+        let tunes = from_abc(include_str!("../../tests/fixtures/tunes.abc")).unwrap();
+        assert_eq!(tunes.len(), 2);
+        assert_eq!(tunes[0].number, 1);
+        assert_eq!(tunes[0].title, Some(String::from("The Kesh")));
+        assert_eq!(tunes[1].title, Some(String::from("Study in A Dorian")));
+        let lilypond: Vec<String> = tunes
+            .iter()
+            .map(|tune| {
+                Score::from(tune.staff.clone())
+                    .to_lilypond(NoteNameLanguage::English)
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(
+            lilypond.join("\n"),
+            include_str!("../../tests/fixtures/tunes.ly")
+        );
+    }
+    #[test]
+    fn test_read_key() {
+        let key = |value| read_key(value).map(|(key, _)| *key.get_key());
+        assert_eq!(key("G"), Ok(Key::Sharps(1)));
+        assert_eq!(key("F#m"), Ok(Key::Sharps(3)));
+        assert_eq!(key("Bb"), Ok(Key::Flats(2)));
+        assert_eq!(key("Ador"), Ok(Key::Sharps(1)));
+        assert_eq!(key("Dm"), Ok(Key::Flats(1)));
+        assert_eq!(key("E Mixolydian"), Ok(Key::Sharps(3)));
+        assert_eq!(key("none"), Ok(Key::None));
+        assert_eq!(key("C# loc"), Ok(Key::Sharps(2)));
+        assert_eq!(
+            read_key("D clef=bass").map(|(_, clef)| clef),
+            Ok(Some(Clef::Bass))
+        );
+        assert!(key("Hp").is_err());
+        assert!(key("Cb dor ^f").is_err());
+    }
+    #[test]
+    fn test_read_meter() {
+        assert_eq!(read_meter("C"), TimeSignature::from_fraction(4, 4));
+        assert_eq!(read_meter("C|"), TimeSignature::from_fraction(2, 2));
+        assert_eq!(read_meter("6/8"), TimeSignature::from_fraction(6, 8));
+        assert_eq!(
+            read_meter("(2+2+3)/8"),
+            TimeSignature::additive(&[2, 2, 3], 8)
+        );
+        assert!(read_meter("none").is_err());
+    }
+    #[test]
+    fn test_from_abc_errors() {
+        let tune = |body: &str| from_abc(&format!("X:3\nL:1/8\nK:C\n{}\n", body));
+        assert_eq!(
+            tune("(3abc d"),
+            Err(String::from("Tuplets are not supported in ABC tune 3."))
+        );
+        assert_eq!(
+            tune("a b | - c"),
+            Err(String::from("Expected a note before a tie in ABC tune 3."))
+        );
+        assert_eq!(
+            tune("V:1\nab\nV:2\ncd"),
+            Err(String::from(
+                "Multiple voices are not supported in ABC tune 3."
+            ))
+        );
+        assert_eq!(
+            tune("a2/3"),
+            Err(String::from("Unsupported note length 1/12 in ABC tune 3."))
+        );
+        assert!(tune("a ? b").is_err());
+        assert!(from_abc("X:1\nT:No key\nabc\n").is_err());
+        assert!(from_abc("X:one\nK:C\nabc\n").is_err());
+    }
+}
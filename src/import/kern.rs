@@ -0,0 +1,527 @@
+//! Import of [Humdrum](https://www.humdrum.org/) `**kern` spines.
+//!
+//! Each `**kern` spine becomes a staff, with the leftmost spine as the
+//! lowest staff, as is usual in Humdrum. A spine split with `*^` adds a voice
+//! to its staff until the sub-spines are joined with `*v`. Other spines, like
+//! `**dynam` or `**text`, are skipped.
+
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::chord::Chord;
+use crate::notation::clef::Clef;
+use crate::notation::key::KeySignature;
+use crate::notation::note::Note;
+use crate::notation::pitch::{Accidental, Alteration, NoteName, Octave, Pitch};
+use crate::notation::rhythm::{Duration, DurationType, Rhythm};
+use crate::notation::score::{Score, Staff, StaffChange};
+use crate::notation::transform::split;
+use crate::notation::voice::{Event, Voice};
+
+/// Attempt to read the `**kern` spines of a Humdrum file as a score.
+///
+/// Clefs (`*clefF4`), key signatures (`*k[f#c#]`) and meters (`*M3/4`) set
+/// the staff's attributes, or change them from the measure they are in.
+/// Grace notes are skipped.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the file has no `**kern` spine, if a record has
+/// the wrong number of fields, if a token cannot be read, or if spines are
+/// added or exchanged with `*+` or `*x`.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::import::kern::from_kern;
+/// use lilypond::NoteNameLanguage;
+///
+/// let score = from_kern("**kern\n*M2/4\n8cc#L\n8BB-J\n4r\n=\n2C\n*-\n").unwrap();
+///
+/// assert_eq!(
+///     score.to_lilypond(NoteNameLanguage::English).unwrap(),
+///     "\\language \"english\"\n\n<<\n  \\new Staff { \\clef treble \\key c \\major \\time 2/4 cs''8[ bf,8] r4 | c2 }\n>>\n"
+/// );
+/// ```
+pub fn from_kern(input: &str) -> Result<Score, String> {
+    let mut reader = KernReader {
+        staves: vec![],
+        spines: None,
+    };
+    for (index, line) in input.lines().enumerate() {
+        reader.record(line, index + 1)?;
+    }
+    if reader.staves.is_empty() {
+        return Err(String::from("Expected a **kern spine."));
+    }
+    Ok(Score {
+        staves: reader
+            .staves
+            .into_iter()
+            .rev()
+            .map(|staff| staff.staff)
+            .collect(),
+    })
+}
+
+/// The staff and voice a `**kern` spine or sub-spine is read into.
+#[derive(Debug, Clone, Copy)]
+struct Spine {
+    staff: usize,
+    voice: usize,
+}
+
+struct StaffReader {
+    staff: Staff,
+    /// The time each voice has reached.
+    times: Vec<Duration>,
+    /// The index of the current measure.
+    measure: usize,
+    /// The time of the last barline.
+    barline: Duration,
+}
+
+struct KernReader {
+    staves: Vec<StaffReader>,
+    /// The spine each field of a record belongs to, or `None` for spines
+    /// that are not `**kern`, once the exclusive interpretations are read.
+    spines: Option<Vec<Option<Spine>>>,
+}
+
+impl KernReader {
+    fn record(&mut self, line: &str, number: usize) -> Result<(), String> {
+        if line.is_empty() || line.starts_with("!!") {
+            return Ok(());
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let spines = match &self.spines {
+            Some(spines) => spines.clone(),
+            None => return self.exclusive(&fields, number),
+        };
+        if fields.len() != spines.len() {
+            return Err(format!(
+                "Expected {} spines in line {}, found {}.",
+                spines.len(),
+                number,
+                fields.len()
+            ));
+        }
+        if line.starts_with('!') {
+            Ok(())
+        } else if line.starts_with('*') {
+            self.interpretations(&fields, &spines, number)
+        } else if line.starts_with('=') {
+            self.barline(&spines);
+            Ok(())
+        } else {
+            self.data(&fields, &spines, number)
+        }
+    }
+    /// Start a staff for each `**kern` spine.
+    fn exclusive(&mut self, fields: &[&str], number: usize) -> Result<(), String> {
+        if !fields.iter().all(|field| field.starts_with("**")) {
+            return Err(format!(
+                "Expected exclusive interpretations like **kern in line {}.",
+                number
+            ));
+        }
+        let mut spines = vec![];
+        for field in fields {
+            if *field == "**kern" {
+                spines.push(Some(Spine {
+                    staff: self.staves.len(),
+                    voice: 0,
+                }));
+                self.staves.push(StaffReader {
+                    staff: Staff::from(Voice::new()),
+                    times: vec![Duration::from_integer(0)],
+                    measure: 0,
+                    barline: Duration::from_integer(0),
+                });
+            } else {
+                spines.push(None);
+            }
+        }
+        self.spines = Some(spines);
+        Ok(())
+    }
+    fn interpretations(
+        &mut self,
+        fields: &[&str],
+        spines: &[Option<Spine>],
+        number: usize,
+    ) -> Result<(), String> {
+        let mut new = vec![];
+        let mut index = 0;
+        while index < fields.len() {
+            let spine = spines[index];
+            match fields[index] {
+                "*^" => {
+                    new.push(spine);
+                    new.push(spine.map(|spine| self.split(spine)));
+                }
+                "*v" => {
+                    // adjacent sub-spines join into the first of them
+                    new.push(spine);
+                    while fields.get(index + 1) == Some(&"*v") {
+                        index += 1;
+                    }
+                }
+                "*-" => {}
+                field if field == "*+" || field == "*x" || field.starts_with("**") => {
+                    return Err(format!(
+                        "Unsupported spine manipulation {} in line {}.",
+                        field, number
+                    ));
+                }
+                field => {
+                    let text = field.strip_prefix('*').ok_or_else(|| {
+                        format!("Invalid interpretation '{}' in line {}.", field, number)
+                    })?;
+                    if let Some(spine) = spine {
+                        self.interpretation(spine.staff, text, number)?;
+                    }
+                    new.push(spine);
+                }
+            }
+            index += 1;
+        }
+        self.spines = Some(new);
+        Ok(())
+    }
+    /// Add a voice to a staff for a new sub-spine, starting with rests up to
+    /// the time of the spine that was split.
+    fn split(&mut self, spine: Spine) -> Spine {
+        let reader = &mut self.staves[spine.staff];
+        let time = reader.times[spine.voice];
+        let mut voice = Voice::new();
+        if let Ok(rhythms) = Rhythm::tied(time) {
+            voice.events = split(&rest(), rhythms);
+        }
+        reader.staff.voices.push(voice);
+        reader.times.push(time);
+        Spine {
+            staff: spine.staff,
+            voice: reader.times.len() - 1,
+        }
+    }
+    fn interpretation(&mut self, staff: usize, text: &str, number: usize) -> Result<(), String> {
+        let invalid = || format!("Invalid interpretation *{} in line {}.", text, number);
+        let change = if let Some(clef) = text.strip_prefix("clef") {
+            StaffChange::Clef(read_clef(clef).ok_or_else(invalid)?)
+        } else if let Some(key) = text.strip_prefix("k[") {
+            let key = key.strip_suffix(']').ok_or_else(invalid)?;
+            StaffChange::KeySignature(read_key(key).ok_or_else(invalid)?)
+        } else if text.starts_with("MM") {
+            // a tempo
+            return Ok(());
+        } else if let Some(meter) = text.strip_prefix('M') {
+            StaffChange::TimeSignature(meter.parse().map_err(|_| invalid())?)
+        } else {
+            return Ok(());
+        };
+        let reader = &mut self.staves[staff];
+        reader.staff.change(reader.measure, change);
+        Ok(())
+    }
+    /// Start a new measure in each staff that has music since the last
+    /// barline, taking a short first measure as a pickup.
+    fn barline(&mut self, spines: &[Option<Spine>]) {
+        let mut done = vec![];
+        for spine in spines.iter().flatten() {
+            if done.contains(&spine.staff) {
+                continue;
+            }
+            done.push(spine.staff);
+            let reader = &mut self.staves[spine.staff];
+            let time = reader.times[spine.voice];
+            if time > reader.barline {
+                if reader.measure == 0 && time < reader.staff.time_signature.measure_duration() {
+                    reader.staff.partial = Some(time);
+                }
+                reader.measure += 1;
+                reader.barline = time;
+            }
+        }
+    }
+    fn data(
+        &mut self,
+        fields: &[&str],
+        spines: &[Option<Spine>],
+        number: usize,
+    ) -> Result<(), String> {
+        for (field, spine) in fields.iter().zip(spines) {
+            let spine = match spine {
+                Some(spine) if *field != "." => spine,
+                _ => continue,
+            };
+            let (events, duration) = match read_token(field)
+                .map_err(|e| format!("{} '{}' in line {}.", e, field, number))?
+            {
+                Some(token) => token,
+                None => continue,
+            };
+            let reader = &mut self.staves[spine.staff];
+            reader.staff.voices[spine.voice].events.extend(events);
+            reader.times[spine.voice] += duration;
+        }
+        Ok(())
+    }
+}
+
+fn rest() -> Event {
+    let mut rest = Note::new(NoteName::None);
+    rest.pitch.octave(Octave::None);
+    rest.rhythm.duration_type(DurationType::Rest);
+    Event::Note(rest)
+}
+
+/// Read a clef like `G2`, `Gv2`, `F4`, `C3` or `X`.
+fn read_clef(text: &str) -> Option<Clef> {
+    if text == "X" {
+        return Some(Clef::Percussion);
+    }
+    let sign = text.get(..1)?;
+    let line = text.get(text.len() - 1..)?.parse().ok()?;
+    let octave_change = match text.get(1..text.len() - 1)? {
+        "" => 0,
+        "v" => -1,
+        "^" => 1,
+        _ => return None,
+    };
+    Clef::from_sign(sign, line, octave_change)
+}
+
+/// Read the altered notes of a key signature, like `f#c#` or `b-e-`.
+fn read_key(text: &str) -> Option<KeySignature> {
+    let mut fifths = 0;
+    for character in text.chars() {
+        match character {
+            '#' => fifths += 1,
+            '-' => fifths -= 1,
+            'a'..='g' => {}
+            _ => return None,
+        }
+    }
+    KeySignature::from_fifths(fifths).ok()
+}
+
+/// Read a data token: a note like `4.cc#`, a rest like `8r`, or a chord of
+/// notes separated by spaces. Returns the events, split into tied notes if
+/// no single note lasts as long, and their duration, or `None` for grace
+/// notes.
+fn read_token(token: &str) -> Result<Option<(Vec<Event>, Duration)>, String> {
+    let mut pitches = vec![];
+    let mut duration = None;
+    let mut tie = false;
+    let mut is_rest = false;
+    let mut beam = None;
+    let mut articulations = vec![];
+    for note in token.split(' ').filter(|note| !note.is_empty()) {
+        let chars: Vec<char> = note.chars().collect();
+        let mut digits = String::new();
+        let mut divisor = String::new();
+        let mut dots = 0;
+        let mut letter: Option<(char, i32)> = None;
+        let mut alteration = 0;
+        let mut index = 0;
+        while index < chars.len() {
+            let c = chars[index];
+            match c {
+                '0'..='9' if letter.is_none() && dots == 0 => {
+                    if divisor.is_empty() {
+                        digits.push(c)
+                    } else {
+                        divisor.push(c)
+                    }
+                }
+                '%' => divisor.push('%'),
+                '.' => dots += 1,
+                'a'..='g' | 'A'..='G' => match &mut letter {
+                    Some((first, count)) if *first == c => *count += 1,
+                    Some(_) => return Err(String::from("Invalid **kern token")),
+                    None => letter = Some((c, 1)),
+                },
+                'r' => is_rest = true,
+                '#' => alteration += 1,
+                '-' => alteration -= 1,
+                '[' | '_' => tie = true,
+                ']' => {}
+                'L' => {
+                    beam.get_or_insert(Beam::Start);
+                }
+                'J' => {
+                    beam.get_or_insert(Beam::End);
+                }
+                '\'' => articulations.push(Articulation::Staccato),
+                '`' => articulations.push(Articulation::Staccatissimo),
+                '~' => articulations.push(Articulation::Tenuto),
+                '^' if chars.get(index + 1) == Some(&'^') => {
+                    articulations.push(Articulation::Marcato);
+                    index += 1;
+                }
+                '^' => articulations.push(Articulation::Accent),
+                ';' => articulations.push(Articulation::Fermata),
+                't' | 'T' => articulations.push(Articulation::Trill),
+                'q' | 'Q' | 'P' | 'p' => return Ok(None),
+                // slurs, phrases, stems, bowings, editorial and layout marks
+                'n' | '(' | ')' | '{' | '}' | '&' | '/' | '\\' | 'v' | 'u' | 'y' | 'x' | 'X'
+                | 'k' | 'K' | 'm' | 'M' | 'W' | 'w' | 'S' | '$' | 'R' | 'o' | 'O' | 'H' | 'h'
+                | 'z' | 'N' | 'j' | 'Z' | ':' | '<' | '>' | '?' | '@' | '|' | 'i' | 'I' => {}
+                _ => return Err(format!("Unexpected '{}' in **kern token", c)),
+            }
+            index += 1;
+        }
+        if duration.is_none() && !digits.is_empty() {
+            duration = Some(read_duration(&digits, &divisor, dots)?);
+        }
+        if let Some((letter, count)) = letter {
+            if !is_rest {
+                pitches.push(read_pitch(letter, count, alteration)?);
+            }
+        }
+    }
+    let duration = duration.ok_or_else(|| String::from("Expected a duration in **kern token"))?;
+    let rhythms = match Rhythm::from_duration(duration) {
+        Ok(rhythm) => vec![rhythm],
+        Err(_) => Rhythm::tied(duration)
+            .map_err(|_| format!("Unsupported duration {} in **kern token", duration))?,
+    };
+    let event = match pitches.as_slice() {
+        _ if is_rest => rest(),
+        [] => return Err(String::from("Expected a note or rest in **kern token")),
+        [pitch] => {
+            let mut note = Note::new(pitch.note_name);
+            note.pitch = pitch.clone();
+            note.tie = tie;
+            note.articulations = articulations;
+            note.beam = beam;
+            Event::Note(note)
+        }
+        pitches => {
+            let mut chord = Chord::new(pitches.to_vec());
+            chord.tie = tie;
+            chord.articulations = articulations;
+            chord.beam = beam;
+            Event::Chord(chord)
+        }
+    };
+    Ok(Some((split(&event, rhythms), duration)))
+}
+
+/// Read a duration from its reciprocal, like `4` for a quarter note, `0` for
+/// a breve or `3%2` for two thirds of a whole note, and its dots.
+fn read_duration(digits: &str, divisor: &str, dots: i32) -> Result<Duration, String> {
+    let invalid = || String::from("Invalid duration in **kern token");
+    let base = if digits.chars().all(|c| c == '0') {
+        Duration::from_integer(1 << digits.len().min(3))
+    } else {
+        let reciprocal: i32 = digits.parse().map_err(|_| invalid())?;
+        let divisor: i32 = match divisor.strip_prefix('%') {
+            Some(divisor) => divisor.parse().map_err(|_| invalid())?,
+            None => 1,
+        };
+        if divisor == 0 {
+            return Err(invalid());
+        }
+        Duration::new(divisor, reciprocal)
+    };
+    let dots = dots.min(20);
+    Ok(base * Duration::new((1 << (dots + 1)) - 1, 1 << dots))
+}
+
+/// Read a pitch from its letter repeated for each octave away from middle
+/// C, like `cc` for c'' or `BB` for b,, and its alteration.
+fn read_pitch(letter: char, count: i32, alteration: i32) -> Result<Pitch, String> {
+    let index = "cdefgab"
+        .find(letter.to_ascii_lowercase())
+        .expect("the letter is a note name");
+    let mut pitch = Pitch::new(NoteName::from_diatonic_index(index as i32));
+    let octave = if letter.is_ascii_lowercase() {
+        3 + count
+    } else {
+        4 - count
+    };
+    pitch.octave(
+        Octave::from_number(octave)
+            .ok_or_else(|| String::from("Pitch out of range in **kern token"))?,
+    );
+    pitch.accidental(Accidental::from_alteration(Alteration::from_integer(
+        alteration,
+    )));
+    Ok(pitch)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::import::kern::*;
+    use crate::NoteNameLanguage;
+    #[test]
+    fn test_from_kern() {
+        let score = from_kern(include_str!("../../tests/fixtures/minuet.krn")).unwrap();
+        assert_eq!(
+            score.to_lilypond(NoteNameLanguage::English).unwrap(),
+            include_str!("../../tests/fixtures/minuet.ly")
+        );
+    }
+    #[test]
+    fn test_read_token() {
+        let (events, duration) = read_token("[4.cc#L").unwrap().unwrap();
+        assert_eq!(duration, Duration::new(3, 8));
+        match &events[0] {
+            Event::Note(note) => {
+                assert_eq!(note.pitch.octave, Octave::S5);
+                assert_eq!(note.pitch.accidental, Accidental::Sharp);
+                assert!(note.tie);
+                assert_eq!(note.beam, Some(Beam::Start));
+            }
+            _ => panic!("expected a note"),
+        }
+        let (events, _) = read_token("2BB- 2F 2d'").unwrap().unwrap();
+        match &events[0] {
+            Event::Chord(chord) => {
+                assert_eq!(chord.pitches[0].octave, Octave::S2);
+                assert_eq!(chord.pitches[0].accidental, Accidental::Flat);
+                assert_eq!(chord.articulations, vec![Articulation::Staccato]);
+            }
+            _ => panic!("expected a chord"),
+        }
+        assert_eq!(read_token("0r").unwrap().unwrap().0.len(), 2);
+        assert_eq!(read_token("1%2c").unwrap().unwrap().1, Duration::new(2, 1));
+        assert!(read_token("3%2c").is_err());
+        assert_eq!(read_token("8qd"), Ok(None));
+        assert!(read_token("c").is_err());
+        assert!(read_token("4cd").is_err());
+        assert!(read_token("4c!").is_err());
+    }
+    #[test]
+    fn test_from_kern_errors() {
+        assert_eq!(
+            from_kern("**kern\t**kern\n4c\n"),
+            Err(String::from("Expected 2 spines in line 2, found 1."))
+        );
+        assert_eq!(
+            from_kern("**kern\n12c\n"),
+            Err(String::from(
+                "Unsupported duration 1/12 in **kern token '12c' in line 2."
+            ))
+        );
+        assert_eq!(
+            from_kern("**kern\n*x\n"),
+            Err(String::from("Unsupported spine manipulation *x in line 2."))
+        );
+        assert_eq!(
+            from_kern("**text\nhello\n"),
+            Err(String::from("Expected a **kern spine."))
+        );
+        assert!(from_kern("4c\n").is_err());
+        assert!(from_kern("**kern\n*clefQ2\n").is_err());
+        assert_eq!(
+            from_kern("**kern\t**kern\n*\t\n"),
+            Err(String::from("Invalid interpretation '' in line 2."))
+        );
+        assert_eq!(
+            from_kern("**kern\t**kern\n*\té\n"),
+            Err(String::from("Invalid interpretation 'é' in line 2."))
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Readers of other music notation formats.
+//!
+//! The readers map other formats into the crate's [`Score`][crate::notation::score::Score],
+//! so that they can be written as LilyPond with
+//! [`Score::to_lilypond`][crate::notation::score::Score::to_lilypond].
+
+pub mod abc;
+pub mod kern;
+pub mod musicxml;
+
+/// An element of an XML document, with the text directly inside it.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    /// Return the value of an attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+    /// Return the first child element with a name.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+    /// Return the child elements with a name.
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+    /// Return the trimmed text of the first child element with a name.
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.trim())
+    }
+}
+
+/// Attempt to read an XML document, returning its root element. The XML
+/// declaration, processing instructions, comments and the document type
+/// declaration are skipped.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the document is not well-formed XML.
+pub(crate) fn parse_xml(input: &str) -> Result<Element, String> {
+    let mut reader = Reader { input, position: 0 };
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    reader.skip_misc()?;
+    if reader.position < input.len() {
+        return Err(reader.error("expected the end of the document"));
+    }
+    Ok(root)
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.position..]
+    }
+    fn error(&self, expected: &str) -> String {
+        format!("Invalid XML at byte {}: {}.", self.position, expected)
+    }
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+    /// Skip past the next occurrence of `end`.
+    fn skip_past(&mut self, end: &str) -> Result<(), String> {
+        match self.rest().find(end) {
+            Some(index) => {
+                self.position += index + end.len();
+                Ok(())
+            }
+            None => Err(self.error(&format!("expected \"{}\"", end))),
+        }
+    }
+    fn expect(&mut self, text: &str) -> Result<(), String> {
+        if self.rest().starts_with(text) {
+            self.position += text.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected \"{}\"", text)))
+        }
+    }
+    /// Skip whitespace, processing instructions, comments and document type
+    /// declarations.
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<!DOCTYPE") {
+                // the declaration may contain an internal subset in brackets
+                let mut depth = 0;
+                let end = self.rest().char_indices().find(|(_, c)| {
+                    match c {
+                        '[' => depth += 1,
+                        ']' => depth -= 1,
+                        '>' if depth == 0 => return true,
+                        _ => {}
+                    }
+                    false
+                });
+                match end {
+                    Some((index, _)) => self.position += index + 1,
+                    None => return Err(self.error("expected the end of the DOCTYPE")),
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+    fn name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| c.is_whitespace() || "/>=".contains(c))
+            .unwrap_or(rest.len());
+        if length == 0 {
+            return Err(self.error("expected a name"));
+        }
+        let name = rest[..length].to_string();
+        self.position += length;
+        Ok(name)
+    }
+    fn element(&mut self) -> Result<Element, String> {
+        self.expect("<")?;
+        let mut element = Element {
+            name: self.name()?,
+            ..Default::default()
+        };
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.position += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.position += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(quote) if quote == '"' || quote == '\'' => quote,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.position += 1;
+            let length = match self.rest().find(quote) {
+                Some(length) => length,
+                None => return Err(self.error("expected the end of the attribute value")),
+            };
+            let value = unescape(&self.rest()[..length]).map_err(|e| self.error(&e))?;
+            self.position += length + 1;
+            element.attributes.push((name, value));
+        }
+        loop {
+            if self.rest().starts_with("</") {
+                self.position += 2;
+                if self.name()? != element.name {
+                    return Err(self.error(&format!("expected \"</{}>\"", element.name)));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let length = match self.rest().find("]]>") {
+                    Some(length) => length,
+                    None => return Err(self.error("expected \"]]>\"")),
+                };
+                element.text.push_str(&self.rest()[..length]);
+                self.position += length + 3;
+            } else if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with('<') {
+                element.children.push(self.element()?);
+            } else if self.rest().is_empty() {
+                return Err(self.error(&format!("expected \"</{}>\"", element.name)));
+            } else {
+                let length = self.rest().find('<').unwrap_or(self.rest().len());
+                let text = unescape(&self.rest()[..length]).map_err(|e| self.error(&e))?;
+                element.text.push_str(&text);
+                self.position += length;
+            }
+        }
+    }
+}
+
+/// Replace XML character and entity references with the characters they
+/// stand for.
+fn unescape(text: &str) -> Result<String, String> {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        let end = match rest[start..].find(';') {
+            Some(end) => start + end,
+            None => return Err(String::from("expected \";\" after \"&\"")),
+        };
+        let entity = &rest[start + 1..end];
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(decimal) = entity.strip_prefix('#') {
+                    decimal.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(std::char::from_u32)
+            }
+        };
+        match character {
+            Some(character) => output.push(character),
+            None => return Err(format!("unknown entity \"&{};\"", entity)),
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::import::*;
+    #[test]
+    fn test_parse_xml() {
+        let root = parse_xml(
+            "<?xml version=\"1.0\"?>\n\
+             <!DOCTYPE score [ <!ENTITY x \"y\"> ]>\n\
+             <!-- a comment -->\n\
+             <score version='4.0'>\n  \
+             <title>Fish &amp; Chips &#233;</title>\n  \
+             <empty a=\"&lt;b&gt;\"/>\n  \
+             <text><![CDATA[<raw>]]></text>\n\
+             </score>\n",
+        )
+        .unwrap();
+        assert_eq!(root.name, "score");
+        assert_eq!(root.attribute("version"), Some("4.0"));
+        assert_eq!(root.child_text("title"), Some("Fish & Chips \u{e9}"));
+        assert_eq!(root.child("empty").unwrap().attribute("a"), Some("<b>"));
+        assert_eq!(root.child_text("text"), Some("<raw>"));
+        assert_eq!(root.children("empty").count(), 1);
+    }
+    #[test]
+    fn test_parse_xml_errors() {
+        assert!(parse_xml("<a><b></a>").is_err());
+        assert!(parse_xml("<a>").is_err());
+        assert!(parse_xml("<a x=1/>").is_err());
+        assert!(parse_xml("<a>&nbsp;</a>").is_err());
+        assert!(parse_xml("<a/><b/>").is_err());
+    }
+}
@@ -0,0 +1,675 @@
+//! Import of partwise [MusicXML](https://www.w3.org/2021/06/musicxml40/)
+//! scores.
+//!
+//! Each staff of a part becomes a [`Staff`], and each `<voice>` of a staff a
+//! [`Voice`]. Times are counted in the part's `<divisions>` of a quarter
+//! note, moving back and forth with `<backup>` and `<forward>`; a voice that
+//! skips ahead is filled with rests.
+
+use crate::import::{parse_xml, Element};
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::chord::Chord;
+use crate::notation::clef::Clef;
+use crate::notation::dynamic::Dynamic;
+use crate::notation::key::KeySignature;
+use crate::notation::note::Note;
+use crate::notation::pitch::{Accidental, Alteration, NoteName, Octave, Pitch};
+use crate::notation::rhythm::{Duration, DurationType, Length, Rhythm};
+use crate::notation::score::{Score, Staff, StaffChange};
+use crate::notation::time::TimeSignature;
+use crate::notation::transform::split;
+use crate::notation::voice::{Event, Voice};
+
+/// Attempt to read a partwise MusicXML document as a score, with a staff for
+/// each staff of each part.
+///
+/// Clefs, key signatures and time signatures in the first measure set those
+/// of the staff, and later ones become the staff's changes, taking effect
+/// from the start of the measure they are in. An implicit first measure is
+/// read as a pickup. Notes whose durations don't
+/// match their types, like those in tuplets, are written as tied notes when
+/// possible. Grace and cue notes are skipped.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the document is not a partwise MusicXML score,
+/// or if it has notes, durations, clefs, key signatures or time signatures
+/// that cannot be represented.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::import::musicxml::from_musicxml;
+/// use lilypond::NoteNameLanguage;
+///
+/// let score = from_musicxml(
+///     "<score-partwise version=\"4.0\">
+///        <part-list><score-part id=\"P1\"><part-name>Flute</part-name></score-part></part-list>
+///        <part id=\"P1\">
+///          <measure number=\"1\">
+///            <attributes>
+///              <divisions>2</divisions>
+///              <key><fifths>1</fifths></key>
+///              <time><beats>2</beats><beat-type>4</beat-type></time>
+///              <clef><sign>G</sign><line>2</line></clef>
+///            </attributes>
+///            <note><pitch><step>F</step><alter>1</alter><octave>5</octave></pitch>
+///              <duration>3</duration><voice>1</voice><type>quarter</type><dot/></note>
+///            <note><rest/><duration>1</duration><voice>1</voice><type>eighth</type></note>
+///          </measure>
+///        </part>
+///      </score-partwise>",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     score.to_lilypond(NoteNameLanguage::English).unwrap(),
+///     "\\language \"english\"\n\n<<\n  \\new Staff { \\clef treble \\key g \\major \\time 2/4 fs''4. r8 }\n>>\n"
+/// );
+/// ```
+pub fn from_musicxml(input: &str) -> Result<Score, String> {
+    let root = parse_xml(input)?;
+    match root.name.as_str() {
+        "score-partwise" => {}
+        "score-timewise" => {
+            return Err(String::from("Timewise MusicXML is not supported."));
+        }
+        name => return Err(format!("Invalid MusicXML root element <{}>.", name)),
+    }
+    let mut score = Score::new();
+    for part in root.children("part") {
+        let mut reader = PartReader::new();
+        for (index, measure) in part.children("measure").enumerate() {
+            reader.read_measure(measure, index)?;
+        }
+        score
+            .staves
+            .extend(reader.staves.into_iter().map(|staff| staff.staff));
+    }
+    Ok(score)
+}
+
+/// A voice being read, with the time it has reached.
+struct VoiceReader {
+    id: String,
+    end: Duration,
+    /// How many events the last note was written as.
+    last: usize,
+}
+
+/// A staff being read.
+#[derive(Default)]
+struct StaffReader {
+    staff: Staff,
+    voices: Vec<VoiceReader>,
+    /// A dynamic waiting for the next note of the staff.
+    dynamic: Option<Dynamic>,
+}
+
+impl StaffReader {
+    /// Return the index of a voice, adding it if it is new.
+    fn voice(&mut self, id: &str) -> usize {
+        match self.voices.iter().position(|voice| voice.id == id) {
+            Some(index) => index,
+            None => {
+                self.voices.push(VoiceReader {
+                    id: id.to_string(),
+                    end: Duration::from_integer(0),
+                    last: 0,
+                });
+                self.staff.voices.push(Voice::new());
+                self.voices.len() - 1
+            }
+        }
+    }
+}
+
+/// A part being read.
+struct PartReader {
+    staves: Vec<StaffReader>,
+    /// The number of divisions of a quarter note.
+    divisions: i32,
+    /// The current time, in whole notes from the start of the part.
+    position: Duration,
+}
+
+impl PartReader {
+    fn new() -> PartReader {
+        PartReader {
+            staves: vec![StaffReader::default()],
+            divisions: 1,
+            position: Duration::from_integer(0),
+        }
+    }
+    /// Return the staff with a `<staff>` or `number` value, counting from 1.
+    fn staff(&mut self, number: Option<&str>, measure: &str) -> Result<&mut StaffReader, String> {
+        let number = number.unwrap_or("1");
+        match number.parse::<usize>() {
+            Ok(index) if index >= 1 && index <= self.staves.len() => {
+                Ok(&mut self.staves[index - 1])
+            }
+            _ => Err(format!("Invalid staff {} in measure {}.", number, measure)),
+        }
+    }
+    fn duration(&self, element: &Element, measure: &str) -> Result<Duration, String> {
+        let text = element.child_text("duration").unwrap_or("");
+        match text.parse::<i32>() {
+            Ok(ticks) if ticks >= 0 => Ok(Duration::new(ticks, 4 * self.divisions)),
+            _ => Err(format!(
+                "Invalid duration '{}' in measure {}.",
+                text, measure
+            )),
+        }
+    }
+    fn read_measure(&mut self, measure: &Element, index: usize) -> Result<(), String> {
+        let number = measure.attribute("number").unwrap_or("?").to_string();
+        let start = self.position;
+        let mut end = start;
+        for element in &measure.children {
+            match element.name.as_str() {
+                "attributes" => self.read_attributes(element, index, &number)?,
+                "note" => self.read_note(element, &number)?,
+                "backup" => self.position -= self.duration(element, &number)?,
+                "forward" => self.position += self.duration(element, &number)?,
+                "direction" => {
+                    let dynamic = element
+                        .children("direction-type")
+                        .filter_map(|direction| direction.child("dynamics"))
+                        .flat_map(|dynamics| dynamics.children.iter())
+                        .find_map(|mark| Dynamic::from_name(&mark.name));
+                    if let Some(dynamic) = dynamic {
+                        self.staff(element.child_text("staff"), &number)?.dynamic = Some(dynamic);
+                    }
+                }
+                _ => {}
+            }
+            if self.position < start {
+                return Err(format!("Backup before the start of measure {}.", number));
+            }
+            end = end.max(self.position);
+        }
+        self.position = end;
+        if index == 0 && measure.attribute("implicit") == Some("yes") && end > start {
+            for staff in &mut self.staves {
+                staff.staff.partial = Some(end - start);
+            }
+        }
+        Ok(())
+    }
+    fn read_attributes(
+        &mut self,
+        attributes: &Element,
+        measure: usize,
+        number: &str,
+    ) -> Result<(), String> {
+        if let Some(text) = attributes.child_text("divisions") {
+            self.divisions = match text.parse() {
+                Ok(divisions) if divisions > 0 => divisions,
+                _ => {
+                    return Err(format!(
+                        "Invalid divisions '{}' in measure {}.",
+                        text, number
+                    ))
+                }
+            };
+        }
+        if let Some(text) = attributes.child_text("staves") {
+            let staves = match text.parse() {
+                Ok(staves) if staves > 0 => staves,
+                _ => return Err(format!("Invalid staves '{}' in measure {}.", text, number)),
+            };
+            while self.staves.len() < staves {
+                let staff = Staff {
+                    voices: vec![],
+                    ..self.staves[0].staff.clone()
+                };
+                self.staves.push(StaffReader {
+                    staff,
+                    ..Default::default()
+                });
+            }
+        }
+        for child in &attributes.children {
+            let change = match child.name.as_str() {
+                "key" => StaffChange::KeySignature(read_key(child, number)?),
+                "time" => StaffChange::TimeSignature(read_time(child, number)?),
+                "clef" => StaffChange::Clef(read_clef(child, number)?),
+                _ => continue,
+            };
+            match child.attribute("number") {
+                Some(staff) => self
+                    .staff(Some(staff), number)?
+                    .staff
+                    .change(measure, change),
+                None => {
+                    for staff in &mut self.staves {
+                        staff.staff.change(measure, change.clone());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn read_note(&mut self, element: &Element, number: &str) -> Result<(), String> {
+        if element.child("grace").is_some() || element.child("cue").is_some() {
+            return Ok(());
+        }
+        let duration = self.duration(element, number)?;
+        let chord = element.child("chord").is_some();
+        let pitch = read_pitch(element, number)?;
+        let tie = element
+            .children("tie")
+            .any(|tie| tie.attribute("type") == Some("start"));
+        let position = if chord {
+            self.position - duration
+        } else {
+            self.position
+        };
+        let staff = self.staff(element.child_text("staff"), number)?;
+        let index = staff.voice(element.child_text("voice").unwrap_or("1"));
+        let voice = &mut staff.staff.voices[index];
+        let reader = &mut staff.voices[index];
+        if chord {
+            if reader.last == 0 {
+                return Err(format!("Chord without a first note in measure {}.", number));
+            }
+            let start = voice.events.len() - reader.last;
+            for event in &mut voice.events[start..] {
+                add_pitch(event, pitch.clone(), tie);
+            }
+            return Ok(());
+        }
+        if position < reader.end {
+            return Err(format!(
+                "Notes overlap in voice {} of measure {}.",
+                reader.id, number
+            ));
+        }
+        if position > reader.end {
+            let mut rest = Note::new(NoteName::None);
+            rest.pitch.octave(Octave::None);
+            rest.rhythm.duration_type(DurationType::Rest);
+            let rhythms = Rhythm::tied(position - reader.end)?;
+            voice.events.extend(split(&Event::Note(rest), rhythms));
+        }
+
+        let mut note = Note::new(NoteName::None);
+        note.pitch = pitch;
+        note.tie = tie;
+        note.dynamic = staff.dynamic.take();
+        note.articulations = read_articulations(element);
+        note.beam = element
+            .children("beam")
+            .find(|beam| beam.attribute("number").unwrap_or("1") == "1")
+            .and_then(|beam| match beam.text.trim() {
+                "begin" => Some(Beam::Start),
+                "end" => Some(Beam::End),
+                _ => None,
+            });
+        if note.pitch.note_name == NoteName::None {
+            note.rhythm.duration_type(DurationType::Rest);
+        }
+        let rhythms = match read_rhythm(element, number)? {
+            Some(rhythm) if rhythm.duration() == duration => vec![rhythm],
+            _ => Rhythm::tied(duration)
+                .map_err(|_| format!("Unsupported duration {} in measure {}.", duration, number))?,
+        };
+        let event = Event::Note(Note {
+            rhythm: Rhythm {
+                duration_type: note.rhythm.duration_type,
+                ..rhythms[0].clone()
+            },
+            ..note
+        });
+        let events = split(&event, rhythms);
+        reader.last = events.len();
+        reader.end = position + duration;
+        voice.events.extend(events);
+        self.position = position + duration;
+        Ok(())
+    }
+}
+
+fn read_key(key: &Element, measure: &str) -> Result<KeySignature, String> {
+    let fifths = key.child_text("fifths").unwrap_or("");
+    match fifths.parse() {
+        Ok(fifths) => KeySignature::from_fifths(fifths),
+        Err(_) => Err(format!("Unsupported key signature in measure {}.", measure)),
+    }
+}
+
+fn read_time(time: &Element, measure: &str) -> Result<TimeSignature, String> {
+    let invalid = || format!("Unsupported time signature in measure {}.", measure);
+    if time.children("beats").count() != 1 {
+        return Err(invalid());
+    }
+    let beats = time.child_text("beats").unwrap_or("");
+    let denominator = time
+        .child_text("beat-type")
+        .and_then(|beat_type| beat_type.parse().ok())
+        .ok_or_else(invalid)?;
+    if beats.contains('+') {
+        let groups = beats
+            .split('+')
+            .map(|group| group.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<Vec<u8>, String>>()?;
+        TimeSignature::additive(&groups, denominator)
+    } else {
+        TimeSignature::from_fraction(beats.parse().map_err(|_| invalid())?, denominator)
+    }
+}
+
+fn read_clef(clef: &Element, measure: &str) -> Result<Clef, String> {
+    let sign = clef.child_text("sign").unwrap_or("");
+    let line = match clef.child_text("line") {
+        Some(line) => line.parse().ok(),
+        None => match sign {
+            "G" => Some(2),
+            "F" => Some(4),
+            "C" | "percussion" => Some(3),
+            _ => None,
+        },
+    };
+    let octave_change = match clef.child_text("clef-octave-change") {
+        Some(change) => change.parse().ok(),
+        None => Some(0),
+    };
+    match (line, octave_change) {
+        (Some(line), Some(octave_change)) => Clef::from_sign(sign, line, octave_change),
+        _ => None,
+    }
+    .ok_or_else(|| format!("Unsupported {} clef in measure {}.", sign, measure))
+}
+
+/// Read the pitch of a note, or a pitch without a name for a rest.
+fn read_pitch(note: &Element, measure: &str) -> Result<Pitch, String> {
+    let pitch = match note.child("pitch") {
+        Some(pitch) => pitch,
+        None if note.child("rest").is_some() => {
+            let mut rest = Pitch::new(NoteName::None);
+            rest.octave(Octave::None);
+            return Ok(rest);
+        }
+        None => {
+            return Err(format!(
+                "Unsupported unpitched note in measure {}.",
+                measure
+            ))
+        }
+    };
+    let invalid = || format!("Invalid pitch in measure {}.", measure);
+    let step = pitch.child_text("step").unwrap_or("");
+    let index = "CDEFGAB"
+        .find(step)
+        .filter(|_| step.len() == 1)
+        .ok_or_else(invalid)?;
+    let mut result = Pitch::new(NoteName::from_diatonic_index(index as i32));
+    let octave = pitch
+        .child_text("octave")
+        .and_then(|octave| octave.parse().ok())
+        .and_then(Octave::from_number)
+        .ok_or_else(invalid)?;
+    result.octave(octave);
+    if let Some(alter) = pitch.child_text("alter") {
+        let alteration = parse_decimal(alter).ok_or_else(invalid)?;
+        result.accidental(Accidental::from_alteration(alteration));
+    }
+    Ok(result)
+}
+
+/// Parse a decimal number, e.g. `-0.5`. Numbers with up to six decimal
+/// places are parsed exactly, and longer ones, e.g. `0.3333333333333333`,
+/// are approximated by the nearest fraction with a denominator of at most
+/// 1000.
+fn parse_decimal(text: &str) -> Option<Alteration> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = match digits.find('.') {
+        Some(point) => (&digits[..point], &digits[point + 1..]),
+        None => (digits, ""),
+    };
+    if whole.is_empty() && fraction.is_empty() || !fraction.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let mut value = Alteration::from_integer(if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    });
+    if fraction.len() > 6 {
+        let digits = &fraction[..9.min(fraction.len())];
+        value += approximate(digits.parse().ok()?, 10i64.pow(digits.len() as u32), 1000);
+    } else if !fraction.is_empty() {
+        let denominator = 10i32.pow(fraction.len() as u32);
+        value += Alteration::new(fraction.parse().ok()?, denominator);
+    }
+    Some(if negative { -value } else { value })
+}
+
+/// Approximate a fraction by the last convergent of its continued fraction
+/// whose denominator is at most `limit`.
+fn approximate(mut numerator: i64, mut denominator: i64, limit: i64) -> Alteration {
+    // the last two convergents
+    let (mut h0, mut h1, mut k0, mut k1) = (0, 1, 1, 0);
+    while denominator != 0 {
+        let term = numerator / denominator;
+        let (h2, k2) = (term * h1 + h0, term * k1 + k0);
+        if k2 > limit {
+            break;
+        }
+        (h0, h1, k0, k1) = (h1, h2, k1, k2);
+        (numerator, denominator) = (denominator, numerator - term * denominator);
+    }
+    Alteration::new(h1 as i32, k1 as i32)
+}
+
+/// Read the written rhythm of a note from its `<type>` and `<dot>`s, if it
+/// has a type.
+fn read_rhythm(note: &Element, measure: &str) -> Result<Option<Rhythm>, String> {
+    let name = match note.child_text("type") {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let length = match name {
+        "whole" => Length::Whole,
+        "half" => Length::Half,
+        "quarter" => Length::Quarter,
+        "eighth" => Length::Eighth,
+        "16th" => Length::Sixteenth,
+        "32nd" => Length::ThirtySecond,
+        "64th" => Length::SixtyFourth,
+        "128th" => Length::OneTwentyEighth,
+        _ => return Ok(None),
+    };
+    let dots = note.children("dot").count();
+    if dots > 255 {
+        return Err(format!("Too many dots in measure {}.", measure));
+    }
+    let mut rhythm = Rhythm::new();
+    rhythm.length(length);
+    rhythm.dots(dots as u8);
+    Ok(Some(rhythm))
+}
+
+fn read_articulations(note: &Element) -> Vec<Articulation> {
+    let mut articulations = vec![];
+    for notations in note.children("notations") {
+        for group in &notations.children {
+            match group.name.as_str() {
+                "articulations" => articulations.extend(group.children.iter().filter_map(|mark| {
+                    match mark.name.as_str() {
+                        "accent" => Some(Articulation::Accent),
+                        "strong-accent" => Some(Articulation::Marcato),
+                        "staccatissimo" => Some(Articulation::Staccatissimo),
+                        "staccato" => Some(Articulation::Staccato),
+                        "tenuto" => Some(Articulation::Tenuto),
+                        "detached-legato" => Some(Articulation::Portato),
+                        _ => None,
+                    }
+                })),
+                "ornaments" if group.child("trill-mark").is_some() => {
+                    articulations.push(Articulation::Trill)
+                }
+                "fermata" => articulations.push(Articulation::Fermata),
+                _ => {}
+            }
+        }
+    }
+    articulations
+}
+
+/// Add a pitch to a note or chord, turning a note into a chord.
+fn add_pitch(event: &mut Event, pitch: Pitch, tie: bool) {
+    match event {
+        Event::Note(note) => {
+            let mut chord = Chord::new(vec![note.pitch.clone(), pitch]);
+            chord.rhythm = note.rhythm.clone();
+            chord.tie = note.tie || tie;
+            chord.articulations = note.articulations.clone();
+            chord.beam = note.beam;
+            chord.dynamic = note.dynamic;
+            *event = Event::Chord(chord);
+        }
+        Event::Chord(chord) => {
+            chord.pitches.push(pitch);
+            chord.tie |= tie;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::export::musicxml::to_musicxml;
+    use crate::import::musicxml::*;
+    use crate::parser::{parse_notes, voice};
+    use crate::NoteNameLanguage;
+    #[test]
+    fn test_from_musicxml() {
+        let score = from_musicxml(include_str!("../../tests/fixtures/piano.musicxml")).unwrap();
+        assert_eq!(score.staves.len(), 2);
+        assert_eq!(
+            score.to_lilypond(NoteNameLanguage::English),
+            Ok(include_str!("../../tests/fixtures/piano.ly").to_string())
+        );
+    }
+    #[test]
+    fn test_round_trip() {
+        let mut melody = voice("r8 bf'8[\\mf c''8 d''8] ef''4-> b'4.. r16 fs'4~ fs'4");
+        let pitches = parse_notes("d' f' bf'", NoteNameLanguage::English).unwrap();
+        let mut chord = Chord::new(pitches.into_iter().map(|note| note.pitch).collect());
+        chord.rhythm.length(Length::Half);
+        chord.articulations.push(Articulation::Fermata);
+        melody.push(chord);
+        let mut upper = Staff::from(melody);
+        upper.key_signature = KeySignature::from_fifths(-2).unwrap();
+        upper.time_signature = TimeSignature::from_fraction(3, 4).unwrap();
+        upper.changes.push((2, StaffChange::Clef(Clef::Alto)));
+        let mut lower = Staff::from(voice("c,1 r2 r8"));
+        lower.voices.push(voice("e,2 f,2 g,2~ g,8"));
+        lower.clef = Clef::Bass;
+        let key_signature = KeySignature::from_fifths(7).unwrap();
+        lower
+            .changes
+            .push((1, StaffChange::KeySignature(key_signature)));
+        let time_signature = TimeSignature::additive(&[3, 2], 8).unwrap();
+        lower
+            .changes
+            .push((1, StaffChange::TimeSignature(time_signature)));
+        let mut score = Score::from(upper);
+        score.staves.push(lower);
+        let musicxml = to_musicxml(&score).unwrap();
+        assert_eq!(from_musicxml(&musicxml), Ok(score));
+    }
+    #[test]
+    fn test_round_trip_microtones() {
+        let mut melody = voice("c'4 d'4 e'2");
+        for (index, alteration) in [(0, Alteration::new(1, 3)), (2, Alteration::new(-5, 3))] {
+            if let Event::Note(note) = &mut melody.events[index] {
+                note.pitch.accidental(Accidental::Other(alteration));
+            }
+        }
+        let score = Score::from(Staff::from(melody));
+        let musicxml = to_musicxml(&score).unwrap();
+        assert!(musicxml.contains("<alter>0.3333333333333333</alter>"));
+        assert_eq!(from_musicxml(&musicxml), Ok(score));
+    }
+    #[test]
+    fn test_pickup() {
+        let mut staff = Staff::from(voice("g'4 c''2. b'2"));
+        staff.time_signature = TimeSignature::from_fraction(3, 4).unwrap();
+        staff.partial = Some(Duration::new(1, 4));
+        let score = Score::from(staff);
+        let musicxml = to_musicxml(&score).unwrap();
+        assert!(musicxml.contains("<measure number=\"0\" implicit=\"yes\">"));
+        assert!(musicxml.contains("<measure number=\"2\">"));
+        assert_eq!(from_musicxml(&musicxml), Ok(score));
+    }
+    #[test]
+    fn test_from_musicxml_errors() {
+        let part = |notes: &str| {
+            format!(
+                "<score-partwise><part id=\"P1\"><measure number=\"1\">\
+                 <attributes><divisions>1</divisions></attributes>{}\
+                 </measure></part></score-partwise>",
+                notes
+            )
+        };
+        let note = |step: &str, duration: u8| {
+            format!(
+                "<note><pitch><step>{}</step><octave>4</octave></pitch>\
+                 <duration>{}</duration></note>",
+                step, duration
+            )
+        };
+        assert_eq!(
+            from_musicxml("<score-timewise/>"),
+            Err(String::from("Timewise MusicXML is not supported."))
+        );
+        assert_eq!(
+            from_musicxml(&part(&format!(
+                "{}<backup><duration>1</duration></backup>{}",
+                note("C", 2),
+                note("D", 1)
+            ))),
+            Err(String::from("Notes overlap in voice 1 of measure 1."))
+        );
+        assert_eq!(
+            from_musicxml(&part(&note("H", 1))),
+            Err(String::from("Invalid pitch in measure 1."))
+        );
+        assert_eq!(
+            from_musicxml(&part("<backup><duration>1</duration></backup>")),
+            Err(String::from("Backup before the start of measure 1."))
+        );
+        assert!(from_musicxml(&part(
+            "<attributes><time><beats>3</beats><beat-type>5</beat-type></time></attributes>"
+        ))
+        .is_err());
+        assert!(from_musicxml(&part(
+            &note("C", 5).replace("<pitch>", "<pitch><alter>x</alter>")
+        ))
+        .is_err());
+    }
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("-1"), Some(Alteration::from_integer(-1)));
+        assert_eq!(parse_decimal("0.5"), Some(Alteration::new(1, 2)));
+        assert_eq!(parse_decimal("-1.5"), Some(Alteration::new(-3, 2)));
+        assert_eq!(parse_decimal(".25"), Some(Alteration::new(1, 4)));
+        assert_eq!(
+            parse_decimal("0.3333333333333333"),
+            Some(Alteration::new(1, 3))
+        );
+        assert_eq!(
+            parse_decimal("-1.6666666666666667"),
+            Some(Alteration::new(-5, 3))
+        );
+        assert_eq!(parse_decimal("0.1428571"), Some(Alteration::new(1, 7)));
+        assert_eq!(parse_decimal("0.33333333x"), None);
+        assert_eq!(parse_decimal("1.x"), None);
+        assert_eq!(parse_decimal("-"), None);
+    }
+}
@@ -5,7 +5,9 @@ use std::convert::TryInto;
 use crate::{
     lilypond_objects::lilypond_note::LilyPondNote,
     notation::{
-        pitch::Octave,
+        beam::Beam,
+        note::Note,
+        pitch::{Accidental, NoteName, Octave, Pitch},
         rhythm::{Dots, DurationType, Length, Rhythm},
     },
 };
@@ -83,10 +85,120 @@ fn dotted_from_lilypond(note: &LilyPondNote) -> Result<Dots, String> {
     }
 }
 
+/// Check whether the accidental of a note has a note name in LilyPond's
+/// note-name languages.
+pub fn has_named_accidental(note: &Note) -> bool {
+    note.rhythm.duration_type == DurationType::Rest
+        || !matches!(
+            Accidental::from_alteration(note.pitch.accidental.alteration()),
+            Accidental::Other(_)
+        )
+}
+
+/// Check whether the accidental of a note is a named accidental that alters
+/// the note by a whole number of semitones.
+///
+/// This is used by languages without names for quarter tones.
+pub fn has_named_chromatic_accidental(note: &Note) -> bool {
+    has_named_accidental(note)
+        && (note.rhythm.duration_type == DurationType::Rest || note.pitch.accidental.is_chromatic())
+}
+
+/// Encode a note as a Scheme music expression.
+///
+/// This is used for notes whose alteration has no note name, since LilyPond
+/// only accepts arbitrary alterations through `ly:make-pitch`, whose
+/// alteration is measured in whole tones.
+pub fn scheme_from_note(note: &Note) -> String {
+    scheme_note_event(note, &[])
+}
+
+/// Encode a note as a Scheme music expression like [`scheme_from_note`],
+/// with its beam, articulations, dynamic and tie inside the expression,
+/// since LilyPond cannot attach post-events like `~` or `-.` to a Scheme
+/// expression.
+pub fn scheme_from_note_with_post_events(note: &Note) -> String {
+    let mut events = vec![];
+    if let Some(beam) = note.beam {
+        let direction = match beam {
+            Beam::Start => -1,
+            Beam::End => 1,
+        };
+        events.push(format!(
+            "(make-music 'BeamEvent 'span-direction {})",
+            direction
+        ));
+    }
+    for articulation in &note.articulations {
+        events.push(format!(
+            "(make-music 'ArticulationEvent 'articulation-type '{})",
+            articulation.name()
+        ));
+    }
+    if let Some(dynamic) = note.dynamic {
+        events.push(format!(
+            "(make-music 'AbsoluteDynamicEvent 'text \"{}\")",
+            dynamic.name()
+        ));
+    }
+    if note.tie {
+        events.push(String::from("(make-music 'TieEvent)"));
+    }
+    scheme_note_event(note, &events)
+}
+
+fn scheme_note_event(note: &Note, articulations: &[String]) -> String {
+    let articulations = if articulations.is_empty() {
+        String::new()
+    } else {
+        format!(" 'articulations (list {})", articulations.join(" "))
+    };
+    format!(
+        "$(make-music 'NoteEvent 'pitch {} 'duration (ly:make-duration {} {}){})",
+        scheme_from_pitch(&note.pitch),
+        note.rhythm.length.as_u16().trailing_zeros(),
+        note.rhythm.dots.get_num_dots(),
+        articulations,
+    )
+}
+
+/// Encode a pitch as a Scheme `ly:make-pitch` expression, for the same
+/// pitches as [`scheme_from_note`].
+pub fn scheme_from_pitch(pitch: &Pitch) -> String {
+    let octave: i8 = match pitch.octave {
+        Octave::S0 => -4,
+        Octave::S1 => -3,
+        Octave::S2 => -2,
+        Octave::S3 | Octave::None => -1,
+        Octave::S4 => 0,
+        Octave::S5 => 1,
+        Octave::S6 => 2,
+        Octave::S7 => 3,
+        Octave::S8 => 4,
+        Octave::S9 => 5,
+    };
+    let note_name: u8 = match pitch.note_name {
+        NoteName::C | NoteName::None => 0,
+        NoteName::D => 1,
+        NoteName::E => 2,
+        NoteName::F => 3,
+        NoteName::G => 4,
+        NoteName::A => 5,
+        NoteName::B => 6,
+    };
+    format!(
+        "(ly:make-pitch {} {} {})",
+        octave,
+        note_name,
+        pitch.accidental.alteration() / 2,
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::lilypond_objects::lilypond_note::LilyPondNote;
+    use crate::notation::pitch::Alteration;
     #[test]
     fn test_get_duration_type() {
         let note = LilyPondNote::new("r8").unwrap();
@@ -124,6 +236,24 @@ mod test {
         assert_eq!(length, Length::SixtyFourth);
     }
     #[test]
+    fn test_scheme_from_note() {
+        let mut note = Note::new(NoteName::E);
+        note.pitch
+            .accidental(Accidental::Other(Alteration::new(1, 3)));
+        note.pitch.octave(Octave::S5);
+        note.rhythm.length(Length::Eighth);
+        note.rhythm.dots(1);
+        assert!(!has_named_accidental(&note));
+        assert_eq!(
+            scheme_from_note(&note),
+            "$(make-music 'NoteEvent 'pitch (ly:make-pitch 1 2 1/6) 'duration (ly:make-duration 3 1))"
+        );
+        assert_eq!(scheme_from_pitch(&note.pitch), "(ly:make-pitch 1 2 1/6)");
+        note.pitch
+            .accidental(Accidental::Other(Alteration::new(-3, 2)));
+        assert!(has_named_accidental(&note));
+    }
+    #[test]
     fn get_dot() {
         let ly_note = LilyPondNote::new("r8.").unwrap();
         assert_eq!(dotted_from_lilypond(&ly_note).unwrap().get_num_dots(), 1);
@@ -8,6 +8,7 @@ use crate::{
         pitch::{Accidental, NoteName, Pitch},
         rhythm::DurationType,
     },
+    AccidentalSpelling,
 };
 
 pub static LANGUAGE_STR: &str = "english";
@@ -15,7 +16,8 @@ pub static LANGUAGE_STR: &str = "english";
 pub static NOTE_REGEX_STR: &str = r"(?x-u)
     # Flags: x = whitespace allowed, -u = no unicode support
     ^(?P<note_name>[a-gr]) # note name or rest
-    (?P<accidental>(?:f{0,2}|s{0,2})| # one-letter accidentals
+    (?P<accidental>(?:f{0,2}|s{0,2}|x)| # one-letter accidentals
+    (?:t?q[sf])| # quarter-tone accidentals
     (?:(?:-sharp)?|(?:-flat)?|(?:-sharpsharp)|(?:-flatflat))) # spelled-out accidentals
     (?P<octave>(?:(?:,{0,3})|(?:'{0,6}))?) # octave transposition characters
     (?P<duration>(?:1|2|4|8|(?:16)|(?:32)|(?:64)|(?:128))?) # Durations
@@ -23,14 +25,36 @@ pub static NOTE_REGEX_STR: &str = r"(?x-u)
     ";
 
 pub fn lilypond_from_note(note: &Note) -> String {
-    format!(
-        "{}{}{}{}{}",
+    match lilypond_pitch(note) {
+        Some(pitch) => format!("{}{}{}", pitch, note.rhythm.length, note.rhythm.dots),
+        None => common::scheme_from_note(note),
+    }
+}
+
+pub fn lilypond_from_note_with_spelling(note: &Note, spelling: AccidentalSpelling) -> String {
+    match lilypond_pitch_with_spelling(note, spelling) {
+        Some(pitch) => format!("{}{}{}", pitch, note.rhythm.length, note.rhythm.dots),
+        None => common::scheme_from_note(note),
+    }
+}
+
+pub fn lilypond_pitch(note: &Note) -> Option<String> {
+    lilypond_pitch_with_spelling(note, AccidentalSpelling::Short)
+}
+
+fn lilypond_pitch_with_spelling(note: &Note, spelling: AccidentalSpelling) -> Option<String> {
+    if !common::has_named_accidental(note) {
+        return None;
+    }
+    Some(format!(
+        "{}{}{}",
         lilypond_from_note_name(note),
-        lilypond_from_accidental(note),
+        match spelling {
+            AccidentalSpelling::Short => lilypond_from_accidental(note),
+            AccidentalSpelling::Long => lilypond_from_long_accidental(note),
+        },
         note.pitch.octave,
-        note.rhythm.length,
-        note.rhythm.dots,
-    )
+    ))
 }
 
 fn lilypond_from_note_name(note: &Note) -> &str {
@@ -50,12 +74,29 @@ fn lilypond_from_note_name(note: &Note) -> &str {
 }
 
 fn lilypond_from_accidental(note: &Note) -> &str {
-    match note.pitch.accidental {
+    match Accidental::from_alteration(note.pitch.accidental.alteration()) {
         Accidental::None => "",
         Accidental::Flat => "f",
         Accidental::DoubleFlat => "ff",
         Accidental::Sharp => "s",
         Accidental::DoubleSharp => "ss",
+        Accidental::QuarterSharp => "qs",
+        Accidental::ThreeQuarterSharp => "tqs",
+        Accidental::QuarterFlat => "qf",
+        Accidental::ThreeQuarterFlat => "tqf",
+        // unnamed alterations are written as Scheme by `lilypond_from_note`
+        Accidental::Other(_) => "",
+    }
+}
+
+fn lilypond_from_long_accidental(note: &Note) -> &str {
+    match Accidental::from_alteration(note.pitch.accidental.alteration()) {
+        Accidental::Flat => "-flat",
+        Accidental::DoubleFlat => "-flatflat",
+        Accidental::Sharp => "-sharp",
+        Accidental::DoubleSharp => "-sharpsharp",
+        // quarter tones only have short names
+        _ => lilypond_from_accidental(note),
     }
 }
 
@@ -67,6 +108,10 @@ pub fn note_from_lilypond(note: &LilyPondNote) -> Result<Note, String> {
             octave: common::octave_from_lilypond(note)?,
         },
         rhythm: common::rhythm_from_lilypond(note)?,
+        tie: false,
+        articulations: vec![],
+        beam: None,
+        dynamic: None,
     })
 }
 
@@ -91,10 +136,14 @@ fn accidental_from_lilypond(note: &LilyPondNote) -> Result<Accidental, String> {
         DurationType::Rest => Ok(Accidental::None),
         DurationType::Note => match note.get_capture("accidental").as_str() {
             "" => Ok(Accidental::None),
-            "s" => Ok(Accidental::Sharp),
-            "ss" => Ok(Accidental::DoubleSharp),
-            "f" => Ok(Accidental::Flat),
-            "ff" => Ok(Accidental::DoubleFlat),
+            "s" | "-sharp" => Ok(Accidental::Sharp),
+            "ss" | "x" | "-sharpsharp" => Ok(Accidental::DoubleSharp),
+            "f" | "-flat" => Ok(Accidental::Flat),
+            "ff" | "-flatflat" => Ok(Accidental::DoubleFlat),
+            "qs" => Ok(Accidental::QuarterSharp),
+            "tqs" => Ok(Accidental::ThreeQuarterSharp),
+            "qf" => Ok(Accidental::QuarterFlat),
+            "tqf" => Ok(Accidental::ThreeQuarterFlat),
             e => Err(format!("Invalid accidental '{}'.", e)),
         },
     }
@@ -149,6 +198,62 @@ mod test {
         note.pitch.accidental(Accidental::DoubleSharp);
         let accidental_str = lilypond_from_accidental(&note);
         assert_eq!("ss", accidental_str);
+        note.pitch.accidental(Accidental::QuarterSharp);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("qs", accidental_str);
+        note.pitch.accidental(Accidental::ThreeQuarterSharp);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("tqs", accidental_str);
+        note.pitch.accidental(Accidental::QuarterFlat);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("qf", accidental_str);
+        note.pitch.accidental(Accidental::ThreeQuarterFlat);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("tqf", accidental_str);
+    }
+    #[test]
+    fn test_lilypond_from_long_accidental() {
+        let mut note = Note::new(NoteName::A);
+        let accidental_str = lilypond_from_long_accidental(&note);
+        assert_eq!("", accidental_str);
+        note.pitch.accidental(Accidental::Flat);
+        let accidental_str = lilypond_from_long_accidental(&note);
+        assert_eq!("-flat", accidental_str);
+        note.pitch.accidental(Accidental::Sharp);
+        let accidental_str = lilypond_from_long_accidental(&note);
+        assert_eq!("-sharp", accidental_str);
+        note.pitch.accidental(Accidental::DoubleFlat);
+        let accidental_str = lilypond_from_long_accidental(&note);
+        assert_eq!("-flatflat", accidental_str);
+        note.pitch.accidental(Accidental::DoubleSharp);
+        let accidental_str = lilypond_from_long_accidental(&note);
+        assert_eq!("-sharpsharp", accidental_str);
+        note.pitch.accidental(Accidental::QuarterSharp);
+        let accidental_str = lilypond_from_long_accidental(&note);
+        assert_eq!("qs", accidental_str);
+    }
+    #[test]
+    fn test_round_trip() {
+        let accidentals = [
+            Accidental::None,
+            Accidental::Sharp,
+            Accidental::DoubleSharp,
+            Accidental::Flat,
+            Accidental::DoubleFlat,
+            Accidental::QuarterSharp,
+            Accidental::ThreeQuarterSharp,
+            Accidental::QuarterFlat,
+            Accidental::ThreeQuarterFlat,
+        ];
+        for spelling in [AccidentalSpelling::Short, AccidentalSpelling::Long] {
+            for accidental in accidentals {
+                let mut note = Note::new(NoteName::E);
+                note.pitch.accidental(accidental);
+                let ly_str = lilypond_from_note_with_spelling(&note, spelling);
+                let ly_note = LilyPondNote::new(&ly_str).unwrap();
+                assert_eq!(note_from_lilypond(&ly_note).unwrap(), note);
+            }
+        }
     }
     fn test_regex_case(note: &str) {
         assert!(LILYPOND_NOTE_REGEX.is_match(note));
@@ -179,6 +284,15 @@ mod test {
             "ff,,,8.",
             "fff,,,8.",
             "gs''''''16.",
+            "cqs",
+            "dqf,4",
+            "etqs''8.",
+            "ftqf16",
+            "gx",
+            "a-sharp,8",
+            "b-flat''2.",
+            "c-sharpsharp16",
+            "d-flatflat,,,1",
         ];
         for n in notes {
             test_regex_case(n);
@@ -201,6 +315,33 @@ mod test {
         let ly_note = LilyPondNote::new("aff").unwrap();
         let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
         assert_eq!(accidental_type, Accidental::DoubleFlat);
+        let ly_note = LilyPondNote::new("cqs").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::QuarterSharp);
+        let ly_note = LilyPondNote::new("dtqs").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::ThreeQuarterSharp);
+        let ly_note = LilyPondNote::new("eqf").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::QuarterFlat);
+        let ly_note = LilyPondNote::new("btqf").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::ThreeQuarterFlat);
+        let ly_note = LilyPondNote::new("cx").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::DoubleSharp);
+        let ly_note = LilyPondNote::new("d-sharp").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::Sharp);
+        let ly_note = LilyPondNote::new("e-sharpsharp").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::DoubleSharp);
+        let ly_note = LilyPondNote::new("f-flat").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::Flat);
+        let ly_note = LilyPondNote::new("g-flatflat").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::DoubleFlat);
     }
     #[test]
     fn test_note_name_from_lilypond() {
@@ -0,0 +1,261 @@
+//! A module for French LilyPond note input.
+//!
+//! D may be written `re` or `ré`; it is written as `re`.
+
+use super::common;
+use crate::{
+    lilypond_objects::lilypond_note::LilyPondNote,
+    notation::{
+        note::Note,
+        pitch::{Accidental, NoteName, Pitch},
+        rhythm::DurationType,
+    },
+};
+
+pub static LANGUAGE_STR: &str = "français";
+
+pub static NOTE_REGEX_STR: &str = r"(?x-u)
+    # Flags: x = whitespace allowed, -u = no unicode support
+    ^(?P<note_name>do|re|ré|mi|fa|sol|la|si|r) # note name or rest
+    (?P<accidental>(?:d{0,2}|b{0,2}|x)| # accidentals
+    (?:sd|sb|dsd|bsb)) # quarter-tone accidentals
+    (?P<octave>(?:(?:,{0,3})|(?:'{0,6}))?) # octave transposition characters
+    (?P<duration>(?:1|2|4|8|(?:16)|(?:32)|(?:64)|(?:128))?) # Durations
+    (?P<dot>\.{0,255})$ # optional dots and end of line
+    ";
+
+pub fn lilypond_from_note(note: &Note) -> String {
+    match lilypond_pitch(note) {
+        Some(pitch) => format!("{}{}{}", pitch, note.rhythm.length, note.rhythm.dots),
+        None => common::scheme_from_note(note),
+    }
+}
+
+pub fn lilypond_pitch(note: &Note) -> Option<String> {
+    if !common::has_named_accidental(note) {
+        return None;
+    }
+    Some(format!(
+        "{}{}{}",
+        lilypond_from_note_name(note),
+        lilypond_from_accidental(note),
+        note.pitch.octave,
+    ))
+}
+
+fn lilypond_from_note_name(note: &Note) -> &str {
+    match note.rhythm.duration_type {
+        DurationType::Rest => "r",
+        DurationType::Note => match note.pitch.note_name {
+            NoteName::A => "la",
+            NoteName::B => "si",
+            NoteName::C => "do",
+            NoteName::D => "re",
+            NoteName::E => "mi",
+            NoteName::F => "fa",
+            NoteName::G => "sol",
+            NoteName::None => "r",
+        },
+    }
+}
+
+fn lilypond_from_accidental(note: &Note) -> &str {
+    match Accidental::from_alteration(note.pitch.accidental.alteration()) {
+        Accidental::None => "",
+        Accidental::Flat => "b",
+        Accidental::DoubleFlat => "bb",
+        Accidental::Sharp => "d",
+        Accidental::DoubleSharp => "dd",
+        Accidental::QuarterSharp => "sd",
+        Accidental::ThreeQuarterSharp => "dsd",
+        Accidental::QuarterFlat => "sb",
+        Accidental::ThreeQuarterFlat => "bsb",
+        // unnamed alterations are written as Scheme by `lilypond_from_note`
+        Accidental::Other(_) => "",
+    }
+}
+
+pub fn note_from_lilypond(note: &LilyPondNote) -> Result<Note, String> {
+    Ok(Note {
+        pitch: Pitch {
+            note_name: note_name_from_lilypond(note)?,
+            accidental: accidental_from_lilypond(note)?,
+            octave: common::octave_from_lilypond(note)?,
+        },
+        rhythm: common::rhythm_from_lilypond(note)?,
+        tie: false,
+        articulations: vec![],
+        beam: None,
+        dynamic: None,
+    })
+}
+
+fn note_name_from_lilypond(note: &LilyPondNote) -> Result<NoteName, String> {
+    match common::duration_type_from_lilypond(note) {
+        DurationType::Rest => Ok(NoteName::None),
+        DurationType::Note => match note.get_capture("note_name").as_str() {
+            "do" => Ok(NoteName::C),
+            "re" | "ré" => Ok(NoteName::D),
+            "mi" => Ok(NoteName::E),
+            "fa" => Ok(NoteName::F),
+            "sol" => Ok(NoteName::G),
+            "la" => Ok(NoteName::A),
+            "si" => Ok(NoteName::B),
+            e => Err(format!("Invalid note name '{}'.", e)),
+        },
+    }
+}
+
+fn accidental_from_lilypond(note: &LilyPondNote) -> Result<Accidental, String> {
+    match common::duration_type_from_lilypond(note) {
+        DurationType::Rest => Ok(Accidental::None),
+        DurationType::Note => match note.get_capture("accidental").as_str() {
+            "" => Ok(Accidental::None),
+            "d" => Ok(Accidental::Sharp),
+            "dd" | "x" => Ok(Accidental::DoubleSharp),
+            "b" => Ok(Accidental::Flat),
+            "bb" => Ok(Accidental::DoubleFlat),
+            "sd" => Ok(Accidental::QuarterSharp),
+            "dsd" => Ok(Accidental::ThreeQuarterSharp),
+            "sb" => Ok(Accidental::QuarterFlat),
+            "bsb" => Ok(Accidental::ThreeQuarterFlat),
+            e => Err(format!("Invalid accidental '{}'.", e)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NoteNameLanguage;
+    fn francais(note: &str) -> Result<LilyPondNote, String> {
+        LilyPondNote::with_language(note, NoteNameLanguage::Francais)
+    }
+    #[test]
+    fn test_lilypond_from_note_name() {
+        let note_names = [
+            (NoteName::C, "do"),
+            (NoteName::D, "re"),
+            (NoteName::E, "mi"),
+            (NoteName::F, "fa"),
+            (NoteName::G, "sol"),
+            (NoteName::A, "la"),
+            (NoteName::B, "si"),
+        ];
+        for (note_name, note_name_str) in note_names {
+            let note = Note::new(note_name);
+            assert_eq!(note_name_str, lilypond_from_note_name(&note));
+        }
+    }
+    #[test]
+    fn test_lilypond_from_accidental() {
+        let accidentals = [
+            (Accidental::None, ""),
+            (Accidental::Flat, "b"),
+            (Accidental::DoubleFlat, "bb"),
+            (Accidental::Sharp, "d"),
+            (Accidental::DoubleSharp, "dd"),
+            (Accidental::QuarterSharp, "sd"),
+            (Accidental::ThreeQuarterSharp, "dsd"),
+            (Accidental::QuarterFlat, "sb"),
+            (Accidental::ThreeQuarterFlat, "bsb"),
+        ];
+        let mut note = Note::new(NoteName::A);
+        for (accidental, accidental_str) in accidentals {
+            note.pitch.accidental(accidental);
+            assert_eq!(accidental_str, lilypond_from_accidental(&note));
+        }
+    }
+    #[test]
+    fn test_regex() {
+        let notes = [
+            "r",
+            "do",
+            "réb",
+            "reb",
+            "mid,",
+            "fadd'",
+            "solx,,1",
+            "labb''2.",
+            "si4",
+            "dosd8",
+            "rébsb,16.",
+            "r128",
+        ];
+        for note in notes {
+            assert!(
+                NoteNameLanguage::Francais.note_regex().is_match(note),
+                "{}",
+                note
+            );
+        }
+    }
+    #[test]
+    fn test_accidental_from_lilypond() {
+        let accidentals = [
+            ("r8", Accidental::None),
+            ("fad", Accidental::Sharp),
+            ("soldd", Accidental::DoubleSharp),
+            ("fax", Accidental::DoubleSharp),
+            ("solb", Accidental::Flat),
+            ("fabb", Accidental::DoubleFlat),
+            ("solsd", Accidental::QuarterSharp),
+            ("fadsd", Accidental::ThreeQuarterSharp),
+            ("solsb", Accidental::QuarterFlat),
+            ("fabsb", Accidental::ThreeQuarterFlat),
+        ];
+        for (ly_str, accidental) in accidentals {
+            let ly_note = francais(ly_str).unwrap();
+            assert_eq!(accidental_from_lilypond(&ly_note).unwrap(), accidental);
+        }
+    }
+    #[test]
+    fn test_note_name_from_lilypond() {
+        let note_names = [
+            ("r8", NoteName::None),
+            ("do'4", NoteName::C),
+            ("re'4", NoteName::D),
+            ("ré'4", NoteName::D),
+            ("mi'4", NoteName::E),
+            ("fa'4", NoteName::F),
+            ("sol'4", NoteName::G),
+            ("la'4", NoteName::A),
+            ("si'4", NoteName::B),
+        ];
+        for (ly_str, note_name) in note_names {
+            let ly_note = francais(ly_str).unwrap();
+            assert_eq!(note_name_from_lilypond(&ly_note).unwrap(), note_name);
+        }
+    }
+    #[test]
+    fn test_round_trip() {
+        let note_names = [
+            NoteName::C,
+            NoteName::D,
+            NoteName::E,
+            NoteName::F,
+            NoteName::G,
+            NoteName::A,
+            NoteName::B,
+        ];
+        let accidentals = [
+            Accidental::None,
+            Accidental::Sharp,
+            Accidental::DoubleSharp,
+            Accidental::Flat,
+            Accidental::DoubleFlat,
+            Accidental::QuarterSharp,
+            Accidental::ThreeQuarterSharp,
+            Accidental::QuarterFlat,
+            Accidental::ThreeQuarterFlat,
+        ];
+        for note_name in note_names {
+            for accidental in accidentals {
+                let mut note = Note::new(note_name);
+                note.pitch.accidental(accidental);
+                let ly_note = francais(&lilypond_from_note(&note)).unwrap();
+                assert_eq!(note_from_lilypond(&ly_note).unwrap(), note);
+            }
+        }
+    }
+}
@@ -7,22 +7,57 @@
 //! Each language module must define the following public members:
 //!
 //! - `pub static LANGUAGE_STRING: &str`: The string to be used by LilyPond to
-//! select the language, e.g. `"english"` or `"nederlands"`.
+//!   select the language, e.g. `"english"` or `"nederlands"`.
 //! - `pub static NOTE_REGEX: &str`: A regular expression string which is used
-//! to compile
-//! `lilypond::lilypond_objects::lilypond_note::LILYPOND_NOTE_REGEX`. The string
-//! must define five named capture groups as described in that documentation.
+//!   to compile
+//!   `lilypond::lilypond_objects::lilypond_note::LILYPOND_NOTE_REGEX`. The
+//!   string must define five named capture groups as described in that
+//!   documentation.
 //! - `pub fn from(note: &note) -> String`: A function that generates a string
-//! from the data in the note object according to the chosen language.
+//!   from the data in the note object according to the chosen language.
+//! - `pub fn lilypond_pitch(note: &Note) -> Option<String>`: A function that
+//!   generates the note name, accidental and octave of the note, or `None` if
+//!   its accidental has no name in the language.
+
+use std::collections::HashMap;
 
 use crate::{
-    lilypond_objects::lilypond_note::LilyPondNote, notation::note::Note, NoteNameLanguage,
+    lilypond_objects::lilypond_note::LilyPondNote,
+    notation::{articulation::Articulation, note::Note, pitch::Pitch, voice::Event},
+    AccidentalSpelling, NoteNameLanguage,
 };
 use lazy_static::lazy_static;
+use regex::Regex;
 
+mod catala;
 mod common;
+mod deutsch;
 mod english;
+mod espanol;
+mod francais;
+mod italiano;
 mod nederlands;
+mod norsk;
+mod portugues;
+mod suomi;
+mod svenska;
+mod vlaams;
+
+/// Every supported [`NoteNameLanguage`].
+static LANGUAGES: [NoteNameLanguage; 12] = [
+    NoteNameLanguage::English,
+    NoteNameLanguage::Nederlands,
+    NoteNameLanguage::Catala,
+    NoteNameLanguage::Deutsch,
+    NoteNameLanguage::Espanol,
+    NoteNameLanguage::Francais,
+    NoteNameLanguage::Italiano,
+    NoteNameLanguage::Norsk,
+    NoteNameLanguage::Portugues,
+    NoteNameLanguage::Suomi,
+    NoteNameLanguage::Svenska,
+    NoteNameLanguage::Vlaams,
+];
 
 lazy_static! {
     /// The string to be used by LilyPond to select the language,
@@ -30,10 +65,7 @@ lazy_static! {
     ///
     /// Its value is dependent on that of
     /// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE].
-    pub static ref LANGUAGE_STR: &'static str = match *crate::NOTE_NAME_LANGUAGE {
-        NoteNameLanguage::English => english::LANGUAGE_STR,
-        NoteNameLanguage::Nederlands => nederlands::LANGUAGE_STR,
-    };
+    pub static ref LANGUAGE_STR: &'static str = crate::NOTE_NAME_LANGUAGE.language_str();
     /// A regular expression string which is used to match and parse LilyPond
     /// notes.
     ///
@@ -43,23 +75,322 @@ lazy_static! {
     /// This is compiled to
     /// [`LILYPOND_NOTE_REGEX`][struct@crate::lilypond_objects::lilypond_note::LILYPOND_NOTE_REGEX]. See
     /// its documentation for more details.
-    pub static ref NOTE_REGEX_STR: &'static str = match *crate::NOTE_NAME_LANGUAGE {
-        NoteNameLanguage::English => english::NOTE_REGEX_STR,
-        NoteNameLanguage::Nederlands => nederlands::NOTE_REGEX_STR,
-    };
+    pub static ref NOTE_REGEX_STR: &'static str = crate::NOTE_NAME_LANGUAGE.note_regex_str();
+    /// The compiled note regex of every language, so that each is only
+    /// compiled once.
+    static ref NOTE_REGEXES: HashMap<NoteNameLanguage, Regex> = LANGUAGES
+        .iter()
+        .map(|language| (*language, Regex::new(language.note_regex_str()).unwrap()))
+        .collect();
+}
+
+impl NoteNameLanguage {
+    /// The string to be used by LilyPond to select the language, e.g.
+    /// `"english"` or `"nederlands"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// assert_eq!(NoteNameLanguage::Nederlands.language_str(), "nederlands");
+    /// ```
+    pub fn language_str(&self) -> &'static str {
+        match self {
+            NoteNameLanguage::English => english::LANGUAGE_STR,
+            NoteNameLanguage::Nederlands => nederlands::LANGUAGE_STR,
+            NoteNameLanguage::Catala => catala::LANGUAGE_STR,
+            NoteNameLanguage::Deutsch => deutsch::LANGUAGE_STR,
+            NoteNameLanguage::Espanol => espanol::LANGUAGE_STR,
+            NoteNameLanguage::Francais => francais::LANGUAGE_STR,
+            NoteNameLanguage::Italiano => italiano::LANGUAGE_STR,
+            NoteNameLanguage::Norsk => norsk::LANGUAGE_STR,
+            NoteNameLanguage::Portugues => portugues::LANGUAGE_STR,
+            NoteNameLanguage::Suomi => suomi::LANGUAGE_STR,
+            NoteNameLanguage::Svenska => svenska::LANGUAGE_STR,
+            NoteNameLanguage::Vlaams => vlaams::LANGUAGE_STR,
+        }
+    }
+
+    /// A regular expression string which is used to match and parse LilyPond
+    /// notes in this language.
+    ///
+    /// See
+    /// [`LILYPOND_NOTE_REGEX`][struct@crate::lilypond_objects::lilypond_note::LILYPOND_NOTE_REGEX]
+    /// for a description of its capture groups.
+    pub fn note_regex_str(&self) -> &'static str {
+        match self {
+            NoteNameLanguage::English => english::NOTE_REGEX_STR,
+            NoteNameLanguage::Nederlands => nederlands::NOTE_REGEX_STR,
+            NoteNameLanguage::Catala => catala::NOTE_REGEX_STR,
+            NoteNameLanguage::Deutsch => deutsch::NOTE_REGEX_STR,
+            NoteNameLanguage::Espanol => espanol::NOTE_REGEX_STR,
+            NoteNameLanguage::Francais => francais::NOTE_REGEX_STR,
+            NoteNameLanguage::Italiano => italiano::NOTE_REGEX_STR,
+            NoteNameLanguage::Norsk => norsk::NOTE_REGEX_STR,
+            NoteNameLanguage::Portugues => portugues::NOTE_REGEX_STR,
+            NoteNameLanguage::Suomi => suomi::NOTE_REGEX_STR,
+            NoteNameLanguage::Svenska => svenska::NOTE_REGEX_STR,
+            NoteNameLanguage::Vlaams => vlaams::NOTE_REGEX_STR,
+        }
+    }
+
+    /// The compiled [`note_regex_str`][NoteNameLanguage::note_regex_str] of
+    /// this language.
+    ///
+    /// Each language's regex is compiled once and cached.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// assert!(NoteNameLanguage::Nederlands.note_regex().is_match("bes,4"));
+    /// assert!(!NoteNameLanguage::English.note_regex().is_match("bes,4"));
+    /// ```
+    pub fn note_regex(&self) -> &'static Regex {
+        &NOTE_REGEXES[self]
+    }
+
+    /// Convert a note into a LilyPond-formatted String in this language.
+    pub fn lilypond_from_note(&self, note: &Note) -> String {
+        match self {
+            NoteNameLanguage::English => english::lilypond_from_note(note),
+            NoteNameLanguage::Nederlands => nederlands::lilypond_from_note(note),
+            NoteNameLanguage::Catala => catala::lilypond_from_note(note),
+            NoteNameLanguage::Deutsch => deutsch::lilypond_from_note(note),
+            NoteNameLanguage::Espanol => espanol::lilypond_from_note(note),
+            NoteNameLanguage::Francais => francais::lilypond_from_note(note),
+            NoteNameLanguage::Italiano => italiano::lilypond_from_note(note),
+            NoteNameLanguage::Norsk => norsk::lilypond_from_note(note),
+            NoteNameLanguage::Portugues => portugues::lilypond_from_note(note),
+            NoteNameLanguage::Suomi => suomi::lilypond_from_note(note),
+            NoteNameLanguage::Svenska => svenska::lilypond_from_note(note),
+            NoteNameLanguage::Vlaams => vlaams::lilypond_from_note(note),
+        }
+    }
+
+    /// Convert a pitch into a LilyPond-formatted String in this language:
+    /// its note name, accidental and octave, without a duration. Pitches
+    /// whose alteration has no note name are written as Scheme `ly:make-pitch`
+    /// expressions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{NoteName, Octave, Pitch};
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let mut pitch = Pitch::new(NoteName::B);
+    /// pitch.flatten();
+    /// pitch.octave(Octave::S4);
+    ///
+    /// assert_eq!(NoteNameLanguage::English.lilypond_from_pitch(&pitch), "bf'");
+    /// assert_eq!(NoteNameLanguage::Deutsch.lilypond_from_pitch(&pitch), "b'");
+    /// ```
+    pub fn lilypond_from_pitch(&self, pitch: &Pitch) -> String {
+        let mut note = Note::new(pitch.note_name);
+        note.pitch = pitch.clone();
+        self.lilypond_pitch(&note)
+            .unwrap_or_else(|| format!("${}", common::scheme_from_pitch(pitch)))
+    }
+
+    /// Write the note name, accidental and octave of a note in this
+    /// language, if its accidental has a name.
+    fn lilypond_pitch(&self, note: &Note) -> Option<String> {
+        match self {
+            NoteNameLanguage::English => english::lilypond_pitch(note),
+            NoteNameLanguage::Nederlands => nederlands::lilypond_pitch(note),
+            NoteNameLanguage::Catala => catala::lilypond_pitch(note),
+            NoteNameLanguage::Deutsch => deutsch::lilypond_pitch(note),
+            NoteNameLanguage::Espanol => espanol::lilypond_pitch(note),
+            NoteNameLanguage::Francais => francais::lilypond_pitch(note),
+            NoteNameLanguage::Italiano => italiano::lilypond_pitch(note),
+            NoteNameLanguage::Norsk => norsk::lilypond_pitch(note),
+            NoteNameLanguage::Portugues => portugues::lilypond_pitch(note),
+            NoteNameLanguage::Suomi => suomi::lilypond_pitch(note),
+            NoteNameLanguage::Svenska => svenska::lilypond_pitch(note),
+            NoteNameLanguage::Vlaams => vlaams::lilypond_pitch(note),
+        }
+    }
+
+    /// Convert a note or chord into a LilyPond-formatted String in this
+    /// language, followed by its beam, articulations, dynamic and tie.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::articulation::Articulation;
+    /// use lilypond::notation::chord::Chord;
+    /// use lilypond::notation::dynamic::Dynamic;
+    /// use lilypond::notation::pitch::{Pitch, NoteName};
+    /// use lilypond::notation::voice::Event;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let mut chord = Chord::new(vec![Pitch::new(NoteName::C), Pitch::new(NoteName::E)]);
+    /// chord.articulations.push(Articulation::Accent);
+    /// chord.dynamic = Some(Dynamic::MF);
+    /// chord.tie = true;
+    ///
+    /// assert_eq!(
+    ///     NoteNameLanguage::English.lilypond_from_event(&Event::Chord(chord)),
+    ///     "<c e>4->\\mf~"
+    /// );
+    /// ```
+    pub fn lilypond_from_event(&self, event: &Event) -> String {
+        let (music, beam, articulations, dynamic, tie) = match event {
+            // post-events cannot follow a Scheme expression
+            Event::Note(note) if self.lilypond_pitch(note).is_none() => {
+                return common::scheme_from_note_with_post_events(note);
+            }
+            Event::Note(note) => (
+                self.lilypond_from_note(note),
+                note.beam,
+                &note.articulations,
+                note.dynamic,
+                note.tie,
+            ),
+            Event::Chord(chord) => {
+                let duration = format!("{}{}", chord.rhythm.length, chord.rhythm.dots);
+                let pitches: Vec<String> = chord
+                    .pitches
+                    .iter()
+                    .map(|pitch| self.lilypond_from_pitch(pitch))
+                    .collect();
+                let music = format!("<{}>{}", pitches.join(" "), duration);
+                (
+                    music,
+                    chord.beam,
+                    &chord.articulations,
+                    chord.dynamic,
+                    chord.tie,
+                )
+            }
+        };
+        let articulations: String = articulations.iter().map(Articulation::to_string).collect();
+        let beam = beam.map(|beam| beam.to_string()).unwrap_or_default();
+        let dynamic = dynamic
+            .map(|dynamic| dynamic.to_string())
+            .unwrap_or_default();
+        format!(
+            "{}{}{}{}{}",
+            music,
+            beam,
+            articulations,
+            dynamic,
+            if tie { "~" } else { "" }
+        )
+    }
+
+    /// Convert a note into a LilyPond-formatted String in this language,
+    /// with accidentals in the given `spelling`.
+    ///
+    /// Only English has long accidentals; every other language writes its
+    /// usual accidentals for either spelling.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::note::Note;
+    /// use lilypond::notation::pitch::NoteName;
+    /// use lilypond::{AccidentalSpelling, NoteNameLanguage};
+    ///
+    /// let mut note = Note::new(NoteName::F);
+    /// note.pitch.sharpen();
+    ///
+    /// let english = NoteNameLanguage::English;
+    /// assert_eq!(english.lilypond_from_note_with_spelling(&note, AccidentalSpelling::Short), "fs4");
+    /// assert_eq!(english.lilypond_from_note_with_spelling(&note, AccidentalSpelling::Long), "f-sharp4");
+    /// ```
+    pub fn lilypond_from_note_with_spelling(
+        &self,
+        note: &Note,
+        spelling: AccidentalSpelling,
+    ) -> String {
+        match self {
+            NoteNameLanguage::English => english::lilypond_from_note_with_spelling(note, spelling),
+            _ => self.lilypond_from_note(note),
+        }
+    }
+
+    /// Attempt to convert a LilyPondNote into a Note object, reading its
+    /// note name and accidental in this language.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Result` according to whether the conversion was successful.
+    /// On a success, returns `Ok(Note)`, and on a failure, returns
+    /// `Err(String)` where the `String` is the associated error message.
+    pub fn note_from_lilypond(&self, note: &LilyPondNote) -> Result<Note, String> {
+        match self {
+            NoteNameLanguage::English => english::note_from_lilypond(note),
+            NoteNameLanguage::Nederlands => nederlands::note_from_lilypond(note),
+            NoteNameLanguage::Catala => catala::note_from_lilypond(note),
+            NoteNameLanguage::Deutsch => deutsch::note_from_lilypond(note),
+            NoteNameLanguage::Espanol => espanol::note_from_lilypond(note),
+            NoteNameLanguage::Francais => francais::note_from_lilypond(note),
+            NoteNameLanguage::Italiano => italiano::note_from_lilypond(note),
+            NoteNameLanguage::Norsk => norsk::note_from_lilypond(note),
+            NoteNameLanguage::Portugues => portugues::note_from_lilypond(note),
+            NoteNameLanguage::Suomi => suomi::note_from_lilypond(note),
+            NoteNameLanguage::Svenska => svenska::note_from_lilypond(note),
+            NoteNameLanguage::Vlaams => vlaams::note_from_lilypond(note),
+        }
+    }
+}
+
+impl std::str::FromStr for NoteNameLanguage {
+    type Err = String;
+
+    /// Attempt to find the language LilyPond selects with `\language "s"`.
+    ///
+    /// As in LilyPond, the languages with accented names may also be selected
+    /// without the accents, e.g. `"francais"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the language is unknown or not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let language: NoteNameLanguage = "nederlands".parse().unwrap();
+    /// assert_eq!(language, NoteNameLanguage::Nederlands);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language_str = match s {
+            "catalan" => "català",
+            "espanol" => "español",
+            "francais" => "français",
+            "portugues" => "português",
+            s => s,
+        };
+        LANGUAGES
+            .iter()
+            .find(|language| language.language_str() == language_str)
+            .copied()
+            .ok_or_else(|| format!("Unsupported note-name language \"{}\".", s))
+    }
+}
+
+impl std::fmt::Display for NoteNameLanguage {
+    /// Write the string LilyPond uses to select the language.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.language_str())
+    }
 }
 
 /// Convert a note into a LilyPond-formatted String according to the value of
 /// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE].
 pub fn lilypond_from_note(note: &Note) -> String {
-    match *crate::NOTE_NAME_LANGUAGE {
-        NoteNameLanguage::English => english::lilypond_from_note(note),
-        NoteNameLanguage::Nederlands => nederlands::lilypond_from_note(note),
-    }
+    crate::NOTE_NAME_LANGUAGE.lilypond_from_note(note)
 }
 
-/// Attempt to convert a LilyPondNote into a Note object according to the value
-/// of [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE].
+/// Attempt to convert a LilyPondNote into a Note object according to the
+/// language the note was created with (by default
+/// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE]).
 ///
 /// # Errors
 ///
@@ -67,8 +398,96 @@ pub fn lilypond_from_note(note: &Note) -> String {
 /// success, returns `Ok(Note)`, and on a failure, returns `Err(String)` where
 /// the `String` is the associated error message.
 pub fn note_from_lilypond(note: &LilyPondNote) -> Result<Note, String> {
-    match *crate::NOTE_NAME_LANGUAGE {
-        NoteNameLanguage::English => english::note_from_lilypond(note),
-        NoteNameLanguage::Nederlands => nederlands::note_from_lilypond(note),
+    note.get_language().note_from_lilypond(note)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::notation::beam::Beam;
+    use crate::notation::dynamic::Dynamic;
+    use crate::notation::pitch::{Accidental, Alteration, NoteName, Octave};
+    use crate::notation::rhythm::Length;
+    #[test]
+    fn test_from_str() {
+        for language in LANGUAGES.iter() {
+            assert_eq!(language.language_str().parse(), Ok(*language));
+            assert_eq!(language.to_string(), language.language_str());
+        }
+        let aliases = [
+            ("catalan", NoteNameLanguage::Catala),
+            ("espanol", NoteNameLanguage::Espanol),
+            ("francais", NoteNameLanguage::Francais),
+            ("portugues", NoteNameLanguage::Portugues),
+        ];
+        for (alias, language) in aliases {
+            assert_eq!(alias.parse(), Ok(language));
+        }
+        assert!("klingon".parse::<NoteNameLanguage>().is_err());
+    }
+    #[test]
+    fn test_translate() {
+        let mut note = Note::new(NoteName::B);
+        note.pitch.flatten();
+        note.pitch.octave(Octave::S4);
+        note.rhythm.length(Length::Eighth);
+        let expected = [
+            (NoteNameLanguage::English, "bf'8"),
+            (NoteNameLanguage::Nederlands, "bes'8"),
+            (NoteNameLanguage::Catala, "sib'8"),
+            (NoteNameLanguage::Deutsch, "b'8"),
+            (NoteNameLanguage::Espanol, "sib'8"),
+            (NoteNameLanguage::Francais, "sib'8"),
+            (NoteNameLanguage::Italiano, "sib'8"),
+            (NoteNameLanguage::Norsk, "b'8"),
+            (NoteNameLanguage::Portugues, "sib'8"),
+            (NoteNameLanguage::Suomi, "b'8"),
+            (NoteNameLanguage::Svenska, "b'8"),
+            (NoteNameLanguage::Vlaams, "sib'8"),
+        ];
+        for (language, ly_str) in expected {
+            assert_eq!(language.lilypond_from_note(&note), ly_str);
+            let ly_note = LilyPondNote::with_language(ly_str, language).unwrap();
+            assert_eq!(language.note_from_lilypond(&ly_note).unwrap(), note);
+        }
+    }
+    #[test]
+    fn test_quarter_tone_fallback() {
+        let mut note = Note::new(NoteName::C);
+        note.pitch.accidental(Accidental::QuarterSharp);
+        assert_eq!(NoteNameLanguage::Deutsch.lilypond_from_note(&note), "cih4");
+        assert!(NoteNameLanguage::Svenska
+            .lilypond_from_note(&note)
+            .starts_with("$(make-music"));
+    }
+    #[test]
+    fn test_scheme_post_events() {
+        // post-events go inside a Scheme note, which they cannot follow
+        let mut note = Note::new(NoteName::E);
+        note.pitch
+            .accidental(Accidental::Other(Alteration::new(1, 3)));
+        note.pitch.octave(Octave::S4);
+        note.articulations.push(Articulation::Staccato);
+        note.dynamic = Some(Dynamic::F);
+        note.beam = Some(Beam::Start);
+        note.tie = true;
+        assert_eq!(
+            NoteNameLanguage::English.lilypond_from_event(&Event::Note(note.clone())),
+            "$(make-music 'NoteEvent 'pitch (ly:make-pitch 0 2 1/6) \
+             'duration (ly:make-duration 2 0) 'articulations (list \
+             (make-music 'BeamEvent 'span-direction -1) \
+             (make-music 'ArticulationEvent 'articulation-type 'staccato) \
+             (make-music 'AbsoluteDynamicEvent 'text \"f\") \
+             (make-music 'TieEvent)))"
+        );
+        // a plain Scheme note is written as before
+        note.articulations.clear();
+        note.dynamic = None;
+        note.beam = None;
+        note.tie = false;
+        assert_eq!(
+            NoteNameLanguage::English.lilypond_from_event(&Event::Note(note.clone())),
+            NoteNameLanguage::English.lilypond_from_note(&note)
+        );
     }
 }
@@ -15,21 +15,30 @@ pub static LANGUAGE_STR: &str = "nederlands";
 pub static NOTE_REGEX_STR: &str = r"(?x-u)
     # Flags: x = whitespace allowed, -u = no unicode support
     ^(?P<note_name>[a-gr]) # note name or rest
-    (?P<accidental>(?:is){0,2}|(?:es){0,2}) # accidental
+    (?P<accidental>(?:is){0,2}|(?:es){0,2}| # accidental
+    (?:is)?ih|(?:es)?eh) # quarter-tone accidental
     (?P<octave>(?:(?:,{0,3})|(?:'{0,6}))?) # octave transposition characters
     (?P<duration>(?:1|2|4|8|(?:16)|(?:32)|(?:64)|(?:128))?) # Durations
     (?P<dot>\.{0,255})$ # optional dot and end of line
     ";
 
 pub fn lilypond_from_note(note: &Note) -> String {
-    format!(
-        "{}{}{}{}{}",
+    match lilypond_pitch(note) {
+        Some(pitch) => format!("{}{}{}", pitch, note.rhythm.length, note.rhythm.dots),
+        None => common::scheme_from_note(note),
+    }
+}
+
+pub fn lilypond_pitch(note: &Note) -> Option<String> {
+    if !common::has_named_accidental(note) {
+        return None;
+    }
+    Some(format!(
+        "{}{}{}",
         lilypond_from_note_name(note),
         lilypond_from_accidental(note),
         note.pitch.octave,
-        note.rhythm.length,
-        note.rhythm.dots,
-    )
+    ))
 }
 
 fn lilypond_from_note_name(note: &Note) -> &str {
@@ -49,12 +58,18 @@ fn lilypond_from_note_name(note: &Note) -> &str {
 }
 
 fn lilypond_from_accidental(note: &Note) -> &str {
-    match note.pitch.accidental {
+    match Accidental::from_alteration(note.pitch.accidental.alteration()) {
         Accidental::None => "",
         Accidental::Flat => "es",
         Accidental::DoubleFlat => "eses",
         Accidental::Sharp => "is",
         Accidental::DoubleSharp => "isis",
+        Accidental::QuarterSharp => "ih",
+        Accidental::ThreeQuarterSharp => "isih",
+        Accidental::QuarterFlat => "eh",
+        Accidental::ThreeQuarterFlat => "eseh",
+        // unnamed alterations are written as Scheme by `lilypond_from_note`
+        Accidental::Other(_) => "",
     }
 }
 
@@ -66,6 +81,10 @@ pub fn note_from_lilypond(note: &LilyPondNote) -> Result<Note, String> {
             octave: common::octave_from_lilypond(note)?,
         },
         rhythm: common::rhythm_from_lilypond(note)?,
+        tie: false,
+        articulations: vec![],
+        beam: None,
+        dynamic: None,
     })
 }
 
@@ -94,6 +113,10 @@ fn accidental_from_lilypond(note: &LilyPondNote) -> Result<Accidental, String> {
             "isis" => Ok(Accidental::DoubleSharp),
             "es" => Ok(Accidental::Flat),
             "eses" => Ok(Accidental::DoubleFlat),
+            "ih" => Ok(Accidental::QuarterSharp),
+            "isih" => Ok(Accidental::ThreeQuarterSharp),
+            "eh" => Ok(Accidental::QuarterFlat),
+            "eseh" => Ok(Accidental::ThreeQuarterFlat),
             e => Err(format!("Invalid accidental '{}'.", e)),
         },
     }
@@ -102,6 +125,7 @@ fn accidental_from_lilypond(note: &LilyPondNote) -> Result<Accidental, String> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::NoteNameLanguage;
     use lazy_static::lazy_static;
     use regex::Regex;
     lazy_static! {
@@ -148,6 +172,21 @@ mod test {
         note.pitch.accidental(Accidental::DoubleSharp);
         let accidental_str = lilypond_from_accidental(&note);
         assert_eq!("isis", accidental_str);
+        note.pitch.accidental(Accidental::QuarterSharp);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("ih", accidental_str);
+        note.pitch.accidental(Accidental::ThreeQuarterSharp);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("isih", accidental_str);
+        note.pitch.accidental(Accidental::QuarterFlat);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("eh", accidental_str);
+        note.pitch.accidental(Accidental::ThreeQuarterFlat);
+        let accidental_str = lilypond_from_accidental(&note);
+        assert_eq!("eseh", accidental_str);
+    }
+    fn dutch(note: &str) -> Result<LilyPondNote, String> {
+        LilyPondNote::with_language(note, NoteNameLanguage::Nederlands)
     }
     fn test_regex_case(note: &str) {
         assert!(LILYPOND_NOTE_REGEX.is_match(note));
@@ -178,47 +217,61 @@ mod test {
             "fes,,,8.",
             "feses,,,8.",
             "gis''''''16.",
+            "cih",
+            "deh,4",
+            "eisih''8.",
+            "feseh16",
         ];
         for note in notes {
             test_regex_case(note);
             println!("{}", note);
         }
     }
-    // not currently testing these two because I can't change the value of
-    // NOTE_NAME_LANGUAGE to change the LILYPOND_NOTE_REGEX.
-    #[allow(dead_code)]
+    #[test]
     fn test_accidental_from_lilypond() {
-        let ly_note = LilyPondNote::new("r8").unwrap();
+        let ly_note = dutch("r8").unwrap();
         let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
         assert_eq!(accidental_type, Accidental::None);
-        let ly_note = LilyPondNote::new("fis").unwrap();
+        let ly_note = dutch("fis").unwrap();
         let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
         assert_eq!(accidental_type, Accidental::Sharp);
-        let ly_note = LilyPondNote::new("ees").unwrap();
+        let ly_note = dutch("ees").unwrap();
         let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
         assert_eq!(accidental_type, Accidental::Flat);
-        let ly_note = LilyPondNote::new("gisis").unwrap();
+        let ly_note = dutch("gisis").unwrap();
         let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
         assert_eq!(accidental_type, Accidental::DoubleSharp);
-        let ly_note = LilyPondNote::new("aeses").unwrap();
+        let ly_note = dutch("aeses").unwrap();
         let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
         assert_eq!(accidental_type, Accidental::DoubleFlat);
+        let ly_note = dutch("cih").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::QuarterSharp);
+        let ly_note = dutch("disih").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::ThreeQuarterSharp);
+        let ly_note = dutch("eeh").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::QuarterFlat);
+        let ly_note = dutch("beseh").unwrap();
+        let accidental_type = accidental_from_lilypond(&ly_note).unwrap();
+        assert_eq!(accidental_type, Accidental::ThreeQuarterFlat);
     }
-    #[allow(dead_code)]
+    #[test]
     fn test_note_name_from_lilypond() {
-        let ly_note = LilyPondNote::new("r8").unwrap();
+        let ly_note = dutch("r8").unwrap();
         let note_name = note_name_from_lilypond(&ly_note).unwrap();
         assert_eq!(note_name, NoteName::None);
-        let ly_note = LilyPondNote::new("fis").unwrap();
+        let ly_note = dutch("fis").unwrap();
         let note_name = note_name_from_lilypond(&ly_note).unwrap();
         assert_eq!(note_name, NoteName::F);
-        let ly_note = LilyPondNote::new("ees").unwrap();
+        let ly_note = dutch("ees").unwrap();
         let note_name = note_name_from_lilypond(&ly_note).unwrap();
         assert_eq!(note_name, NoteName::E);
-        let ly_note = LilyPondNote::new("gisis").unwrap();
+        let ly_note = dutch("gisis").unwrap();
         let note_name = note_name_from_lilypond(&ly_note).unwrap();
         assert_eq!(note_name, NoteName::G);
-        let ly_note = LilyPondNote::new("aeses").unwrap();
+        let ly_note = dutch("aeses").unwrap();
         let note_name = note_name_from_lilypond(&ly_note).unwrap();
         assert_eq!(note_name, NoteName::A);
     }
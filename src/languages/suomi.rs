@@ -0,0 +1,251 @@
+//! A module for Finnish LilyPond note input.
+//!
+//! In Finnish, `h` is B and `b` is B flat, and the flats of E and A are
+//! contracted to `es` and `as`.
+
+use super::common;
+use crate::{
+    lilypond_objects::lilypond_note::LilyPondNote,
+    notation::{
+        note::Note,
+        pitch::{Accidental, NoteName, Pitch},
+        rhythm::DurationType,
+    },
+};
+
+pub static LANGUAGE_STR: &str = "suomi";
+
+pub static NOTE_REGEX_STR: &str = r"(?x-u)
+    # Flags: x = whitespace allowed, -u = no unicode support
+    ^(?P<note_name>[a-hr]) # note name or rest
+    (?P<accidental>(?:is){0,2}|(?:es){0,2}| # accidentals
+    s(?:es)?) # contracted flats of E and A
+    (?P<octave>(?:(?:,{0,3})|(?:'{0,6}))?) # octave transposition characters
+    (?P<duration>(?:1|2|4|8|(?:16)|(?:32)|(?:64)|(?:128))?) # Durations
+    (?P<dot>\.{0,255})$ # optional dots and end of line
+    ";
+
+pub fn lilypond_from_note(note: &Note) -> String {
+    match lilypond_pitch(note) {
+        Some(pitch) => format!("{}{}{}", pitch, note.rhythm.length, note.rhythm.dots),
+        None => common::scheme_from_note(note),
+    }
+}
+
+pub fn lilypond_pitch(note: &Note) -> Option<String> {
+    // quarter tones have no names in this language
+    if !common::has_named_chromatic_accidental(note) {
+        return None;
+    }
+    Some(format!(
+        "{}{}",
+        lilypond_from_pitch(note),
+        note.pitch.octave,
+    ))
+}
+
+fn lilypond_from_pitch(note: &Note) -> String {
+    let accidental = Accidental::from_alteration(note.pitch.accidental.alteration());
+    let note_name = match note.rhythm.duration_type {
+        DurationType::Rest => return String::from("r"),
+        DurationType::Note => match (&note.pitch.note_name, accidental) {
+            (NoteName::E, Accidental::Flat) => return String::from("es"),
+            (NoteName::E, Accidental::DoubleFlat) => return String::from("eses"),
+            (NoteName::A, Accidental::Flat) => return String::from("as"),
+            (NoteName::A, Accidental::DoubleFlat) => return String::from("ases"),
+            (NoteName::B, Accidental::Flat) => return String::from("b"),
+            (NoteName::B, Accidental::DoubleFlat) => return String::from("bes"),
+            (NoteName::A, _) => "a",
+            (NoteName::B, _) => "h",
+            (NoteName::C, _) => "c",
+            (NoteName::D, _) => "d",
+            (NoteName::E, _) => "e",
+            (NoteName::F, _) => "f",
+            (NoteName::G, _) => "g",
+            (NoteName::None, _) => return String::from("r"),
+        },
+    };
+    format!("{}{}", note_name, lilypond_from_accidental(accidental))
+}
+
+fn lilypond_from_accidental(accidental: Accidental) -> &'static str {
+    match accidental {
+        Accidental::None => "",
+        Accidental::Flat => "es",
+        Accidental::DoubleFlat => "eses",
+        Accidental::Sharp => "is",
+        Accidental::DoubleSharp => "isis",
+        // quarter tones and unnamed alterations are written as Scheme by
+        // `lilypond_from_note`
+        _ => "",
+    }
+}
+
+pub fn note_from_lilypond(note: &LilyPondNote) -> Result<Note, String> {
+    let (note_name, accidental) = pitch_from_lilypond(note)?;
+    Ok(Note {
+        pitch: Pitch {
+            note_name,
+            accidental,
+            octave: common::octave_from_lilypond(note)?,
+        },
+        rhythm: common::rhythm_from_lilypond(note)?,
+        tie: false,
+        articulations: vec![],
+        beam: None,
+        dynamic: None,
+    })
+}
+
+fn pitch_from_lilypond(note: &LilyPondNote) -> Result<(NoteName, Accidental), String> {
+    if common::duration_type_from_lilypond(note) == DurationType::Rest {
+        return Ok((NoteName::None, Accidental::None));
+    }
+    let note_name = note.get_capture("note_name");
+    let accidental = note.get_capture("accidental");
+    match (note_name.as_str(), accidental.as_str()) {
+        ("e", "s") => Ok((NoteName::E, Accidental::Flat)),
+        ("a", "s") => Ok((NoteName::A, Accidental::Flat)),
+        ("e", "ses") => Ok((NoteName::E, Accidental::DoubleFlat)),
+        ("a", "ses") => Ok((NoteName::A, Accidental::DoubleFlat)),
+        ("b", "") => Ok((NoteName::B, Accidental::Flat)),
+        ("b", "es") => Ok((NoteName::B, Accidental::DoubleFlat)),
+        // flats of E, A and B are only written in their contracted forms
+        ("e" | "a", "es" | "eses") | ("h", "es" | "eses") | ("b", _) => {
+            Err(format!("Invalid note name '{}{}'.", note_name, accidental))
+        }
+        (note_name, accidental) => Ok((
+            note_name_from_str(note_name)?,
+            accidental_from_str(accidental)?,
+        )),
+    }
+}
+
+fn note_name_from_str(note_name: &str) -> Result<NoteName, String> {
+    match note_name {
+        "a" => Ok(NoteName::A),
+        "h" => Ok(NoteName::B),
+        "c" => Ok(NoteName::C),
+        "d" => Ok(NoteName::D),
+        "e" => Ok(NoteName::E),
+        "f" => Ok(NoteName::F),
+        "g" => Ok(NoteName::G),
+        e => Err(format!("Invalid note name '{}'.", e)),
+    }
+}
+
+fn accidental_from_str(accidental: &str) -> Result<Accidental, String> {
+    match accidental {
+        "" => Ok(Accidental::None),
+        "is" => Ok(Accidental::Sharp),
+        "isis" => Ok(Accidental::DoubleSharp),
+        "es" => Ok(Accidental::Flat),
+        "eses" => Ok(Accidental::DoubleFlat),
+        e => Err(format!("Invalid accidental '{}'.", e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NoteNameLanguage;
+    fn suomi(note: &str) -> Result<LilyPondNote, String> {
+        LilyPondNote::with_language(note, NoteNameLanguage::Suomi)
+    }
+    #[test]
+    fn test_lilypond_from_pitch() {
+        let pitches = [
+            (NoteName::C, Accidental::None, "c"),
+            (NoteName::C, Accidental::Sharp, "cis"),
+            (NoteName::D, Accidental::Flat, "des"),
+            (NoteName::E, Accidental::Flat, "es"),
+            (NoteName::E, Accidental::DoubleFlat, "eses"),
+            (NoteName::F, Accidental::DoubleSharp, "fisis"),
+            (NoteName::A, Accidental::Flat, "as"),
+            (NoteName::A, Accidental::DoubleFlat, "ases"),
+            (NoteName::B, Accidental::None, "h"),
+            (NoteName::B, Accidental::Sharp, "his"),
+            (NoteName::B, Accidental::Flat, "b"),
+            (NoteName::B, Accidental::DoubleFlat, "bes"),
+        ];
+        for (note_name, accidental, pitch_str) in pitches {
+            let mut note = Note::new(note_name);
+            note.pitch.accidental(accidental);
+            assert_eq!(pitch_str, lilypond_from_pitch(&note));
+        }
+    }
+    #[test]
+    fn test_regex() {
+        let notes = [
+            "r", "c", "des", "es,", "fisis'", "g,,1", "as''2.", "b4", "h8", "bes16", "his,,,32",
+            "ases64",
+        ];
+        for note in notes {
+            assert!(
+                NoteNameLanguage::Suomi.note_regex().is_match(note),
+                "{}",
+                note
+            );
+        }
+    }
+    #[test]
+    fn test_pitch_from_lilypond() {
+        let pitches = [
+            ("r8", NoteName::None, Accidental::None),
+            ("c", NoteName::C, Accidental::None),
+            ("cis", NoteName::C, Accidental::Sharp),
+            ("des", NoteName::D, Accidental::Flat),
+            ("es", NoteName::E, Accidental::Flat),
+            ("eses", NoteName::E, Accidental::DoubleFlat),
+            ("fisis", NoteName::F, Accidental::DoubleSharp),
+            ("as", NoteName::A, Accidental::Flat),
+            ("ases", NoteName::A, Accidental::DoubleFlat),
+            ("h", NoteName::B, Accidental::None),
+            ("his", NoteName::B, Accidental::Sharp),
+            ("b", NoteName::B, Accidental::Flat),
+            ("bes", NoteName::B, Accidental::DoubleFlat),
+        ];
+        for (ly_str, note_name, accidental) in pitches {
+            let ly_note = suomi(ly_str).unwrap();
+            assert_eq!(
+                pitch_from_lilypond(&ly_note).unwrap(),
+                (note_name, accidental)
+            );
+        }
+    }
+    #[test]
+    fn test_pitch_from_lilypond_error() {
+        let notes = ["ees", "aes", "hes", "heses", "beses", "bis"];
+        for ly_str in notes {
+            let ly_note = suomi(ly_str).unwrap();
+            assert!(pitch_from_lilypond(&ly_note).is_err(), "{}", ly_str);
+        }
+    }
+    #[test]
+    fn test_round_trip() {
+        let note_names = [
+            NoteName::C,
+            NoteName::D,
+            NoteName::E,
+            NoteName::F,
+            NoteName::G,
+            NoteName::A,
+            NoteName::B,
+        ];
+        let accidentals = [
+            Accidental::None,
+            Accidental::Sharp,
+            Accidental::DoubleSharp,
+            Accidental::Flat,
+            Accidental::DoubleFlat,
+        ];
+        for note_name in note_names {
+            for accidental in accidentals {
+                let mut note = Note::new(note_name);
+                note.pitch.accidental(accidental);
+                let ly_note = suomi(&lilypond_from_note(&note)).unwrap();
+                assert_eq!(note_from_lilypond(&ly_note).unwrap(), note);
+            }
+        }
+    }
+}
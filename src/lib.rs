@@ -13,16 +13,22 @@
 use lazy_static::lazy_static;
 
 pub use crate::languages::{lilypond_from_note, note_from_lilypond, LANGUAGE_STR, NOTE_REGEX_STR};
-use crate::notation::pitch::NoteName;
+use crate::notation::note::Note;
+use crate::notation::voice::Event;
 use std::io::{self, Write};
 use std::path::Path;
 use std::process::Command;
 
+pub mod analysis;
+pub mod audio;
+pub mod export;
+pub mod import;
 mod languages;
 pub mod lilypond_objects;
 pub mod midi;
 pub mod notation;
 pub mod parser;
+pub mod tuning;
 
 /// Compiles a `.ly` source file
 ///
@@ -101,13 +107,7 @@ pub fn compile(input_file: &str) -> bool {
 /// ```
 pub fn is_lilypond_file(filename: &str) -> bool {
     match Path::new(filename).extension() {
-        Some(ex) => {
-            if ex == "ly" {
-                true
-            } else {
-                false
-            }
-        }
+        Some(ex) => ex == "ly",
         None => false,
     }
 }
@@ -115,7 +115,13 @@ pub fn is_lilypond_file(filename: &str) -> bool {
 /// A Rust representation of LilyPond data.
 #[derive(PartialEq, Debug)]
 pub struct LilyPond {
-    pub notes: Vec<NoteName>,
+    pub notes: Vec<Note>,
+}
+
+impl Default for LilyPond {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl LilyPond {
@@ -135,6 +141,14 @@ impl LilyPond {
     }
     /// Parses LilyPond input as a string into the data structure.
     ///
+    /// Note names are read in the language of
+    /// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE] until the
+    /// input selects another language with `\language`.
+    ///
+    /// # Errors
+    ///
+    /// See [`parse_notes`][crate::parser::parse_notes].
+    ///
     /// # Examples
     ///
     /// ```
@@ -142,9 +156,62 @@ impl LilyPond {
     ///
     /// let mut ly = LilyPond::new();
     /// let parsed = ly.parse("{c e f}");
+    ///
+    /// assert_eq!(parsed, Ok(()));
+    /// assert_eq!(ly.notes.len(), 3);
+    /// ```
+    pub fn parse(&mut self, raw: &str) -> Result<(), String> {
+        self.parse_with_language(raw, *NOTE_NAME_LANGUAGE)
+    }
+    /// Parses LilyPond input as a string into the data structure, reading
+    /// note names in `language` until the input selects another language.
+    ///
+    /// # Errors
+    ///
+    /// See [`parse_notes`][crate::parser::parse_notes].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::{LilyPond, NoteNameLanguage};
+    ///
+    /// let mut ly = LilyPond::new();
+    /// ly.parse_with_language("{ c d ees }", NoteNameLanguage::Nederlands).unwrap();
+    ///
+    /// assert_eq!(ly.notes.len(), 3);
+    /// ```
+    pub fn parse_with_language(
+        &mut self,
+        raw: &str,
+        language: NoteNameLanguage,
+    ) -> Result<(), String> {
+        self.notes.append(&mut parser::parse_notes(raw, language)?);
+        Ok(())
+    }
+    /// Writes the data structure as LilyPond input with note names in
+    /// `language`, selecting that language with a `\language` directive.
+    /// Ties and articulations are kept.
+    ///
+    /// # Examples
+    ///
     /// ```
-    pub fn parse(&mut self, raw: &'static str) {
-        println!("{}", raw);
+    /// use lilypond::{LilyPond, NoteNameLanguage};
+    ///
+    /// let mut ly = LilyPond::new();
+    /// ly.parse("{ c8~ \\language \"nederlands\" c cis-. }").unwrap();
+    ///
+    /// assert_eq!(
+    ///     ly.to_lilypond(NoteNameLanguage::English),
+    ///     "\\language \"english\"\n\n{ c8~ c8 cs8-. }\n"
+    /// );
+    /// ```
+    pub fn to_lilypond(&self, language: NoteNameLanguage) -> String {
+        let notes: Vec<String> = self
+            .notes
+            .iter()
+            .map(|note| language.lilypond_from_event(&Event::Note(note.clone())))
+            .collect();
+        format!("\\language \"{}\"\n\n{{ {} }}\n", language, notes.join(" "))
     }
 }
 
@@ -153,24 +220,101 @@ impl LilyPond {
 /// See
 /// <https://lilypond.org/doc/v2.24/Documentation/notation/writing-pitches#note-names-in-other-languages>
 /// or the corresponding page for whatever LilyPond version is most current
-/// for more information. Currently only supports English and Dutch (LilyPond's
-/// default).
+/// for more information. English is the default for this library, and Dutch
+/// is LilyPond's default.
+///
+/// A language can be chosen for each conversion, so a single program can
+/// read notes in one language and write them in another:
+///
+/// ```rust
+/// use lilypond::lilypond_objects::lilypond_note::LilyPondNote;
+/// use lilypond::NoteNameLanguage;
+///
+/// let dutch = LilyPondNote::with_language("fis'8", NoteNameLanguage::Nederlands).unwrap();
+/// let note = NoteNameLanguage::Nederlands.note_from_lilypond(&dutch).unwrap();
+/// assert_eq!(NoteNameLanguage::English.lilypond_from_note(&note), "fs'8");
+/// ```
 ///
-/// TODO: add support for more languages.
+/// Languages without names for quarter tones write them, like any other
+/// alteration without a name, as Scheme expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NoteNameLanguage {
     /// English note names and accidentals.
     ///
     /// - Note names: `c`, `d`, `e`, `f`, `g`, `a`, `b`
     ///
-    /// - Accidentals: `s`, `f`, `ss`, `ff`, `-sharp`, `-flat`, `-sharpsharp`,
-    /// `-flatflat`
+    /// - Accidentals: `s`, `f`, `ss` or `x`, `ff`, `-sharp`, `-flat`,
+    ///   `-sharpsharp`, `-flatflat`
+    ///
+    /// - Quarter-tone accidentals: `qs`, `qf`, `tqs`, `tqf`
     ///
     English,
     /// Dutch note names and accidentals.
     ///
     /// - Note names: `c`, `d`, `e`, `f`, `g`, `a`, `b`
     /// - Accidentals: `is`, `es`, `isis`, `eses`
+    /// - Quarter-tone accidentals: `ih`, `eh`, `isih`, `eseh`
     Nederlands,
+    /// Catalan note names and accidentals.
+    ///
+    /// - Note names: `do`, `re`, `mi`, `fa`, `sol`, `la`, `si`
+    /// - Accidentals: `d` or `s`, `b`, `dd` or `ss`, `bb`
+    /// - Quarter-tone accidentals: `qd`, `qb`, `tqd`, `tqb`
+    Catala,
+    /// German note names and accidentals.
+    ///
+    /// - Note names: `c`, `d`, `e`, `f`, `g`, `a`, `h`, with `b` for B flat
+    /// - Accidentals: `is`, `es`, `isis`, `eses`, contracted to `es`, `as`,
+    ///   `eses` and `ases` for the flats of E and A
+    /// - Quarter-tone accidentals: `ih`, `eh`, `isih`, `eseh`
+    Deutsch,
+    /// Spanish note names and accidentals.
+    ///
+    /// - Note names: `do`, `re`, `mi`, `fa`, `sol`, `la`, `si`
+    /// - Accidentals: `s`, `b`, `ss` or `x`, `bb`
+    /// - Quarter-tone accidentals: `cs`, `cb`, `tcs`, `tcb`
+    Espanol,
+    /// French note names and accidentals.
+    ///
+    /// - Note names: `do`, `re` or `ré`, `mi`, `fa`, `sol`, `la`, `si`
+    /// - Accidentals: `d`, `b`, `dd` or `x`, `bb`
+    /// - Quarter-tone accidentals: `sd`, `sb`, `dsd`, `bsb`
+    Francais,
+    /// Italian note names and accidentals.
+    ///
+    /// - Note names: `do`, `re`, `mi`, `fa`, `sol`, `la`, `si`
+    /// - Accidentals: `d`, `b`, `dd`, `bb`
+    /// - Quarter-tone accidentals: `sd`, `sb`, `dsd`, `bsb`
+    Italiano,
+    /// Norwegian note names and accidentals.
+    ///
+    /// - Note names: `c`, `d`, `e`, `f`, `g`, `a`, `h`, with `b` for B flat
+    /// - Accidentals: `iss` or `is`, `ess` or `es`, `ississ` or `isis`,
+    ///   `essess` or `eses`, contracted for the flats of E and A
+    Norsk,
+    /// Portuguese note names and accidentals.
+    ///
+    /// - Note names: `do`, `re`, `mi`, `fa`, `sol`, `la`, `si`
+    /// - Accidentals: `s`, `b`, `ss`, `bb`
+    /// - Quarter-tone accidentals: `sqt`, `bqt`, `stqt`, `btqt`
+    Portugues,
+    /// Finnish note names and accidentals.
+    ///
+    /// - Note names: `c`, `d`, `e`, `f`, `g`, `a`, `h`, with `b` for B flat
+    /// - Accidentals: `is`, `es`, `isis`, `eses`, contracted for the flats of
+    ///   E and A
+    Suomi,
+    /// Swedish note names and accidentals.
+    ///
+    /// - Note names: `c`, `d`, `e`, `f`, `g`, `a`, `h`, with `b` for B flat
+    /// - Accidentals: `iss`, `ess`, `ississ`, `essess`, contracted for the
+    ///   flats of E and A
+    Svenska,
+    /// Flemish note names and accidentals.
+    ///
+    /// - Note names: `do`, `re`, `mi`, `fa`, `sol`, `la`, `si`
+    /// - Accidentals: `k`, `b`, `kk`, `bb`
+    Vlaams,
 }
 
 impl Default for NoteNameLanguage {
@@ -180,13 +324,36 @@ impl Default for NoteNameLanguage {
     }
 }
 
+/// How to spell accidentals when writing LilyPond notes.
+///
+/// English is the only language with long accidentals, e.g. `f-sharp` for
+/// `fs`; other languages write their usual accidentals for either spelling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccidentalSpelling {
+    /// One-letter accidentals, e.g. `s`, `ff`, `qs`.
+    Short,
+    /// Spelled-out accidentals, e.g. `-sharp`, `-flatflat`. Quarter-tone
+    /// accidentals have no long form and are written in short form.
+    Long,
+}
+
+impl Default for AccidentalSpelling {
+    /// Initialize the default `AccidentalSpelling` to short accidentals.
+    fn default() -> Self {
+        Self::Short
+    }
+}
+
 lazy_static! {
-    /// The [`NoteNameLanguage`][crate::NoteNameLanguage] for parsing from and
-    /// encoding to
+    /// The default [`NoteNameLanguage`][crate::NoteNameLanguage] for parsing
+    /// from and encoding to
     /// [`LilyPondNote`][crate::lilypond_objects::lilypond_note::LilyPondNote]
-    /// objects.
+    /// objects when no language is given explicitly.
     ///
-    /// The default NoteNameLanguage for the library is English.
+    /// The default NoteNameLanguage for the library is English. To use another
+    /// language, pass it to the conversion instead, e.g. with
+    /// [`LilyPondNote::with_language`][crate::lilypond_objects::lilypond_note::LilyPondNote::with_language]
+    /// or [`NoteNameLanguage::lilypond_from_note`].
     ///
     /// Note that this is created by the `lazy_static!` macro, and as such may
     /// need to be dereferenced with the `*` operator (e.g. in `match`
@@ -197,7 +364,5 @@ lazy_static! {
     ///     _ => println!("Don't forget to deref!"),
     /// }
     /// ```
-    ///
-    /// TODO: make this read a config file via the `config` crate.
     pub static ref NOTE_NAME_LANGUAGE: NoteNameLanguage = Default::default();
 }
@@ -1,6 +1,6 @@
 //! Abstractions for LilyPond-syntax `String` representations of notes.
 
-use crate::{lilypond_from_note, notation::note::Note, NOTE_REGEX_STR};
+use crate::{notation::note::Note, NoteNameLanguage, NOTE_NAME_LANGUAGE, NOTE_REGEX_STR};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -8,7 +8,9 @@ lazy_static! {
     /// A regular expression for matching and capturing from LilyPond notes.
     ///
     /// This regex changes depending on the language set by
-    /// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE].
+    /// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE]. The regex
+    /// for any other language can be obtained with
+    /// [`NoteNameLanguage::note_regex`][crate::NoteNameLanguage::note_regex].
     ///
     /// This regex contains the following named capture groups:
     /// - `note_name`: the name of the note according to the chosen language,
@@ -70,13 +72,18 @@ lazy_static! {
 }
 
 /// A struct to contain the string representation of a LilyPond note.
+///
+/// Each `LilyPondNote` knows the [`NoteNameLanguage`] it is written in.
 #[derive(Debug, PartialEq)]
 pub struct LilyPondNote {
     note: String,
+    language: NoteNameLanguage,
 }
 
 impl LilyPondNote {
-    /// Initialize a lilypond note string, checking for proper formatting
+    /// Initialize a lilypond note string in the language of
+    /// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE], checking for
+    /// proper formatting
     ///
     /// # Errors
     ///
@@ -105,15 +112,70 @@ impl LilyPondNote {
     /// assert_eq!(ly_note, Err(String::from("Invalid LilyPond note \"asdf\".")));
     /// ```
     pub fn new(note: &str) -> Result<Self, String> {
-        if LILYPOND_NOTE_REGEX.is_match(note) {
+        Self::with_language(note, *NOTE_NAME_LANGUAGE)
+    }
+
+    /// Initialize a lilypond note string in the given `language`, checking
+    /// for proper formatting
+    ///
+    /// # Errors
+    ///
+    /// This function returns a Result according to whether `&str note` matches
+    /// the [note regex][crate::NoteNameLanguage::note_regex] of `language`.
+    /// On a successful match, return `Ok(LilyPondNote)`, and on a failure,
+    /// return `Err(String)`, where the `String` is the error message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::lilypond_objects::lilypond_note::LilyPondNote;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let ly_note = LilyPondNote::with_language("bes4", NoteNameLanguage::Nederlands).unwrap();
+    /// assert_eq!(ly_note.get_capture("accidental"), "es");
+    ///
+    /// let ly_note = LilyPondNote::with_language("bes4", NoteNameLanguage::English);
+    /// assert_eq!(ly_note, Err(String::from("Invalid LilyPond note \"bes4\".")));
+    /// ```
+    pub fn with_language(note: &str, language: NoteNameLanguage) -> Result<Self, String> {
+        if language.note_regex().is_match(note) {
             Ok(LilyPondNote {
                 note: note.to_string(),
+                language,
             })
         } else {
             Err(format!("Invalid LilyPond note \"{}\".", note))
         }
     }
 
+    /// Attempt to translate a note object into a lilypond note string in the
+    /// given `language`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the output of
+    /// [`NoteNameLanguage::lilypond_from_note`] is not a valid note in
+    /// `language`, as is the case for notes with an
+    /// [`Accidental::Other`][crate::notation::pitch::Accidental::Other]
+    /// alteration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::lilypond_objects::lilypond_note::LilyPondNote;
+    /// use lilypond::notation::note::Note;
+    /// use lilypond::notation::pitch::NoteName;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let mut note = Note::new(NoteName::E);
+    /// note.pitch.flatten();
+    /// let ly_note = LilyPondNote::from_note(&note, NoteNameLanguage::Nederlands).unwrap();
+    /// assert_eq!(ly_note.get_note(), "ees4");
+    /// ```
+    pub fn from_note(note: &Note, language: NoteNameLanguage) -> Result<Self, String> {
+        Self::with_language(language.lilypond_from_note(note).as_str(), language)
+    }
+
     /// Return a reference to the string that represents the current
     /// [`LilyPondNote`].
     ///
@@ -129,7 +191,14 @@ impl LilyPondNote {
         &self.note
     }
 
-    /// Get a capture from current note according to label `&str capture` in `LILYPOND_NOTE_REGEX`.
+    /// Return the [`NoteNameLanguage`] the current [`LilyPondNote`] is
+    /// written in.
+    pub fn get_language(&self) -> NoteNameLanguage {
+        self.language
+    }
+
+    /// Get a capture from current note according to label `&str capture` in
+    /// the note regex of its language.
     ///
     /// Available capture group labels follow. See
     /// [`LILYPOND_NOTE_REGEX`][struct@crate::lilypond_objects::lilypond_note::LILYPOND_NOTE_REGEX]
@@ -153,7 +222,8 @@ impl LilyPondNote {
     /// assert_eq!(note.get_capture("dot"), "");
     /// ```
     pub fn get_capture(&self, capture: &str) -> String {
-        LILYPOND_NOTE_REGEX
+        self.language
+            .note_regex()
             .replace(&self.note, format!("${}", capture))
             .to_string()
     }
@@ -162,16 +232,19 @@ impl LilyPondNote {
 impl std::convert::TryFrom<&Note> for LilyPondNote {
     type Error = String;
 
-    /// Attempt to translate a note object into a lilypond note string.
+    /// Attempt to translate a note object into a lilypond note string in the
+    /// language of [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE].
     ///
     /// # Errors
     ///
     /// Returns a `Result` according to whether the conversion was
     /// successful. If an `Err(String)` was returned, the output of
-    /// [`lilypond_from_note`] was likely malformed in some way. If so, the
-    /// language module corresponding to the value of
+    /// [`lilypond_from_note`][crate::lilypond_from_note] was likely malformed
+    /// in some way. If so, the language module corresponding to the value of
     /// [`NOTE_NAME_LANGUAGE`][struct@crate::NOTE_NAME_LANGUAGE] has a bug and
-    /// should be reported.
+    /// should be reported. Notes with an
+    /// [`Accidental::Other`][crate::notation::pitch::Accidental::Other]
+    /// alteration are written as Scheme expressions and are always rejected.
     ///
     /// # Examples
     ///
@@ -186,7 +259,7 @@ impl std::convert::TryFrom<&Note> for LilyPondNote {
     /// assert_eq!(ly_note.get_note(), "a4");
     /// ```
     fn try_from(note: &Note) -> Result<Self, Self::Error> {
-        LilyPondNote::new(lilypond_from_note(note).as_str())
+        LilyPondNote::from_note(note, *NOTE_NAME_LANGUAGE)
     }
 }
 
@@ -198,6 +271,7 @@ mod test {
     use crate::notation::note::Note;
     use crate::notation::pitch::{Accidental, NoteName, Octave, Pitch};
     use crate::notation::rhythm::{Dots, DurationType, Length, Rhythm};
+    use crate::NoteNameLanguage;
     fn test_lilypond_note(ly_str: &str) {
         let note = LilyPondNote::new(ly_str).unwrap().note;
         assert_eq!(ly_str, note);
@@ -273,6 +347,29 @@ mod test {
         assert_eq!("a4", ly_note);
     }
     #[test]
+    fn test_language() {
+        let ly_note = LilyPondNote::new("a4").unwrap();
+        assert_eq!(ly_note.get_language(), NoteNameLanguage::English);
+        let ly_note =
+            LilyPondNote::with_language("aeses,,8.", NoteNameLanguage::Nederlands).unwrap();
+        assert_eq!(ly_note.get_language(), NoteNameLanguage::Nederlands);
+        assert_eq!(ly_note.get_capture("note_name"), "a");
+        assert_eq!(ly_note.get_capture("accidental"), "eses");
+        assert_eq!(ly_note.get_capture("octave"), ",,");
+        assert!(LilyPondNote::with_language("aff", NoteNameLanguage::Nederlands).is_err());
+    }
+    #[test]
+    fn test_language_round_trip() {
+        let ly_note =
+            LilyPondNote::with_language("fisis'16", NoteNameLanguage::Nederlands).unwrap();
+        let note = Note::try_from(&ly_note).unwrap();
+        assert_eq!(note.pitch.accidental, Accidental::DoubleSharp);
+        let english = LilyPondNote::from_note(&note, NoteNameLanguage::English).unwrap();
+        assert_eq!(english.get_note(), "fss'16");
+        let dutch = LilyPondNote::from_note(&note, NoteNameLanguage::Nederlands).unwrap();
+        assert_eq!(dutch, ly_note);
+    }
+    #[test]
     fn test_to_note() {
         let ly_note = LilyPondNote::new("r8.").unwrap();
         let test_note = Note {
@@ -286,6 +383,10 @@ mod test {
                 duration_type: DurationType::Rest,
                 dots: Dots::new(1),
             },
+            tie: false,
+            articulations: vec![],
+            beam: None,
+            dynamic: None,
         };
         assert_eq!(
             <&LilyPondNote as TryInto<Note>>::try_into(&ly_note).unwrap(),
@@ -303,6 +404,10 @@ mod test {
                 duration_type: DurationType::Note,
                 dots: Dots::new(0),
             },
+            tie: false,
+            articulations: vec![],
+            beam: None,
+            dynamic: None,
         };
         assert_eq!(
             <&LilyPondNote as TryInto<Note>>::try_into(&ly_note).unwrap(),
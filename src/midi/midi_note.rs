@@ -1,9 +1,12 @@
 //! Abstractions for encoding single pitches as MIDI integers.
 
-use crate::notation::pitch::{Accidental, NoteName, Octave, Pitch};
+use crate::notation::pitch::{NoteName, Octave, Pitch};
+use crate::tuning::temperament::Tuning;
 
 /// A representation of a single pitch as a MIDI integer.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "i16", into = "i16"))]
 pub struct MidiNote {
     note: i16,
 }
@@ -49,6 +52,43 @@ impl MidiNote {
     pub fn get_note(&self) -> i16 {
         self.note
     }
+
+    /// Return the frequency of the MIDI note in hertz in a [`Tuning`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::midi_note::MidiNote;
+    /// use lilypond::tuning::temperament::Tuning;
+    ///
+    /// let note = MidiNote::new(81).unwrap();
+    /// assert_eq!(note.frequency(&Tuning::default()), 880.0);
+    /// ```
+    pub fn frequency(&self, tuning: &Tuning) -> f64 {
+        tuning.midi_frequency(self)
+    }
+
+    /// Attempt to find the MIDI note nearest to a frequency in hertz in a
+    /// [`Tuning`], and return it with the deviation of the frequency from it
+    /// in cents (see [`Tuning::nearest_midi_note`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the frequency is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::midi_note::MidiNote;
+    /// use lilypond::tuning::temperament::Tuning;
+    ///
+    /// let (note, cents) = MidiNote::from_frequency(435.0, &Tuning::default()).unwrap();
+    /// assert_eq!(note.get_note(), 69);
+    /// assert_eq!(cents.round(), -20.0);
+    /// ```
+    pub fn from_frequency(frequency: f64, tuning: &Tuning) -> Result<(MidiNote, f64), String> {
+        tuning.nearest_midi_note(frequency)
+    }
 }
 
 impl Default for MidiNote {
@@ -61,6 +101,16 @@ impl Default for MidiNote {
 impl std::convert::TryFrom<&Pitch> for MidiNote {
     type Error = String;
 
+    /// Attempt to convert a [`Pitch`] to a [`MidiNote`].
+    ///
+    /// Microtonal alterations are truncated toward the natural note, e.g. a
+    /// quarter-tone sharp C4 becomes MIDI note 60. The remaining fraction of
+    /// a semitone can be obtained as a
+    /// [`PitchBend`][crate::midi::pitch_bend::PitchBend].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the resulting MIDI integer is negative.
     fn try_from(pitch: &Pitch) -> Result<Self, Self::Error> {
         // initialize note to MIDI note for pitch C of a given octave
         let mut note: i16 = match pitch.octave {
@@ -89,19 +139,30 @@ impl std::convert::TryFrom<&Pitch> for MidiNote {
             NoteName::None => 0,
         };
 
-        // add to note the number of semitones corresponding to the accidental
-        note += match pitch.accidental {
-            Accidental::None => 0,
-            Accidental::Sharp => 1,
-            Accidental::DoubleSharp => 2,
-            Accidental::Flat => -1,
-            Accidental::DoubleFlat => -2,
-        };
+        // add to note the whole number of semitones of the accidental; any
+        // remaining fraction of a semitone is encoded as a `PitchBend`
+        note += pitch.accidental.alteration().trunc().to_integer() as i16;
+
+        Self::new(note)
+    }
+}
+
+impl std::convert::TryFrom<i16> for MidiNote {
+    type Error = String;
 
+    /// Attempt to convert a MIDI integer to a [`MidiNote`], as with
+    /// [`MidiNote::new`].
+    fn try_from(note: i16) -> Result<Self, Self::Error> {
         Self::new(note)
     }
 }
 
+impl From<MidiNote> for i16 {
+    fn from(note: MidiNote) -> Self {
+        note.note
+    }
+}
+
 impl PartialOrd for MidiNote {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         self.get_note().partial_cmp(&other.get_note())
@@ -133,6 +194,18 @@ mod test {
         let midi_note = MidiNote::new(-1).unwrap();
     }
     #[test]
+    fn test_from_pitch() {
+        let note = Note::try_from(&LilyPondNote::new("cs'").unwrap()).unwrap();
+        let midi_note = MidiNote::try_from(&note.pitch).unwrap();
+        assert_eq!(midi_note.get_note(), 61);
+        let note = Note::try_from(&LilyPondNote::new("cqs'").unwrap()).unwrap();
+        let midi_note = MidiNote::try_from(&note.pitch).unwrap();
+        assert_eq!(midi_note.get_note(), 60);
+        let note = Note::try_from(&LilyPondNote::new("ctqf'").unwrap()).unwrap();
+        let midi_note = MidiNote::try_from(&note.pitch).unwrap();
+        assert_eq!(midi_note.get_note(), 59);
+    }
+    #[test]
     fn test_from_pitch_negative() {
         let note = Note::try_from(&LilyPondNote::new("rff").unwrap()).unwrap();
         let midi_note = MidiNote::try_from(&note.pitch).unwrap();
@@ -1,3 +1,4 @@
 //! Abstractions for encoding notes as MIDI integers.
 
 pub mod midi_note;
+pub mod pitch_bend;
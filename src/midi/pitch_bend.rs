@@ -0,0 +1,203 @@
+//! Abstractions for encoding microtonal alterations as MIDI pitch bends.
+
+use num_rational::Rational32;
+
+use crate::notation::pitch::Pitch;
+
+/// The largest value of a 14-bit MIDI pitch bend.
+pub const MAX_PITCH_BEND: u16 = 16383;
+
+/// The value of a MIDI pitch bend that leaves the pitch unchanged.
+pub const CENTER_PITCH_BEND: u16 = 8192;
+
+/// The pitch-bend range, in semitones, that most synthesizers default to.
+pub const DEFAULT_PITCH_BEND_RANGE: u8 = 2;
+
+/// A 14-bit MIDI pitch-bend value.
+///
+/// A value of [`CENTER_PITCH_BEND`] means no bend; smaller values bend down,
+/// and larger values bend up, to at most the receiver's pitch-bend range.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u16", into = "u16"))]
+pub struct PitchBend {
+    value: u16,
+}
+
+impl PitchBend {
+    /// Attempt to initialize a new `PitchBend` with a raw 14-bit value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if `value` is greater than [`MAX_PITCH_BEND`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::pitch_bend::PitchBend;
+    ///
+    /// let bend = PitchBend::new(8192).unwrap();
+    /// assert_eq!(bend.get_value(), 8192);
+    /// assert_eq!(PitchBend::new(20000), Err(String::from("Invalid pitch bend 20000.")));
+    /// ```
+    pub fn new(value: u16) -> Result<Self, String> {
+        if value > MAX_PITCH_BEND {
+            Err(format!("Invalid pitch bend {}.", value))
+        } else {
+            Ok(Self { value })
+        }
+    }
+
+    /// Attempt to initialize a `PitchBend` that bends by `semitones` on a
+    /// receiver with a pitch-bend range of `range` semitones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if `range` is zero or `semitones` is outside of
+    /// the range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::pitch_bend::PitchBend;
+    /// use lilypond::notation::pitch::Alteration;
+    ///
+    /// let bend = PitchBend::from_semitones(Alteration::new(1, 2), 2).unwrap();
+    /// assert_eq!(bend.get_value(), 10240);
+    /// let bend = PitchBend::from_semitones(Alteration::new(-1, 2), 2).unwrap();
+    /// assert_eq!(bend.get_value(), 6144);
+    /// ```
+    pub fn from_semitones(semitones: Rational32, range: u8) -> Result<Self, String> {
+        if range == 0 {
+            return Err(String::from("Invalid pitch-bend range 0."));
+        }
+        let range = Rational32::from_integer(range.into());
+        if semitones > range || semitones < -range {
+            return Err(format!(
+                "Pitch bend of {} semitones exceeds range of {}.",
+                semitones, range
+            ));
+        }
+        let offset = (semitones / range * i32::from(CENTER_PITCH_BEND))
+            .round()
+            .to_integer();
+        let value = (i32::from(CENTER_PITCH_BEND) + offset).min(MAX_PITCH_BEND.into());
+        Self::new(value as u16)
+    }
+
+    /// Get the raw 14-bit value of the current [`PitchBend`].
+    pub fn get_value(&self) -> u16 {
+        self.value
+    }
+
+    /// Get the bend in semitones on a receiver with a pitch-bend range of
+    /// `range` semitones.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::pitch_bend::PitchBend;
+    ///
+    /// let bend = PitchBend::new(10240).unwrap();
+    /// assert_eq!(bend.semitones(2), 0.5);
+    /// ```
+    pub fn semitones(&self, range: u8) -> f64 {
+        (f64::from(self.value) - f64::from(CENTER_PITCH_BEND)) / f64::from(CENTER_PITCH_BEND)
+            * f64::from(range)
+    }
+}
+
+impl Default for PitchBend {
+    /// Set default `PitchBend` to no bend.
+    fn default() -> Self {
+        PitchBend {
+            value: CENTER_PITCH_BEND,
+        }
+    }
+}
+
+impl std::convert::TryFrom<u16> for PitchBend {
+    type Error = String;
+
+    /// Attempt to convert a raw 14-bit value to a [`PitchBend`], as with
+    /// [`PitchBend::new`].
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<PitchBend> for u16 {
+    fn from(bend: PitchBend) -> Self {
+        bend.value
+    }
+}
+
+impl std::convert::TryFrom<&Pitch> for PitchBend {
+    type Error = String;
+
+    /// Attempt to convert the microtonal part of a [`Pitch`] to a
+    /// [`PitchBend`], assuming the [`DEFAULT_PITCH_BEND_RANGE`].
+    ///
+    /// This is the fraction of a semitone that the
+    /// [`MidiNote`][crate::midi::midi_note::MidiNote] conversion of the same
+    /// pitch truncates, so together they encode the full pitch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::convert::TryFrom;
+    /// use lilypond::midi::pitch_bend::PitchBend;
+    /// use lilypond::notation::pitch::{Accidental, NoteName, Pitch};
+    ///
+    /// let mut pitch = Pitch::new(NoteName::C);
+    /// pitch.accidental(Accidental::ThreeQuarterFlat);
+    /// let bend = PitchBend::try_from(&pitch).unwrap();
+    /// assert_eq!(bend.get_value(), 6144);
+    /// ```
+    fn try_from(pitch: &Pitch) -> Result<Self, Self::Error> {
+        Self::from_semitones(
+            pitch.accidental.alteration().fract(),
+            DEFAULT_PITCH_BEND_RANGE,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use num_rational::Rational32;
+
+    use crate::midi::pitch_bend::*;
+    use crate::notation::pitch::{Accidental, NoteName, Pitch};
+    #[test]
+    fn test_default() {
+        assert_eq!(PitchBend::default().get_value(), CENTER_PITCH_BEND);
+    }
+    #[test]
+    fn test_from_semitones() {
+        let bend = PitchBend::from_semitones(Rational32::from_integer(2), 2).unwrap();
+        assert_eq!(bend.get_value(), MAX_PITCH_BEND);
+        let bend = PitchBend::from_semitones(Rational32::from_integer(-2), 2).unwrap();
+        assert_eq!(bend.get_value(), 0);
+        let bend = PitchBend::from_semitones(Rational32::new(1, 4), 12).unwrap();
+        assert_eq!(bend.get_value(), 8363);
+    }
+    #[test]
+    fn test_from_semitones_error() {
+        assert!(PitchBend::from_semitones(Rational32::from_integer(3), 2).is_err());
+        assert!(PitchBend::from_semitones(Rational32::from_integer(0), 0).is_err());
+    }
+    #[test]
+    fn test_from_pitch() {
+        let mut pitch = Pitch::new(NoteName::G);
+        let bend = PitchBend::try_from(&pitch).unwrap();
+        assert_eq!(bend, PitchBend::default());
+        pitch.accidental(Accidental::QuarterSharp);
+        let bend = PitchBend::try_from(&pitch).unwrap();
+        assert_eq!(bend.get_value(), 10240);
+        pitch.accidental(Accidental::ThreeQuarterSharp);
+        let bend = PitchBend::try_from(&pitch).unwrap();
+        assert_eq!(bend.get_value(), 10240);
+    }
+}
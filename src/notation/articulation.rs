@@ -0,0 +1,111 @@
+//! Articulations attached to notes and chords.
+
+/// Articulations, written after a note like LilyPond's `c4->` or
+/// `c4\fermata`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Articulation {
+    Accent,
+    Marcato,
+    Staccatissimo,
+    Staccato,
+    Tenuto,
+    Portato,
+    Fermata,
+    Trill,
+}
+
+static ARTICULATIONS: [Articulation; 8] = [
+    Articulation::Accent,
+    Articulation::Marcato,
+    Articulation::Staccatissimo,
+    Articulation::Staccato,
+    Articulation::Tenuto,
+    Articulation::Portato,
+    Articulation::Fermata,
+    Articulation::Trill,
+];
+
+impl Articulation {
+    /// Return the name of the articulation's LilyPond command, e.g.
+    /// `staccato` for `\staccato`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Articulation::Accent => "accent",
+            Articulation::Marcato => "marcato",
+            Articulation::Staccatissimo => "staccatissimo",
+            Articulation::Staccato => "staccato",
+            Articulation::Tenuto => "tenuto",
+            Articulation::Portato => "portato",
+            Articulation::Fermata => "fermata",
+            Articulation::Trill => "trill",
+        }
+    }
+    /// Return the articulation's LilyPond shorthand, e.g. `-.` for a
+    /// staccato, if it has one.
+    pub fn shorthand(&self) -> Option<&'static str> {
+        match self {
+            Articulation::Accent => Some("->"),
+            Articulation::Marcato => Some("-^"),
+            Articulation::Staccatissimo => Some("-!"),
+            Articulation::Staccato => Some("-."),
+            Articulation::Tenuto => Some("--"),
+            Articulation::Portato => Some("-_"),
+            Articulation::Fermata | Articulation::Trill => None,
+        }
+    }
+    /// Find the articulation with a LilyPond shorthand, e.g. `-.`.
+    pub fn from_shorthand(shorthand: &str) -> Option<Articulation> {
+        ARTICULATIONS
+            .iter()
+            .find(|articulation| articulation.shorthand() == Some(shorthand))
+            .cloned()
+    }
+    /// Find the articulation with a LilyPond command name, e.g. `staccato`.
+    pub fn from_name(name: &str) -> Option<Articulation> {
+        ARTICULATIONS
+            .iter()
+            .find(|articulation| articulation.name() == name)
+            .cloned()
+    }
+}
+
+impl std::fmt::Display for Articulation {
+    /// Write the articulation as LilyPond, using its shorthand if it has one,
+    /// e.g. `->` or `\fermata`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.shorthand() {
+            Some(shorthand) => write!(f, "{}", shorthand),
+            None => write!(f, "\\{}", self.name()),
+        }
+    }
+}
+
+impl std::str::FromStr for Articulation {
+    type Err = String;
+
+    /// Parse an articulation's LilyPond shorthand or command, e.g. `-.` or
+    /// `\staccato`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Articulation::from_shorthand(s)
+            .or_else(|| s.strip_prefix('\\').and_then(Articulation::from_name))
+            .ok_or_else(|| format!("Invalid articulation '{}'.", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::articulation::*;
+    #[test]
+    fn test_parse_display() {
+        for articulation in &ARTICULATIONS {
+            let lilypond = articulation.to_string();
+            assert_eq!(lilypond.parse(), Ok(*articulation));
+            let command = format!("\\{}", articulation.name());
+            assert_eq!(command.parse(), Ok(*articulation));
+        }
+        assert_eq!("\\fermata".parse(), Ok(Articulation::Fermata));
+        assert!("-?".parse::<Articulation>().is_err());
+        assert!("staccato".parse::<Articulation>().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Beams joining eighth notes and shorter, and grouping them by beat.
+
+use crate::notation::measure::Measures;
+use crate::notation::rhythm::{Duration, DurationType, Length};
+use crate::notation::transform::rhythm;
+use crate::notation::voice::Event;
+
+/// The ends of a manual beam, written after a note like LilyPond's `c8[`
+/// and `d8]`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Beam {
+    Start,
+    End,
+}
+
+impl std::fmt::Display for Beam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Beam::Start => write!(f, "["),
+            Beam::End => write!(f, "]"),
+        }
+    }
+}
+
+impl Measures {
+    /// Attempt to beam the music by beat: every run of two or more eighth
+    /// notes or shorter within one beat of the time signature's
+    /// [beat structure](crate::notation::time::TimeSignature::groups) gets a
+    /// manual beam, and every other beam is removed. Rests, and notes that
+    /// cross into the next beat, are not beamed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the time signature is
+    /// [invalid](crate::notation::time::TimeSignature::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::measure::Measures;
+    /// use lilypond::notation::time::TimeSignature;
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c8 d e f g a b", NoteNameLanguage::English).unwrap());
+    /// let mut time_signature = TimeSignature::from_fraction(7, 8).unwrap();
+    /// time_signature.beat_structure(vec![2, 2, 3]).unwrap();
+    ///
+    /// let measures = Measures::from_voice(&voice, &time_signature, None).unwrap();
+    ///
+    /// assert_eq!(
+    ///     measures.beam().unwrap().to_lilypond(NoteNameLanguage::English),
+    ///     "{ \\time 2,2,3 7/8 c8[ d8] e8[ f8] g8[ a8 b8] }"
+    /// );
+    /// ```
+    pub fn beam(&self) -> Result<Measures, String> {
+        let time_signature = &self.time_signature;
+        time_signature.validate()?;
+        // the time from the start of the measure to the end of each beat
+        let ends: Vec<Duration> = time_signature
+            .beats()
+            .into_iter()
+            .scan(Duration::from_integer(0), |end, beat| {
+                *end += beat;
+                Some(*end)
+            })
+            .collect();
+        let length = ends.last().cloned().unwrap_or_default();
+        let mut beamed = self.clone();
+        for (index, measure) in beamed.measures.iter_mut().enumerate() {
+            // a pickup measure is the end of a full one
+            let mut onset = match self.partial {
+                Some(partial) if index == 0 => length - partial,
+                _ => Duration::from_integer(0),
+            };
+            // the beat of each event, if it can be beamed
+            let mut beats = vec![];
+            for event in measure.events.iter_mut() {
+                set_beam(event, None);
+                let end = onset + rhythm(event).duration();
+                let beat = ends.iter().position(|beat_end| onset < *beat_end);
+                let beamable = is_beamable(event) && beat.is_some_and(|beat| end <= ends[beat]);
+                beats.push(if beamable { beat } else { None });
+                onset = end;
+            }
+            let mut start = 0;
+            while start < beats.len() {
+                let mut end = start + 1;
+                while end < beats.len() && beats[start].is_some() && beats[end] == beats[start] {
+                    end += 1;
+                }
+                if end - start > 1 {
+                    set_beam(&mut measure.events[start], Some(Beam::Start));
+                    set_beam(&mut measure.events[end - 1], Some(Beam::End));
+                }
+                start = end;
+            }
+        }
+        Ok(beamed)
+    }
+}
+
+/// Return whether an event is a note or chord short enough to be beamed.
+fn is_beamable(event: &Event) -> bool {
+    let rhythm = rhythm(event);
+    rhythm.duration_type == DurationType::Note && rhythm.length.as_u16() >= Length::Eighth.as_u16()
+}
+
+/// Choose whether an event starts or ends a beam.
+fn set_beam(event: &mut Event, beam: Option<Beam>) {
+    match event {
+        Event::Note(note) => note.beam = beam,
+        Event::Chord(chord) => chord.beam = beam,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::beam::*;
+    use crate::notation::time::TimeSignature;
+    use crate::notation::voice::Voice;
+    use crate::parser::{parse_measures, parse_notes};
+    use crate::NoteNameLanguage;
+    fn beamed(notes: &str, numerator: u8, denominator: u8, beat_structure: Vec<u8>) -> String {
+        let voice = Voice::from(parse_notes(notes, NoteNameLanguage::English).unwrap());
+        let mut time_signature = TimeSignature::from_fraction(numerator, denominator).unwrap();
+        time_signature.beat_structure(beat_structure).unwrap();
+        Measures::from_voice(&voice, &time_signature, None)
+            .unwrap()
+            .beam()
+            .unwrap()
+            .to_lilypond(NoteNameLanguage::English)
+    }
+    #[test]
+    fn test_beam_compound() {
+        assert_eq!(
+            beamed("c8 d e f4 g8 a16 b c' d' e' f'", 6, 8, vec![]),
+            "{ \\time 6/8 c8[ d8 e8] f4 g8 | a16[ b16 c'16 d'16 e'16 f'16] }"
+        );
+    }
+    #[test]
+    fn test_beam_simple() {
+        // rests and notes crossing a beat break beams
+        assert_eq!(
+            beamed("c8 d r e f4. g8", 4, 4, vec![]),
+            "{ \\time 4/4 c8[ d8] r8 e8 f4. g8 }"
+        );
+        assert_eq!(
+            beamed("c16 d e f g8. a16", 2, 4, vec![]),
+            "{ \\time 2/4 c16[ d16 e16 f16] g8.[ a16] }"
+        );
+    }
+    #[test]
+    fn test_beam_partial() {
+        let measures = parse_measures(
+            "\\time 3/8 \\partial 8*2 c8 d | e8 f[ g] | a8 b c'",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        assert_eq!(
+            measures
+                .beam()
+                .unwrap()
+                .to_lilypond(NoteNameLanguage::English),
+            "{ \\time 3/8 \\partial 4 c8[ d8] | e8[ f8 g8] | a8[ b8 c'8] }"
+        );
+    }
+    #[test]
+    fn test_beam_errors() {
+        let mut measures = Measures::new();
+        measures.time_signature.beat_structure = vec![2, 1];
+        assert_eq!(
+            measures.beam(),
+            Err(String::from(
+                "Beat structure 2+1 does not fill a measure of 4/4."
+            ))
+        );
+    }
+    #[test]
+    fn test_display() {
+        assert_eq!(Beam::Start.to_string(), "[");
+        assert_eq!(Beam::End.to_string(), "]");
+    }
+}
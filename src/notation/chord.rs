@@ -0,0 +1,391 @@
+//! Abstract types for chords, several pitches sharing one rhythm.
+
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::dynamic::Dynamic;
+use crate::notation::pitch::{Alteration, Pitch};
+use crate::notation::rhythm::Rhythm;
+
+/// Qualities of triads and seventh chords.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    /// A major triad with a minor seventh.
+    DominantSeventh,
+    /// A major triad with a major seventh.
+    MajorSeventh,
+    /// A minor triad with a minor seventh.
+    MinorSeventh,
+    /// A minor triad with a major seventh.
+    MinorMajorSeventh,
+    /// A diminished triad with a minor seventh.
+    HalfDiminishedSeventh,
+    /// A diminished triad with a diminished seventh.
+    DiminishedSeventh,
+}
+
+impl ChordQuality {
+    /// Check whether chords of this quality have a seventh.
+    pub fn is_seventh(&self) -> bool {
+        !matches!(
+            self,
+            ChordQuality::Major
+                | ChordQuality::Minor
+                | ChordQuality::Diminished
+                | ChordQuality::Augmented
+        )
+    }
+    /// Return the quality of chords with the given numbers of semitones from
+    /// the root to the third, fifth and seventh. A missing fifth is assumed
+    /// to be perfect.
+    fn from_semitones(third: i32, fifth: Option<i32>, seventh: Option<i32>) -> Option<Self> {
+        Some(match (third, fifth, seventh) {
+            (4, None | Some(7), None) => ChordQuality::Major,
+            (3, None | Some(7), None) => ChordQuality::Minor,
+            (3, Some(6), None) => ChordQuality::Diminished,
+            (4, Some(8), None) => ChordQuality::Augmented,
+            (4, None | Some(7), Some(10)) => ChordQuality::DominantSeventh,
+            (4, None | Some(7), Some(11)) => ChordQuality::MajorSeventh,
+            (3, None | Some(7), Some(10)) => ChordQuality::MinorSeventh,
+            (3, None | Some(7), Some(11)) => ChordQuality::MinorMajorSeventh,
+            (3, Some(6), Some(10)) => ChordQuality::HalfDiminishedSeventh,
+            (3, None | Some(6), Some(9)) => ChordQuality::DiminishedSeventh,
+            _ => return None,
+        })
+    }
+}
+
+/// Pitches sounding together with one rhythm, like LilyPond's `<c e g>4`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chord {
+    /// The pitches of the chord, in the order they were written.
+    pub pitches: Vec<Pitch>,
+    pub rhythm: Rhythm,
+    /// Whether the chord is tied to the next one, like LilyPond's
+    /// `<c e g>4~`.
+    pub tie: bool,
+    /// The articulations of the chord, in the order they are written.
+    pub articulations: Vec<Articulation>,
+    /// Whether the chord starts or ends a manual beam, like LilyPond's `c8[`.
+    pub beam: Option<Beam>,
+    /// The dynamic mark under the chord, like LilyPond's `c4\mf`.
+    pub dynamic: Option<Dynamic>,
+}
+
+impl Chord {
+    /// Construct a new chord with the given pitches and the default rhythm.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::chord::Chord;
+    /// use lilypond::notation::pitch::{Pitch, NoteName};
+    /// use lilypond::notation::rhythm::Length;
+    ///
+    /// let chord = Chord::new(vec![Pitch::new(NoteName::C), Pitch::new(NoteName::E)]);
+    ///
+    /// assert_eq!(chord.pitches.len(), 2);
+    /// assert_eq!(chord.rhythm.length, Length::Quarter);
+    /// ```
+    pub fn new(pitches: Vec<Pitch>) -> Chord {
+        Chord {
+            pitches,
+            rhythm: Rhythm::new(),
+            tie: false,
+            articulations: vec![],
+            beam: None,
+            dynamic: None,
+        }
+    }
+    /// Attempt to find the lowest pitch of the chord.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the chord has no pitches, or has rests among
+    /// its pitches.
+    pub fn bass(&self) -> Result<&Pitch, String> {
+        let mut bass: Option<(&Pitch, (Alteration, i32))> = None;
+        for pitch in &self.pitches {
+            let height = (pitch.semitones()?, pitch.diatonic_number()?);
+            let lower = match bass {
+                Some((_, lowest)) => height < lowest,
+                None => true,
+            };
+            if lower {
+                bass = Some((pitch, height));
+            }
+        }
+        bass.map(|(pitch, _)| pitch)
+            .ok_or_else(|| String::from("The chord has no pitches."))
+    }
+    /// Attempt to find the root of the chord, the lowest pitch of its
+    /// spelling as a stack of thirds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the chord has no pitches or has rests among
+    /// its pitches, if its pitches are not a triad or seventh chord, which
+    /// must at least have a root and a third, or if it has pitches that are
+    /// not a whole number of semitones apart.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::chord::Chord;
+    /// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+    ///
+    /// let mut c = Pitch::new(NoteName::C);
+    /// c.octave(Octave::S4);
+    /// let chord = Chord::new(vec![Pitch::new(NoteName::E), Pitch::new(NoteName::G), c]);
+    ///
+    /// assert_eq!(chord.root().unwrap().note_name, NoteName::C);
+    /// ```
+    pub fn root(&self) -> Result<&Pitch, String> {
+        self.stack().map(|(root, _)| root)
+    }
+    /// Attempt to find the quality of the chord.
+    ///
+    /// # Errors
+    ///
+    /// See [`root`][Chord::root].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::chord::{Chord, ChordQuality};
+    /// use lilypond::notation::pitch::{Pitch, NoteName};
+    ///
+    /// let mut f = Pitch::new(NoteName::F);
+    /// f.sharpen();
+    /// let pitches = vec![Pitch::new(NoteName::D), f, Pitch::new(NoteName::C)];
+    ///
+    /// assert_eq!(Chord::new(pitches).quality(), Ok(ChordQuality::DominantSeventh));
+    /// ```
+    pub fn quality(&self) -> Result<ChordQuality, String> {
+        self.stack().map(|(_, quality)| quality)
+    }
+    /// Attempt to find the inversion of the chord: 0 with the root in the
+    /// bass, 1 with the third, 2 with the fifth, and 3 with the seventh.
+    ///
+    /// # Errors
+    ///
+    /// See [`root`][Chord::root].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::chord::Chord;
+    /// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+    ///
+    /// let mut c = Pitch::new(NoteName::C);
+    /// c.octave(Octave::S4);
+    /// let chord = Chord::new(vec![c, Pitch::new(NoteName::G), Pitch::new(NoteName::E)]);
+    ///
+    /// assert_eq!(chord.inversion(), Ok(1));
+    /// ```
+    pub fn inversion(&self) -> Result<u8, String> {
+        let (root, _) = self.stack()?;
+        let steps = (self.bass()?.diatonic_number()? - root.diatonic_number()?).rem_euclid(7);
+        Ok((steps / 2) as u8)
+    }
+    /// Find the root and quality of the chord as a stack of thirds.
+    fn stack(&self) -> Result<(&Pitch, ChordQuality), String> {
+        let bass = self.bass()?;
+        for root in &self.pitches {
+            // semitones above the root of the third, fifth and seventh
+            let mut members: [Option<i32>; 3] = [None; 3];
+            let mut tertian = true;
+            for pitch in &self.pitches {
+                let steps = (pitch.diatonic_number()? - root.diatonic_number()?).rem_euclid(7);
+                let semitones = pitch.semitones()? - root.semitones()?;
+                if !semitones.is_integer() {
+                    return Err(format!("Chord has pitches {} semitones apart.", semitones));
+                }
+                let semitones = semitones.to_integer().rem_euclid(12);
+                let member = match steps {
+                    0 if semitones == 0 => continue,
+                    2 | 4 | 6 => &mut members[(steps / 2 - 1) as usize],
+                    _ => {
+                        tertian = false;
+                        break;
+                    }
+                };
+                if member.is_some_and(|s| s != semitones) {
+                    tertian = false;
+                    break;
+                }
+                *member = Some(semitones);
+            }
+            if !tertian {
+                continue;
+            }
+            if let [Some(third), fifth, seventh] = members {
+                if let Some(quality) = ChordQuality::from_semitones(third, fifth, seventh) {
+                    // the lowest pitch with the root's spelling
+                    let lowest = self
+                        .pitches
+                        .iter()
+                        .filter(|p| {
+                            p.note_name == root.note_name && p.accidental == root.accidental
+                        })
+                        .min_by_key(|p| p.diatonic_number().unwrap_or_default())
+                        .unwrap_or(bass);
+                    return Ok((lowest, quality));
+                }
+            }
+        }
+        Err(String::from("The chord is not a triad or seventh chord."))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::chord::*;
+    use crate::notation::pitch::Accidental::{Flat, None as Natural, QuarterSharp, Sharp};
+    use crate::notation::pitch::{Accidental, NoteName, Octave};
+    fn chord(pitches: &[(NoteName, Accidental, Octave)]) -> Chord {
+        Chord::new(
+            pitches
+                .iter()
+                .map(|(note_name, accidental, octave)| Pitch {
+                    note_name: *note_name,
+                    accidental: *accidental,
+                    octave: *octave,
+                })
+                .collect(),
+        )
+    }
+    #[test]
+    fn test_qualities() {
+        use NoteName::*;
+        use Octave::*;
+        let cases = [
+            (
+                vec![(C, Natural, S3), (E, Natural, S3), (G, Natural, S3)],
+                ChordQuality::Major,
+            ),
+            (
+                vec![(A, Natural, S3), (C, Natural, S4), (E, Natural, S4)],
+                ChordQuality::Minor,
+            ),
+            (
+                vec![(B, Natural, S3), (D, Natural, S4), (F, Natural, S4)],
+                ChordQuality::Diminished,
+            ),
+            (
+                vec![(C, Natural, S3), (E, Natural, S3), (G, Sharp, S3)],
+                ChordQuality::Augmented,
+            ),
+            (
+                vec![(G, Natural, S2), (B, Natural, S3), (F, Natural, S4)],
+                ChordQuality::DominantSeventh,
+            ),
+            (
+                vec![
+                    (F, Natural, S3),
+                    (A, Natural, S3),
+                    (C, Natural, S4),
+                    (E, Natural, S4),
+                ],
+                ChordQuality::MajorSeventh,
+            ),
+            (
+                vec![
+                    (D, Natural, S3),
+                    (F, Natural, S3),
+                    (A, Natural, S3),
+                    (C, Natural, S4),
+                ],
+                ChordQuality::MinorSeventh,
+            ),
+            (
+                vec![
+                    (C, Natural, S3),
+                    (E, Flat, S3),
+                    (G, Natural, S3),
+                    (B, Natural, S3),
+                ],
+                ChordQuality::MinorMajorSeventh,
+            ),
+            (
+                vec![
+                    (B, Natural, S3),
+                    (D, Natural, S4),
+                    (F, Natural, S4),
+                    (A, Natural, S4),
+                ],
+                ChordQuality::HalfDiminishedSeventh,
+            ),
+            (
+                vec![
+                    (G, Sharp, S3),
+                    (B, Natural, S3),
+                    (D, Natural, S4),
+                    (F, Natural, S4),
+                ],
+                ChordQuality::DiminishedSeventh,
+            ),
+        ];
+        for (pitches, quality) in cases {
+            assert_eq!(chord(&pitches).quality(), Ok(quality), "{:?}", pitches);
+        }
+    }
+    #[test]
+    fn test_root_and_inversion() {
+        use NoteName::*;
+        use Octave::*;
+        let chord_64 = chord(&[
+            (G, Natural, S3),
+            (C, Natural, S4),
+            (E, Natural, S4),
+            (C, Natural, S5),
+        ]);
+        assert_eq!(chord_64.root().unwrap().octave, S4);
+        assert_eq!(chord_64.inversion(), Ok(2));
+        let chord_42 = chord(&[
+            (G, Natural, S4),
+            (F, Natural, S3),
+            (B, Natural, S3),
+            (D, Natural, S4),
+        ]);
+        assert_eq!(chord_42.root().unwrap().note_name, G);
+        assert_eq!(chord_42.inversion(), Ok(3));
+        assert_eq!(chord_42.bass().unwrap().note_name, F);
+        // B sharp is spelled as the third of G sharp, not as C
+        let chord_6 = chord(&[(B, Sharp, S3), (D, Sharp, S4), (G, Sharp, S4)]);
+        assert_eq!(chord_6.root().unwrap().note_name, G);
+        assert_eq!(chord_6.quality(), Ok(ChordQuality::Major));
+        assert_eq!(chord_6.inversion(), Ok(1));
+    }
+    #[test]
+    fn test_errors() {
+        use NoteName::*;
+        use Octave::*;
+        let not_tertian = [
+            vec![(C, Natural, S3), (D, Natural, S3), (G, Natural, S3)],
+            vec![(C, Natural, S3), (G, Natural, S3)],
+            vec![(C, Natural, S3), (C, Sharp, S3), (E, Natural, S3)],
+            vec![(C, Natural, S3), (E, Natural, S3), (G, Flat, S3)],
+        ];
+        for pitches in not_tertian {
+            assert_eq!(
+                chord(&pitches).root(),
+                Err(String::from("The chord is not a triad or seventh chord."))
+            );
+        }
+        assert!(Chord::new(vec![]).root().is_err());
+        assert!(
+            chord(&[(C, Natural, S3), (NoteName::None, Natural, Octave::None)])
+                .root()
+                .is_err()
+        );
+        assert!(chord(&[(C, Natural, S3), (E, QuarterSharp, S3)])
+            .root()
+            .is_err());
+    }
+}
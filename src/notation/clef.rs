@@ -0,0 +1,138 @@
+//! Clefs, which fix the pitches of the lines of a staff.
+
+/// Clefs, named like LilyPond's `\clef treble`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Clef {
+    Treble,
+    /// A treble clef sounding an octave lower, like LilyPond's `treble_8`.
+    TrebleOctaveDown,
+    Soprano,
+    Alto,
+    Tenor,
+    Bass,
+    Percussion,
+}
+
+static CLEFS: [Clef; 7] = [
+    Clef::Treble,
+    Clef::TrebleOctaveDown,
+    Clef::Soprano,
+    Clef::Alto,
+    Clef::Tenor,
+    Clef::Bass,
+    Clef::Percussion,
+];
+
+impl Default for Clef {
+    /// Default to the treble clef.
+    fn default() -> Self {
+        Clef::Treble
+    }
+}
+
+impl Clef {
+    /// Return the LilyPond name of the clef, e.g. `treble`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Clef::Treble => "treble",
+            Clef::TrebleOctaveDown => "treble_8",
+            Clef::Soprano => "soprano",
+            Clef::Alto => "alto",
+            Clef::Tenor => "tenor",
+            Clef::Bass => "bass",
+            Clef::Percussion => "percussion",
+        }
+    }
+    /// Find the clef with a LilyPond name, e.g. `bass`.
+    pub fn from_name(name: &str) -> Option<Clef> {
+        CLEFS.iter().find(|clef| clef.name() == name).cloned()
+    }
+    /// Return the sign of the clef: `G`, `C` or `F`, or `percussion`.
+    pub fn sign(&self) -> &'static str {
+        match self {
+            Clef::Treble | Clef::TrebleOctaveDown => "G",
+            Clef::Soprano | Clef::Alto | Clef::Tenor => "C",
+            Clef::Bass => "F",
+            Clef::Percussion => "percussion",
+        }
+    }
+    /// Return the staff line the clef's sign is on, counting from the
+    /// bottom line as 1.
+    pub fn line(&self) -> u8 {
+        match self {
+            Clef::Treble | Clef::TrebleOctaveDown => 2,
+            Clef::Soprano => 1,
+            Clef::Alto | Clef::Percussion => 3,
+            Clef::Tenor | Clef::Bass => 4,
+        }
+    }
+    /// Return the number of octaves the clef transposes the written pitches,
+    /// e.g. `-1` for a treble clef sounding an octave lower.
+    pub fn octave_change(&self) -> i32 {
+        match self {
+            Clef::TrebleOctaveDown => -1,
+            _ => 0,
+        }
+    }
+    /// Find the clef with a sign on a line and an octave change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::clef::Clef;
+    ///
+    /// assert_eq!(Clef::from_sign("F", 4, 0), Some(Clef::Bass));
+    /// assert_eq!(Clef::from_sign("G", 2, -1), Some(Clef::TrebleOctaveDown));
+    /// assert_eq!(Clef::from_sign("F", 3, 0), None);
+    /// ```
+    pub fn from_sign(sign: &str, line: u8, octave_change: i32) -> Option<Clef> {
+        CLEFS
+            .iter()
+            .find(|clef| {
+                clef.sign() == sign
+                    && (clef.line() == line || sign == "percussion")
+                    && clef.octave_change() == octave_change
+            })
+            .cloned()
+    }
+}
+
+impl std::fmt::Display for Clef {
+    /// Write the clef as LilyPond, e.g. `\clef bass`, quoting names that are
+    /// not plain words, e.g. `\clef "treble_8"`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.name().chars().all(char::is_alphabetic) {
+            write!(f, "\\clef {}", self.name())
+        } else {
+            write!(f, "\\clef \"{}\"", self.name())
+        }
+    }
+}
+
+impl std::str::FromStr for Clef {
+    type Err = String;
+
+    /// Parse a clef's LilyPond name, e.g. `alto`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Clef::from_name(s).ok_or_else(|| format!("Invalid clef '{}'.", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::clef::*;
+    #[test]
+    fn test_parse_display() {
+        for clef in &CLEFS {
+            assert_eq!(clef.name().parse(), Ok(*clef));
+            assert_eq!(
+                Clef::from_sign(clef.sign(), clef.line(), clef.octave_change()),
+                Some(*clef)
+            );
+        }
+        assert_eq!(Clef::Alto.to_string(), "\\clef alto");
+        assert_eq!(Clef::TrebleOctaveDown.to_string(), "\\clef \"treble_8\"");
+        assert!("violin".parse::<Clef>().is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! Dynamic marks attached to notes and chords.
+
+/// Dynamic marks, written after a note like LilyPond's `c4\mf`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dynamic {
+    PPP,
+    PP,
+    P,
+    MP,
+    MF,
+    F,
+    FF,
+    FFF,
+    FP,
+    SF,
+    SFZ,
+}
+
+static DYNAMICS: [Dynamic; 11] = [
+    Dynamic::PPP,
+    Dynamic::PP,
+    Dynamic::P,
+    Dynamic::MP,
+    Dynamic::MF,
+    Dynamic::F,
+    Dynamic::FF,
+    Dynamic::FFF,
+    Dynamic::FP,
+    Dynamic::SF,
+    Dynamic::SFZ,
+];
+
+impl Dynamic {
+    /// Return the name of the dynamic, e.g. `mf`, which is also the name of
+    /// its LilyPond command.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dynamic::PPP => "ppp",
+            Dynamic::PP => "pp",
+            Dynamic::P => "p",
+            Dynamic::MP => "mp",
+            Dynamic::MF => "mf",
+            Dynamic::F => "f",
+            Dynamic::FF => "ff",
+            Dynamic::FFF => "fff",
+            Dynamic::FP => "fp",
+            Dynamic::SF => "sf",
+            Dynamic::SFZ => "sfz",
+        }
+    }
+    /// Find the dynamic with a name, e.g. `pp`.
+    pub fn from_name(name: &str) -> Option<Dynamic> {
+        DYNAMICS
+            .iter()
+            .find(|dynamic| dynamic.name() == name)
+            .cloned()
+    }
+}
+
+impl std::fmt::Display for Dynamic {
+    /// Write the dynamic as a LilyPond command, e.g. `\mf`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\\{}", self.name())
+    }
+}
+
+impl std::str::FromStr for Dynamic {
+    type Err = String;
+
+    /// Parse a dynamic's name or LilyPond command, e.g. `mf` or `\mf`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Dynamic::from_name(s.strip_prefix('\\').unwrap_or(s))
+            .ok_or_else(|| format!("Invalid dynamic '{}'.", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::dynamic::*;
+    #[test]
+    fn test_parse_display() {
+        for dynamic in &DYNAMICS {
+            assert_eq!(dynamic.to_string().parse(), Ok(*dynamic));
+            assert_eq!(dynamic.name().parse(), Ok(*dynamic));
+        }
+        assert_eq!(Dynamic::MF.to_string(), "\\mf");
+        assert!("\\mff".parse::<Dynamic>().is_err());
+    }
+}
@@ -0,0 +1,669 @@
+//! Abstractions for the distance between two pitches.
+
+use std::convert::TryFrom;
+use std::ops::{Add, Sub};
+
+use crate::notation::pitch::{Alteration, Pitch};
+
+/// The number of semitones above the lower note of each simple major or
+/// perfect interval, from the unison to the seventh.
+static SIMPLE_SEMITONES: [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// Interval qualities.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quality {
+    /// Unisons, fourths, fifths, octaves and their compounds.
+    Perfect,
+    /// Seconds, thirds, sixths, sevenths and their compounds.
+    Major,
+    /// A semitone smaller than major.
+    Minor,
+    /// The given number of semitones larger than perfect or major, e.g.
+    /// `Augmented(2)` for a doubly augmented interval.
+    Augmented(u8),
+    /// The given number of semitones smaller than perfect or minor, e.g.
+    /// `Diminished(2)` for a doubly diminished interval.
+    Diminished(u8),
+}
+
+impl std::fmt::Display for Quality {
+    /// Write the quality as its usual abbreviation, e.g. `M`, `m`, `P`, `AA`,
+    /// or `d`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Quality::Perfect => write!(f, "P"),
+            Quality::Major => write!(f, "M"),
+            Quality::Minor => write!(f, "m"),
+            Quality::Augmented(n) => write!(f, "{}", "A".repeat((*n).into())),
+            Quality::Diminished(n) => write!(f, "{}", "d".repeat((*n).into())),
+        }
+    }
+}
+
+/// An interval between two pitches.
+///
+/// Intervals have a [`Quality`] and a generic size (`number`), so they keep
+/// their spelling: an augmented second and a minor third are different
+/// intervals with the same number of semitones. Intervals have no direction;
+/// add them to a pitch to go up, and subtract them to go down.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Interval {
+    quality: Quality,
+    number: u8,
+}
+
+/// Check whether intervals with the generic size `number` are perfect rather
+/// than major or minor.
+fn is_perfect_number(number: u8) -> bool {
+    matches!((number - 1) % 7, 0 | 3 | 4)
+}
+
+impl Interval {
+    /// Attempt to construct a new `Interval` with a quality and a generic
+    /// size, e.g. 3 for a third or 9 for a ninth.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if `number` is 0, if the quality does not exist
+    /// for the size (e.g. a perfect third or a major fifth), or if the
+    /// quality is augmented or diminished by 0 semitones.
+    ///
+    /// # Examples
+    ///
+    /// A successful initialization:
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    ///
+    /// let interval = Interval::new(Quality::Minor, 3).unwrap();
+    /// assert_eq!(interval.to_string(), "m3");
+    /// ```
+    ///
+    /// An unsuccessful initialization:
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    ///
+    /// let interval = Interval::new(Quality::Perfect, 3);
+    /// assert_eq!(interval, Err(String::from("Invalid interval P3.")));
+    /// ```
+    pub fn new(quality: Quality, number: u8) -> Result<Interval, String> {
+        if number == 0 {
+            return Err(String::from("Invalid interval number 0."));
+        }
+        let valid = match quality {
+            Quality::Perfect => is_perfect_number(number),
+            Quality::Major | Quality::Minor => !is_perfect_number(number),
+            Quality::Augmented(n) | Quality::Diminished(n) => n > 0,
+        };
+        if valid {
+            Ok(Interval { quality, number })
+        } else {
+            Err(format!("Invalid interval {}{}.", quality, number))
+        }
+    }
+    /// Return the quality of the interval.
+    pub fn quality(&self) -> Quality {
+        self.quality
+    }
+    /// Return the generic size of the interval, e.g. 3 for a third.
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+    /// Attempt to find the interval between two pitches.
+    ///
+    /// The interval is measured from the lower to the higher pitch, so the
+    /// order of the pitches does not matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if either pitch is a rest, if the pitches are
+    /// not a whole number of semitones apart, or if the interval is too
+    /// large.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    /// use lilypond::notation::pitch::{NoteName, Pitch};
+    ///
+    /// let mut lower = Pitch::new(NoteName::E);
+    /// lower.flatten();
+    /// let upper = Pitch::new(NoteName::A);
+    ///
+    /// let interval = Interval::between(&lower, &upper).unwrap();
+    /// assert_eq!(interval, Interval::new(Quality::Augmented(1), 4).unwrap());
+    /// assert_eq!(Interval::between(&upper, &lower), Ok(interval));
+    /// ```
+    pub fn between(a: &Pitch, b: &Pitch) -> Result<Interval, String> {
+        let (a_steps, b_steps) = (a.diatonic_number()?, b.diatonic_number()?);
+        let (a_semitones, b_semitones) = (a.semitones()?, b.semitones()?);
+        let (steps, semitones) = if (b_steps, b_semitones) < (a_steps, a_semitones) {
+            (a_steps - b_steps, a_semitones - b_semitones)
+        } else {
+            (b_steps - a_steps, b_semitones - a_semitones)
+        };
+        if !semitones.is_integer() {
+            return Err(format!(
+                "Pitches {} semitones apart have no interval.",
+                semitones
+            ));
+        }
+        let number = u8::try_from(steps + 1)
+            .map_err(|_| format!("Interval of {} steps is too large.", steps))?;
+        let major = steps / 7 * 12 + SIMPLE_SEMITONES[(steps % 7) as usize];
+        let difference = semitones.to_integer() - major;
+        let size = |n: i32| {
+            u8::try_from(n)
+                .map_err(|_| format!("Interval of {} semitones is too large.", semitones))
+        };
+        let quality = match (is_perfect_number(number), difference) {
+            (_, d) if d > 0 => Quality::Augmented(size(d)?),
+            (true, 0) => Quality::Perfect,
+            (true, d) => Quality::Diminished(size(-d)?),
+            (false, 0) => Quality::Major,
+            (false, -1) => Quality::Minor,
+            (false, d) => Quality::Diminished(size(-d - 1)?),
+        };
+        Interval::new(quality, number)
+    }
+    /// Return the conventionally spelled interval spanning a number of
+    /// semitones, preferring an augmented fourth to a diminished fifth.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::Interval;
+    ///
+    /// assert_eq!(Interval::from_semitones(3).to_string(), "m3");
+    /// assert_eq!(Interval::from_semitones(6).to_string(), "A4");
+    /// assert_eq!(Interval::from_semitones(19).to_string(), "P12");
+    /// ```
+    pub fn from_semitones(semitones: u8) -> Interval {
+        let (quality, number) = match semitones % 12 {
+            0 => (Quality::Perfect, 1),
+            1 => (Quality::Minor, 2),
+            2 => (Quality::Major, 2),
+            3 => (Quality::Minor, 3),
+            4 => (Quality::Major, 3),
+            5 => (Quality::Perfect, 4),
+            6 => (Quality::Augmented(1), 4),
+            7 => (Quality::Perfect, 5),
+            8 => (Quality::Minor, 6),
+            9 => (Quality::Major, 6),
+            10 => (Quality::Minor, 7),
+            _ => (Quality::Major, 7),
+        };
+        Interval {
+            quality,
+            number: number + semitones / 12 * 7,
+        }
+    }
+    /// Return the number of semitones the interval spans.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    ///
+    /// assert_eq!(Interval::new(Quality::Major, 3).unwrap().semitones(), 4);
+    /// assert_eq!(Interval::new(Quality::Diminished(1), 7).unwrap().semitones(), 9);
+    /// assert_eq!(Interval::new(Quality::Perfect, 15).unwrap().semitones(), 24);
+    /// ```
+    pub fn semitones(&self) -> i32 {
+        let steps = i32::from(self.number) - 1;
+        let major = steps / 7 * 12 + SIMPLE_SEMITONES[(steps % 7) as usize];
+        let perfect = is_perfect_number(self.number);
+        major
+            + match self.quality {
+                Quality::Perfect | Quality::Major => 0,
+                Quality::Minor => -1,
+                Quality::Augmented(n) => i32::from(n),
+                Quality::Diminished(n) if perfect => -i32::from(n),
+                Quality::Diminished(n) => -i32::from(n) - 1,
+            }
+    }
+    /// Check whether the interval is larger than an octave.
+    pub fn is_compound(&self) -> bool {
+        self.number > 8
+    }
+    /// Return the number of whole octaves the interval spans beyond its
+    /// [simple](Interval::simple) interval.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    ///
+    /// assert_eq!(Interval::new(Quality::Major, 10).unwrap().octaves(), 1);
+    /// assert_eq!(Interval::new(Quality::Perfect, 8).unwrap().octaves(), 0);
+    /// assert_eq!(Interval::new(Quality::Perfect, 1).unwrap().octaves(), 0);
+    /// ```
+    pub fn octaves(&self) -> u8 {
+        self.number.saturating_sub(2) / 7
+    }
+    /// Return the interval reduced by octaves to at most an octave, e.g. a
+    /// major third for a major tenth.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    ///
+    /// let tenth = Interval::new(Quality::Major, 10).unwrap();
+    /// assert_eq!(tenth.simple(), Interval::new(Quality::Major, 3).unwrap());
+    /// ```
+    pub fn simple(&self) -> Interval {
+        if self.number <= 8 {
+            return *self;
+        }
+        Interval {
+            quality: self.quality,
+            number: self.number - self.octaves() * 7,
+        }
+    }
+    /// Return the interval expanded by a number of octaves.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the interval would be too large.
+    pub fn compound(&self, octaves: u8) -> Result<Interval, String> {
+        octaves
+            .checked_mul(7)
+            .and_then(|steps| self.number.checked_add(steps))
+            .map(|number| Interval {
+                quality: self.quality,
+                number,
+            })
+            .ok_or_else(|| format!("Interval {} plus {} octaves is too large.", self, octaves))
+    }
+    /// Return the inversion of the [simple](Interval::simple) interval, e.g.
+    /// a minor sixth for a major third.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    ///
+    /// let fourth = Interval::new(Quality::Augmented(1), 4).unwrap();
+    /// assert_eq!(fourth.invert(), Interval::new(Quality::Diminished(1), 5).unwrap());
+    /// ```
+    pub fn invert(&self) -> Interval {
+        let simple = self.simple();
+        Interval {
+            quality: match simple.quality {
+                Quality::Perfect => Quality::Perfect,
+                Quality::Major => Quality::Minor,
+                Quality::Minor => Quality::Major,
+                Quality::Augmented(n) => Quality::Diminished(n),
+                Quality::Diminished(n) => Quality::Augmented(n),
+            },
+            number: 9 - simple.number,
+        }
+    }
+    /// Transpose a pitch by the interval, up if `up` is true and down
+    /// otherwise.
+    fn transpose(&self, pitch: &Pitch, up: bool) -> Result<Pitch, String> {
+        let (steps, semitones) = if up {
+            (i32::from(self.number) - 1, self.semitones())
+        } else {
+            (1 - i32::from(self.number), -self.semitones())
+        };
+        Pitch::from_diatonic_number(
+            pitch.diatonic_number()? + steps,
+            pitch.semitones()? + Alteration::from_integer(semitones),
+        )
+    }
+}
+
+impl std::fmt::Display for Interval {
+    /// Write the interval as its quality abbreviation and number, e.g. `m3`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.quality, self.number)
+    }
+}
+
+impl std::str::FromStr for Interval {
+    type Err = String;
+
+    /// Attempt to parse an interval from its quality abbreviation and number,
+    /// e.g. `M3`, `P5`, `m7`, `A4` or `dd7`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the string is not a valid interval.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::{Interval, Quality};
+    ///
+    /// let interval: Interval = "AA4".parse().unwrap();
+    /// assert_eq!(interval.quality(), Quality::Augmented(2));
+    /// assert_eq!(interval.number(), 4);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid interval \"{}\".", s);
+        let split = s.find(|c: char| c.is_ascii_digit()).ok_or_else(error)?;
+        let (quality, number) = s.split_at(split);
+        let count = || u8::try_from(quality.len()).map_err(|_| error());
+        let quality = match quality {
+            "P" => Quality::Perfect,
+            "M" => Quality::Major,
+            "m" => Quality::Minor,
+            q if !q.is_empty() && q.chars().all(|c| c == 'A') => Quality::Augmented(count()?),
+            q if !q.is_empty() && q.chars().all(|c| c == 'd') => Quality::Diminished(count()?),
+            _ => return Err(error()),
+        };
+        Interval::new(quality, number.parse().map_err(|_| error())?)
+    }
+}
+
+impl std::convert::TryFrom<String> for Interval {
+    type Error = String;
+
+    /// Attempt to parse an interval, as with its [`FromStr`](std::str::FromStr)
+    /// implementation.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Interval> for String {
+    fn from(interval: Interval) -> Self {
+        interval.to_string()
+    }
+}
+
+impl Add<&Interval> for &Pitch {
+    type Output = Result<Pitch, String>;
+
+    /// Attempt to transpose a pitch up by an interval, spelling the result
+    /// with the note name the interval's number calls for.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` for rests, and if the result is outside of the
+    /// octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::Interval;
+    /// use lilypond::notation::pitch::{Accidental, NoteName, Pitch};
+    ///
+    /// let minor_third: Interval = "m3".parse().unwrap();
+    /// let pitch = (&Pitch::new(NoteName::E) + &minor_third).unwrap();
+    ///
+    /// assert_eq!(pitch.note_name, NoteName::G);
+    /// assert_eq!(pitch.accidental, Accidental::None);
+    /// ```
+    fn add(self, interval: &Interval) -> Self::Output {
+        interval.transpose(self, true)
+    }
+}
+
+impl Add<Interval> for Pitch {
+    type Output = Result<Pitch, String>;
+
+    /// See the implementation for references.
+    fn add(self, interval: Interval) -> Self::Output {
+        &self + &interval
+    }
+}
+
+impl Sub<&Interval> for &Pitch {
+    type Output = Result<Pitch, String>;
+
+    /// Attempt to transpose a pitch down by an interval, spelling the result
+    /// with the note name the interval's number calls for.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` for rests, and if the result is outside of the
+    /// octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::Interval;
+    /// use lilypond::notation::pitch::{Accidental, NoteName, Octave, Pitch};
+    ///
+    /// let major_second: Interval = "M2".parse().unwrap();
+    /// let pitch = (&Pitch::new(NoteName::C) - &major_second).unwrap();
+    ///
+    /// assert_eq!(pitch.note_name, NoteName::B);
+    /// assert_eq!(pitch.octave, Octave::S2);
+    /// assert_eq!(pitch.accidental, Accidental::Flat);
+    /// ```
+    fn sub(self, interval: &Interval) -> Self::Output {
+        interval.transpose(self, false)
+    }
+}
+
+impl Sub<Interval> for Pitch {
+    type Output = Result<Pitch, String>;
+
+    /// See the implementation for references.
+    fn sub(self, interval: Interval) -> Self::Output {
+        &self - &interval
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::interval::*;
+    use crate::notation::pitch::{Accidental, NoteName, Octave};
+    fn pitch(note_name: NoteName, accidental: Accidental, octave: Octave) -> Pitch {
+        Pitch {
+            note_name,
+            accidental,
+            octave,
+        }
+    }
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+    #[test]
+    fn test_new() {
+        assert!(Interval::new(Quality::Perfect, 1).is_ok());
+        assert!(Interval::new(Quality::Perfect, 11).is_ok());
+        assert!(Interval::new(Quality::Major, 9).is_ok());
+        assert!(Interval::new(Quality::Major, 8).is_err());
+        assert!(Interval::new(Quality::Minor, 5).is_err());
+        assert!(Interval::new(Quality::Augmented(0), 5).is_err());
+        assert!(Interval::new(Quality::Diminished(1), 0).is_err());
+    }
+    #[test]
+    fn test_between() {
+        let cases = [
+            (
+                NoteName::C,
+                Accidental::None,
+                NoteName::E,
+                Accidental::None,
+                "M3",
+            ),
+            (
+                NoteName::E,
+                Accidental::None,
+                NoteName::G,
+                Accidental::None,
+                "m3",
+            ),
+            (
+                NoteName::C,
+                Accidental::None,
+                NoteName::D,
+                Accidental::Sharp,
+                "A2",
+            ),
+            (
+                NoteName::C,
+                Accidental::Sharp,
+                NoteName::E,
+                Accidental::Flat,
+                "d3",
+            ),
+            (
+                NoteName::F,
+                Accidental::None,
+                NoteName::B,
+                Accidental::None,
+                "A4",
+            ),
+            (
+                NoteName::E,
+                Accidental::None,
+                NoteName::B,
+                Accidental::Flat,
+                "d5",
+            ),
+            (
+                NoteName::C,
+                Accidental::None,
+                NoteName::C,
+                Accidental::Sharp,
+                "A1",
+            ),
+            (
+                NoteName::C,
+                Accidental::Flat,
+                NoteName::G,
+                Accidental::Sharp,
+                "AA5",
+            ),
+            (
+                NoteName::C,
+                Accidental::Sharp,
+                NoteName::B,
+                Accidental::DoubleFlat,
+                "dd7",
+            ),
+            (
+                NoteName::D,
+                Accidental::None,
+                NoteName::C,
+                Accidental::None,
+                "M2",
+            ),
+        ];
+        for (a_name, a_accidental, b_name, b_accidental, expected) in cases {
+            let a = pitch(a_name, a_accidental, Octave::S4);
+            let b = pitch(b_name, b_accidental, Octave::S4);
+            assert_eq!(Interval::between(&a, &b).unwrap().to_string(), expected);
+        }
+    }
+    #[test]
+    fn test_between_compound() {
+        let a = pitch(NoteName::C, Accidental::None, Octave::S3);
+        let b = pitch(NoteName::E, Accidental::Flat, Octave::S5);
+        assert_eq!(Interval::between(&a, &b), Ok(interval("m17")));
+        let b = pitch(NoteName::B, Accidental::None, Octave::S2);
+        let c = pitch(NoteName::C, Accidental::Flat, Octave::S3);
+        assert_eq!(Interval::between(&b, &c), Ok(interval("d2")));
+    }
+    #[test]
+    fn test_between_errors() {
+        let a = pitch(NoteName::C, Accidental::None, Octave::S3);
+        let b = pitch(NoteName::D, Accidental::QuarterSharp, Octave::S3);
+        assert!(Interval::between(&a, &b).is_err());
+        let rest = pitch(NoteName::None, Accidental::None, Octave::None);
+        assert!(Interval::between(&a, &rest).is_err());
+    }
+    #[test]
+    fn test_semitones() {
+        let cases = [
+            ("P1", 0),
+            ("m2", 1),
+            ("A1", 1),
+            ("d3", 2),
+            ("A4", 6),
+            ("d5", 6),
+            ("P8", 12),
+            ("M9", 14),
+            ("dd4", 3),
+        ];
+        for (s, semitones) in cases {
+            assert_eq!(interval(s).semitones(), semitones, "{}", s);
+        }
+    }
+    #[test]
+    fn test_from_semitones() {
+        for semitones in 0..=48 {
+            assert_eq!(
+                Interval::from_semitones(semitones).semitones(),
+                i32::from(semitones)
+            );
+        }
+    }
+    #[test]
+    fn test_compound() {
+        assert!(!interval("P8").is_compound());
+        assert!(interval("m9").is_compound());
+        assert_eq!(interval("P15").simple(), interval("P8"));
+        assert_eq!(interval("A11").simple(), interval("A4"));
+        assert_eq!(interval("m3").compound(2), Ok(interval("m17")));
+        assert!(interval("m3").compound(100).is_err());
+        assert_eq!(interval("M17").octaves(), 2);
+        assert_eq!(interval("P1").octaves(), 0);
+        assert_eq!(interval("A1").octaves(), 0);
+        assert_eq!(interval("A1").simple(), interval("A1"));
+    }
+    #[test]
+    fn test_invert() {
+        assert_eq!(interval("P1").invert(), interval("P8"));
+        assert_eq!(interval("m2").invert(), interval("M7"));
+        assert_eq!(interval("dd5").invert(), interval("AA4"));
+        assert_eq!(interval("M10").invert(), interval("m6"));
+    }
+    #[test]
+    fn test_from_str_errors() {
+        for s in ["", "3", "M", "X3", "P3", "Ad4", "M0"] {
+            assert!(s.parse::<Interval>().is_err(), "{}", s);
+        }
+    }
+    #[test]
+    fn test_add() {
+        let e = pitch(NoteName::E, Accidental::None, Octave::S4);
+        let g = (&e + &interval("m3")).unwrap();
+        assert_eq!(g, pitch(NoteName::G, Accidental::None, Octave::S4));
+        let b = pitch(NoteName::B, Accidental::Flat, Octave::S4);
+        let c = (&b + &interval("A2")).unwrap();
+        assert_eq!(c, pitch(NoteName::C, Accidental::Sharp, Octave::S5));
+        let f = pitch(NoteName::F, Accidental::Sharp, Octave::S3);
+        let e = (f + interval("M14")).unwrap();
+        assert_eq!(e, pitch(NoteName::E, Accidental::Sharp, Octave::S5));
+        let quarter = pitch(NoteName::C, Accidental::QuarterSharp, Octave::S4);
+        let e = (&quarter + &interval("M3")).unwrap();
+        assert_eq!(e, pitch(NoteName::E, Accidental::QuarterSharp, Octave::S4));
+    }
+    #[test]
+    fn test_sub() {
+        let g = pitch(NoteName::G, Accidental::None, Octave::S4);
+        let c = (&g - &interval("P12")).unwrap();
+        assert_eq!(c, pitch(NoteName::C, Accidental::None, Octave::S3));
+        let e = pitch(NoteName::E, Accidental::Flat, Octave::S4);
+        let b = (&e - &interval("A4")).unwrap();
+        assert_eq!(b, pitch(NoteName::B, Accidental::DoubleFlat, Octave::S3));
+    }
+    #[test]
+    fn test_add_errors() {
+        let high = pitch(NoteName::B, Accidental::None, Octave::S9);
+        assert!((&high + &interval("m2")).is_err());
+        let rest = pitch(NoteName::None, Accidental::None, Octave::None);
+        assert!((&rest + &interval("m2")).is_err());
+    }
+    #[test]
+    fn test_round_trip() {
+        let c = pitch(NoteName::C, Accidental::None, Octave::S4);
+        for s in ["d2", "m2", "M3", "A4", "d5", "M6", "d7", "P8", "A9", "m13"] {
+            let upper = (&c + &interval(s)).unwrap();
+            assert_eq!(Interval::between(&c, &upper), Ok(interval(s)));
+            assert_eq!((&upper - &interval(s)), Ok(c.clone()));
+        }
+    }
+}
@@ -1,8 +1,14 @@
 //! Types to represent key signatures.
 
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 
-#[derive(PartialEq, Debug)]
+use crate::notation::pitch::{Accidental, Alteration, NoteName, Pitch};
+use crate::notation::scale::{Scale, ScaleType};
+use crate::NoteNameLanguage;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     /// A key signature may either have up to 7 sharps,
     Sharps(u8),
@@ -19,11 +25,20 @@ impl Default for Key {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Key", into = "Key"))]
 pub struct KeySignature {
     key: Key,
 }
 
+impl Default for KeySignature {
+    /// Default to no sharps or flats
+    fn default() -> Self {
+        KeySignature { key: Key::None }
+    }
+}
+
 /// Check that NUM is valid number of accidentals in key signature.
 ///
 /// # Errors
@@ -38,6 +53,22 @@ fn check_num_accidentals(num: u8) -> Result<u8, u8> {
     }
 }
 
+impl TryFrom<Key> for KeySignature {
+    type Error = String;
+
+    /// Attempt to convert a [`Key`] to a `KeySignature`, as with
+    /// [`KeySignature::new`].
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        Self::new(key)
+    }
+}
+
+impl From<KeySignature> for Key {
+    fn from(key_signature: KeySignature) -> Self {
+        key_signature.key
+    }
+}
+
 impl KeySignature {
     /// Attempt to construct a new `KeySignature` with the input [`Key`].
     ///
@@ -80,6 +111,220 @@ impl KeySignature {
     pub fn get_key(&self) -> &Key {
         &self.key
     }
+    /// Attempt to construct the key signature a number of fifths from C
+    /// major on the circle of fifths: sharps if `fifths` is positive, and
+    /// flats if it is negative.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if there would be more than 7 sharps or flats.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::key::{Key, KeySignature};
+    ///
+    /// let key = KeySignature::from_fifths(-3).unwrap();
+    /// assert_eq!(key.get_key(), &Key::Flats(3));
+    /// assert_eq!(key.fifths(), -3);
+    /// ```
+    pub fn from_fifths(fifths: i32) -> Result<KeySignature, String> {
+        let count = |n: i32| u8::try_from(n.abs()).unwrap_or(u8::MAX);
+        KeySignature::new(match fifths.cmp(&0) {
+            Ordering::Greater => Key::Sharps(count(fifths)),
+            Ordering::Less => Key::Flats(count(fifths)),
+            Ordering::Equal => Key::None,
+        })
+    }
+    /// Return the number of fifths from C major on the circle of fifths: the
+    /// number of sharps, or minus the number of flats.
+    pub fn fifths(&self) -> i32 {
+        match self.key {
+            Key::Sharps(n) => i32::from(n),
+            Key::Flats(n) => -i32::from(n),
+            Key::None => 0,
+        }
+    }
+    /// Return the alteration the key signature gives a note name, e.g. `-1`
+    /// for B in F major.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::key::{Key, KeySignature};
+    /// use lilypond::notation::pitch::{Alteration, NoteName};
+    ///
+    /// let key = KeySignature::new(Key::Flats(1)).unwrap();
+    ///
+    /// assert_eq!(key.alteration(NoteName::B), Alteration::from_integer(-1));
+    /// assert_eq!(key.alteration(NoteName::E), Alteration::from_integer(0));
+    /// ```
+    pub fn alteration(&self, note_name: NoteName) -> Alteration {
+        // note names in the order sharps are added to a key signature
+        let order = [
+            NoteName::F,
+            NoteName::C,
+            NoteName::G,
+            NoteName::D,
+            NoteName::A,
+            NoteName::E,
+            NoteName::B,
+        ];
+        let position = match order.iter().position(|name| *name == note_name) {
+            Some(position) => position as i32,
+            None => return Alteration::from_integer(0),
+        };
+        let fifths = self.fifths();
+        if position < fifths {
+            Alteration::from_integer(1)
+        } else if 6 - position < -fifths {
+            Alteration::from_integer(-1)
+        } else {
+            Alteration::from_integer(0)
+        }
+    }
+    /// Return the tonic of the major key with this key signature, e.g. B flat
+    /// for two flats.
+    pub fn major_tonic(&self) -> Pitch {
+        let note_name = NoteName::from_diatonic_index(4 * self.fifths());
+        let mut tonic = Pitch::new(note_name);
+        tonic.accidental(Accidental::from_alteration(self.alteration(note_name)));
+        tonic
+    }
+    /// Write the key signature as a LilyPond `\key` command for its major
+    /// key, with the tonic named in `language`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::key::{Key, KeySignature};
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let key = KeySignature::new(Key::Sharps(6)).unwrap();
+    ///
+    /// assert_eq!(key.to_lilypond(NoteNameLanguage::English), "\\key fs \\major");
+    /// assert_eq!(key.to_lilypond(NoteNameLanguage::Deutsch), "\\key fis \\major");
+    /// ```
+    pub fn to_lilypond(&self, language: NoteNameLanguage) -> String {
+        format!(
+            "\\key {} \\major",
+            language.lilypond_from_pitch(&self.major_tonic())
+        )
+    }
+}
+
+/// The modes of a tonal key.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mode {
+    Major,
+    Minor,
+}
+
+/// A key with a tonic and a mode, e.g. E flat major.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TonalKey {
+    tonic: Pitch,
+    mode: Mode,
+}
+
+impl TonalKey {
+    /// Attempt to construct a new `TonalKey` with a tonic and a mode.
+    ///
+    /// Only the note name and accidental of the tonic matter; its octave is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the tonic is a rest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::key::{Mode, TonalKey};
+    /// use lilypond::notation::pitch::{NoteName, Pitch};
+    ///
+    /// let key = TonalKey::new(&Pitch::new(NoteName::A), Mode::Minor).unwrap();
+    /// assert_eq!(key.mode(), Mode::Minor);
+    /// ```
+    pub fn new(tonic: &Pitch, mode: Mode) -> Result<TonalKey, String> {
+        tonic.diatonic_number()?;
+        Ok(TonalKey {
+            tonic: tonic.clone(),
+            mode,
+        })
+    }
+    /// Return the tonic of the key.
+    pub fn tonic(&self) -> &Pitch {
+        &self.tonic
+    }
+    /// Return the mode of the key.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+    /// Return the scale of the key: the major scale for major keys, and the
+    /// natural minor scale for minor keys.
+    pub fn scale(&self) -> Scale {
+        let scale_type = match self.mode {
+            Mode::Major => ScaleType::Major,
+            Mode::Minor => ScaleType::NaturalMinor,
+        };
+        Scale::new(&self.tonic, scale_type).expect("the tonic is a pitch")
+    }
+    /// Return the key with the same tonic and the other mode, e.g. C minor
+    /// for C major.
+    pub fn parallel(&self) -> TonalKey {
+        TonalKey {
+            tonic: self.tonic.clone(),
+            mode: match self.mode {
+                Mode::Major => Mode::Minor,
+                Mode::Minor => Mode::Major,
+            },
+        }
+    }
+    /// Attempt to find the key signature of the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the key signature would need more than 7
+    /// sharps or flats, or if the tonic has an alteration that is not a
+    /// whole number of semitones.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::key::{Key, Mode, TonalKey};
+    /// use lilypond::notation::pitch::{NoteName, Pitch};
+    ///
+    /// let mut tonic = Pitch::new(NoteName::C);
+    /// tonic.sharpen();
+    /// let key = TonalKey::new(&tonic, Mode::Minor).unwrap();
+    ///
+    /// assert_eq!(key.signature().unwrap().get_key(), &Key::Sharps(4));
+    /// ```
+    pub fn signature(&self) -> Result<KeySignature, String> {
+        let alteration = self.tonic.accidental.alteration();
+        if !alteration.is_integer() {
+            return Err(format!("Invalid key tonic alteration {}.", alteration));
+        }
+        // position of the natural note name on the circle of fifths from C
+        let natural: i32 = match self.tonic.note_name {
+            NoteName::F => -1,
+            NoteName::C => 0,
+            NoteName::G => 1,
+            NoteName::D => 2,
+            NoteName::A => 3,
+            NoteName::E => 4,
+            _ => 5,
+        };
+        let fifths = natural + 7 * alteration.to_integer()
+            - match self.mode {
+                Mode::Major => 0,
+                Mode::Minor => 3,
+            };
+        KeySignature::from_fifths(fifths)
+    }
 }
 
 #[cfg(test)]
@@ -108,4 +353,64 @@ mod tests {
     fn test_new_error() {
         let key = KeySignature::new(Key::Sharps(8)).unwrap();
     }
+    #[test]
+    fn test_signature() {
+        use crate::notation::pitch::Accidental;
+        let cases = [
+            (NoteName::C, Accidental::None, Mode::Major, Key::None),
+            (NoteName::A, Accidental::None, Mode::Minor, Key::None),
+            (NoteName::B, Accidental::None, Mode::Major, Key::Sharps(5)),
+            (NoteName::F, Accidental::None, Mode::Minor, Key::Flats(4)),
+            (NoteName::E, Accidental::Flat, Mode::Major, Key::Flats(3)),
+            (NoteName::C, Accidental::Sharp, Mode::Major, Key::Sharps(7)),
+            (NoteName::A, Accidental::Flat, Mode::Minor, Key::Flats(7)),
+        ];
+        for (note_name, accidental, mode, expected) in cases {
+            let mut tonic = Pitch::new(note_name);
+            tonic.accidental(accidental);
+            let key = TonalKey::new(&tonic, mode).unwrap();
+            assert_eq!(key.signature().unwrap().get_key(), &expected);
+        }
+        let mut tonic = Pitch::new(NoteName::G);
+        tonic.sharpen();
+        let key = TonalKey::new(&tonic, Mode::Major).unwrap();
+        assert_eq!(
+            key.signature(),
+            Err(String::from("Invalid number of accidentals 8."))
+        );
+        assert!(TonalKey::new(&Pitch::new(NoteName::None), Mode::Major).is_err());
+    }
+    #[test]
+    fn test_fifths() {
+        for fifths in -7..=7 {
+            assert_eq!(KeySignature::from_fifths(fifths).unwrap().fifths(), fifths);
+        }
+        assert!(KeySignature::from_fifths(8).is_err());
+        let a_major = KeySignature::new(Key::Sharps(3)).unwrap();
+        let altered: Vec<NoteName> = [
+            NoteName::C,
+            NoteName::D,
+            NoteName::E,
+            NoteName::F,
+            NoteName::G,
+            NoteName::A,
+            NoteName::B,
+        ]
+        .iter()
+        .cloned()
+        .filter(|note_name| a_major.alteration(*note_name) != Alteration::from_integer(0))
+        .collect();
+        assert_eq!(altered, vec![NoteName::C, NoteName::F, NoteName::G]);
+        let c_flat_major = KeySignature::new(Key::Flats(7)).unwrap();
+        assert_eq!(
+            c_flat_major.alteration(NoteName::F),
+            Alteration::from_integer(-1)
+        );
+    }
+    #[test]
+    fn test_parallel() {
+        let key = TonalKey::new(&Pitch::new(NoteName::D), Mode::Major).unwrap();
+        assert_eq!(key.parallel().mode(), Mode::Minor);
+        assert_eq!(key.parallel().parallel(), key);
+    }
 }
@@ -0,0 +1,476 @@
+//! Abstractions for splitting music into measures.
+//!
+//! A [`Voice`] is a flat sequence of events; [`Measures`] groups those
+//! events by the barlines of a [`TimeSignature`], optionally starting with a
+//! pickup measure like LilyPond's `\partial`. Measures are numbered from 1,
+//! or from 0 when there is a pickup, so that the first full measure is
+//! always measure 1.
+
+use crate::notation::rhythm::{Duration, Rhythm};
+use crate::notation::time::TimeSignature;
+use crate::notation::transform::{rhythm, split};
+use crate::notation::voice::{Event, Voice};
+use crate::NoteNameLanguage;
+
+/// The events between two barlines.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Measure {
+    pub events: Vec<Event>,
+}
+
+impl Measure {
+    /// Construct a new, empty measure.
+    pub fn new() -> Measure {
+        Measure { events: vec![] }
+    }
+    /// Return the total duration of the events in the measure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::measure::Measure;
+    /// use lilypond::notation::rhythm::Duration;
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c4. d8 e2", NoteNameLanguage::English).unwrap());
+    /// let measure = Measure { events: voice.events };
+    ///
+    /// assert_eq!(measure.duration(), Duration::from_integer(1));
+    /// ```
+    pub fn duration(&self) -> Duration {
+        self.events
+            .iter()
+            .map(|event| rhythm(event).duration())
+            .sum()
+    }
+}
+
+/// A measure that does not last as long as its time signature, or its
+/// pickup, says it should.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fullness {
+    /// The measure is too long by `excess` whole notes.
+    Overfull { measure: u32, excess: Duration },
+    /// The measure is too short by `missing` whole notes.
+    Underfull { measure: u32, missing: Duration },
+}
+
+impl std::fmt::Display for Fullness {
+    /// Write the problem, e.g. `measure 2 is overfull by 1/4`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fullness::Overfull { measure, excess } => {
+                write!(f, "measure {} is overfull by {}", measure, excess)
+            }
+            Fullness::Underfull { measure, missing } => {
+                write!(f, "measure {} is underfull by {}", measure, missing)
+            }
+        }
+    }
+}
+
+/// Music in one time signature, split into measures.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Measures {
+    pub time_signature: TimeSignature,
+    /// The duration of the pickup measure, if the music starts with one.
+    pub partial: Option<Duration>,
+    pub measures: Vec<Measure>,
+}
+
+impl Measures {
+    /// Construct new, empty measures in 4/4 without a pickup.
+    pub fn new() -> Measures {
+        Default::default()
+    }
+    /// Attempt to split a voice into measures of `time_signature`, starting
+    /// with a pickup measure lasting `partial` whole notes if there is one.
+    ///
+    /// Events that cross a barline are split into tied notes, or into
+    /// separate rests, with their articulations on the first piece. The last
+    /// measure is left as short as the music is.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the time signature is
+    /// [invalid](TimeSignature::validate), if `partial` is not positive,
+    /// longer than a measure or a whole number of 128th notes, or if an event
+    /// cannot be split into notes at a barline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::measure::Measures;
+    /// use lilypond::notation::rhythm::Duration;
+    /// use lilypond::notation::time::TimeSignature;
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("g4 c'2. d'2->", NoteNameLanguage::English).unwrap());
+    /// let mut time_signature = TimeSignature::new();
+    /// time_signature.numerator(3).unwrap();
+    ///
+    /// let measures =
+    ///     Measures::from_voice(&voice, &time_signature, Some(Duration::new(1, 4))).unwrap();
+    ///
+    /// assert_eq!(measures.measures.len(), 3);
+    /// assert_eq!(
+    ///     measures.to_lilypond(NoteNameLanguage::English),
+    ///     "{ \\time 3/4 \\partial 4 g4 | c'2. | d'2-> }"
+    /// );
+    /// ```
+    pub fn from_voice(
+        voice: &Voice,
+        time_signature: &TimeSignature,
+        partial: Option<Duration>,
+    ) -> Result<Measures, String> {
+        time_signature.validate()?;
+        let length = time_signature.measure_duration();
+        if let Some(partial) = partial {
+            check_partial(partial, length)?;
+        }
+        let measures = split_measures(voice, partial.unwrap_or(length), |_| length)?;
+        Ok(Measures {
+            time_signature: time_signature.clone(),
+            partial,
+            measures,
+        })
+    }
+    /// Return the measures as a single voice.
+    pub fn to_voice(&self) -> Voice {
+        Voice {
+            events: self
+                .measures
+                .iter()
+                .flat_map(|measure| measure.events.iter().cloned())
+                .collect(),
+        }
+    }
+    /// Return the number of a measure from its index, counting a pickup
+    /// measure as measure 0.
+    pub fn number(&self, index: usize) -> u32 {
+        match self.partial {
+            Some(_) => index as u32,
+            None => index as u32 + 1,
+        }
+    }
+    /// Attempt to find the measures that are longer or shorter than the time
+    /// signature, or than the pickup for the first measure.
+    ///
+    /// A last measure may also be short by the length of the pickup, which
+    /// it completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the time signature is
+    /// [invalid](TimeSignature::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::measure::Fullness;
+    /// use lilypond::notation::rhythm::Duration;
+    /// use lilypond::parser::parse_measures;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let measures = parse_measures("c2 d2 | e2 f2 g4 | a2", NoteNameLanguage::English).unwrap();
+    ///
+    /// assert_eq!(
+    ///     measures.check(),
+    ///     Ok(vec![
+    ///         Fullness::Overfull { measure: 2, excess: Duration::new(1, 4) },
+    ///         Fullness::Underfull { measure: 3, missing: Duration::new(1, 2) },
+    ///     ])
+    /// );
+    /// ```
+    pub fn check(&self) -> Result<Vec<Fullness>, String> {
+        self.time_signature.validate()?;
+        let length = self.time_signature.measure_duration();
+        let last = self.measures.len().saturating_sub(1);
+        let mut findings = vec![];
+        for (index, measure) in self.measures.iter().enumerate() {
+            let duration = measure.duration();
+            let expected = match self.partial {
+                Some(partial) if index == 0 => partial,
+                Some(partial) if index == last && duration == length - partial => continue,
+                _ => length,
+            };
+            let number = self.number(index);
+            if duration > expected {
+                findings.push(Fullness::Overfull {
+                    measure: number,
+                    excess: duration - expected,
+                });
+            } else if duration < expected {
+                findings.push(Fullness::Underfull {
+                    measure: number,
+                    missing: expected - duration,
+                });
+            }
+        }
+        Ok(findings)
+    }
+    /// Write the measures as LilyPond in `language`, with the time
+    /// signature, any `\partial`, and a bar check between every two
+    /// measures.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::measure::Measures;
+    /// use lilypond::notation::time::TimeSignature;
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c2. d2.-.", NoteNameLanguage::English).unwrap());
+    /// let measures = Measures::from_voice(&voice, &TimeSignature::new(), None).unwrap();
+    ///
+    /// assert_eq!(
+    ///     measures.to_lilypond(NoteNameLanguage::English),
+    ///     "{ \\time 4/4 c2. d4-.~ | d2 }"
+    /// );
+    /// ```
+    pub fn to_lilypond(&self, language: NoteNameLanguage) -> String {
+        let mut music = vec![self.time_signature.to_lilypond()];
+        if let Some(partial) = self.partial {
+            music.push(format!("\\partial {}", lilypond_duration(partial)));
+        }
+        let measures: Vec<String> = self
+            .measures
+            .iter()
+            .map(|measure| {
+                measure
+                    .events
+                    .iter()
+                    .map(|event| language.lilypond_from_event(event))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect();
+        if !measures.is_empty() {
+            music.push(measures.join(" | "));
+        }
+        format!("{{ {} }}", music.join(" "))
+    }
+}
+
+/// Check that a pickup measure is positive, no longer than a full measure,
+/// and a whole number of 128th notes.
+pub(crate) fn check_partial(partial: Duration, length: Duration) -> Result<(), String> {
+    let shortest = Duration::new(1, 128);
+    if partial <= Duration::from_integer(0)
+        || partial > length
+        || !(partial / shortest).is_integer()
+    {
+        return Err(format!("Invalid partial measure {}.", partial));
+    }
+    Ok(())
+}
+
+/// Split a voice into measures, the first lasting `first` and each later one
+/// the length returned for its index. Events crossing a barline are split
+/// into tied notes.
+pub(crate) fn split_measures<F: Fn(usize) -> Duration>(
+    voice: &Voice,
+    first: Duration,
+    length: F,
+) -> Result<Vec<Measure>, String> {
+    let mut measures: Vec<Measure> = vec![];
+    // the time left in the current measure
+    let mut remaining = first;
+    let mut index = 0;
+    for event in &voice.events {
+        // the rhythms of the event's pieces, and how many of them go in
+        // each measure
+        let mut rhythms = vec![];
+        let mut counts = vec![];
+        let mut left = rhythm(event).duration();
+        while left > Duration::from_integer(0) {
+            if remaining == Duration::from_integer(0) {
+                index += 1;
+                remaining = length(index);
+            }
+            let take = left.min(remaining);
+            let pieces = if take == left && rhythms.is_empty() {
+                vec![rhythm(event).clone()]
+            } else {
+                Rhythm::tied(take)?
+            };
+            counts.push(pieces.len());
+            rhythms.extend(pieces);
+            left -= take;
+            remaining -= take;
+        }
+        let mut pieces = split(event, rhythms).into_iter();
+        for (piece, count) in counts.into_iter().enumerate() {
+            if measures.is_empty() || piece > 0 {
+                measures.push(Measure::new());
+            }
+            let measure = measures.last_mut().expect("a measure was pushed");
+            measure.events.extend(pieces.by_ref().take(count));
+        }
+        if remaining == Duration::from_integer(0) {
+            // the next event starts a new measure
+            measures.push(Measure::new());
+        }
+    }
+    if measures
+        .last()
+        .is_some_and(|measure| measure.events.is_empty())
+    {
+        measures.pop();
+    }
+    Ok(measures)
+}
+
+/// Write a duration as a LilyPond duration, e.g. `4.`, or as a multiplied
+/// one, e.g. `8*5`, if no single note lasts that long.
+pub(crate) fn lilypond_duration(duration: Duration) -> String {
+    match Rhythm::from_duration(duration) {
+        Ok(rhythm) => format!("{}{}", rhythm.length, rhythm.dots),
+        Err(_) => format!("{}*{}", duration.denom(), duration.numer()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::articulation::Articulation;
+    use crate::notation::measure::*;
+    use crate::parser::voice;
+    fn time(numerator: u8, denominator: u8) -> TimeSignature {
+        TimeSignature::from_fraction(numerator, denominator).unwrap()
+    }
+    #[test]
+    fn test_from_voice() {
+        let measures = Measures::from_voice(&voice("c2. d2 e4 f2"), &time(4, 4), None).unwrap();
+        assert_eq!(
+            measures.measures,
+            vec![
+                Measure {
+                    events: voice("c2. d4~").events
+                },
+                Measure {
+                    events: voice("d4 e4 f2").events
+                },
+            ]
+        );
+        assert_eq!(measures.to_voice(), voice("c2. d4~ d4 e4 f2"));
+        assert_eq!(measures.check(), Ok(vec![]));
+    }
+    #[test]
+    fn test_from_voice_across_measures() {
+        // a note over several barlines keeps its articulations on the first
+        // piece and its own tie on the last
+        let measures = Measures::from_voice(&voice("c4 d1.->~ d4 r2."), &time(3, 4), None).unwrap();
+        assert_eq!(
+            measures.to_lilypond(NoteNameLanguage::English),
+            "{ \\time 3/4 c4 d2->~ | d2.~ | d4~ d4 r4 | r2 }"
+        );
+        if let Event::Note(note) = &measures.measures[0].events[1] {
+            assert_eq!(note.articulations, vec![Articulation::Accent]);
+        }
+        assert_eq!(
+            measures.check(),
+            Ok(vec![Fullness::Underfull {
+                measure: 4,
+                missing: Duration::new(1, 4)
+            }])
+        );
+    }
+    #[test]
+    fn test_from_voice_beams() {
+        // a beam starts on the first piece of a split note and ends on the
+        // last one
+        let measures = Measures::from_voice(&voice("c4 d1 e2[ f1]"), &time(4, 4), None).unwrap();
+        assert_eq!(
+            measures.to_lilypond(NoteNameLanguage::English),
+            "{ \\time 4/4 c4 d2.~ | d4 e2[ f4~ | f2.] }"
+        );
+        let measures = Measures::from_voice(&voice("c2. d2[ e4]"), &time(4, 4), None).unwrap();
+        assert_eq!(
+            measures.to_lilypond(NoteNameLanguage::English),
+            "{ \\time 4/4 c2. d4[~ | d4 e4] }"
+        );
+    }
+    #[test]
+    fn test_from_voice_dynamics() {
+        // a dynamic stays on the first piece of a split note
+        let measures = Measures::from_voice(&voice("c4 d1\\f"), &time(4, 4), None).unwrap();
+        assert_eq!(
+            measures.to_lilypond(NoteNameLanguage::English),
+            "{ \\time 4/4 c4 d2.\\f~ | d4 }"
+        );
+    }
+    #[test]
+    fn test_from_voice_partial() {
+        let measures = Measures::from_voice(
+            &voice("g8 a8 b2 c'4 d'8"),
+            &time(3, 4),
+            Some(Duration::new(1, 4)),
+        )
+        .unwrap();
+        assert_eq!(measures.measures.len(), 3);
+        assert_eq!(measures.number(0), 0);
+        assert_eq!(
+            measures.to_lilypond(NoteNameLanguage::English),
+            "{ \\time 3/4 \\partial 4 g8 a8 | b2 c'4 | d'8 }"
+        );
+        assert_eq!(
+            measures.check(),
+            Ok(vec![Fullness::Underfull {
+                measure: 2,
+                missing: Duration::new(5, 8)
+            }])
+        );
+        // a last measure may complete the pickup
+        let complete = Measures::from_voice(
+            &voice("g4 c'2. d'2"),
+            &time(3, 4),
+            Some(Duration::new(1, 4)),
+        )
+        .unwrap();
+        assert_eq!(complete.check(), Ok(vec![]));
+        let odd =
+            Measures::from_voice(&voice("c8"), &time(5, 8), Some(Duration::new(5, 16))).unwrap();
+        assert_eq!(
+            odd.to_lilypond(NoteNameLanguage::English),
+            "{ \\time 5/8 \\partial 16*5 c8 }"
+        );
+    }
+    #[test]
+    fn test_from_voice_errors() {
+        let c = voice("c4");
+        // time signatures built from their fields are checked
+        let unchecked = |numerator, denominator| TimeSignature {
+            numerator,
+            denominator,
+            ..TimeSignature::new()
+        };
+        assert!(Measures::from_voice(&c, &unchecked(4, 0), None).is_err());
+        assert!(Measures::from_voice(&c, &time(4, 4), Some(Duration::from_integer(0))).is_err());
+        assert!(Measures::from_voice(&c, &time(4, 4), Some(Duration::new(5, 4))).is_err());
+        assert!(Measures::from_voice(&c, &time(4, 4), Some(Duration::new(1, 3))).is_err());
+        assert!(Measures::from_voice(&voice("c2"), &unchecked(1, 3), None).is_err());
+        assert_eq!(
+            Measures::from_voice(&Voice::new(), &time(4, 4), None)
+                .unwrap()
+                .measures,
+            vec![]
+        );
+    }
+    #[test]
+    fn test_fullness_display() {
+        let overfull = Fullness::Overfull {
+            measure: 2,
+            excess: Duration::new(1, 4),
+        };
+        assert_eq!(overfull.to_string(), "measure 2 is overfull by 1/4");
+    }
+}
@@ -1,7 +1,117 @@
 //! Abstractions for rendering notations.
+//!
+//! # Serialization
+//!
+//! With the `serde` feature, the notation model implements `Serialize` and
+//! `Deserialize`. In JSON, a struct is an object with a key for each field,
+//! and an enum variant is its name as a string, or an object with the name
+//! as its only key if it holds a value:
+//!
+//! ```json
+//! {
+//!   "pitch": { "note_name": "C", "octave": "S5", "accidental": "Sharp" },
+//!   "rhythm": { "length": "Eighth", "dots": 1, "duration_type": "Note" },
+//!   "tie": false,
+//!   "articulations": ["Staccato"],
+//!   "beam": "Start",
+//!   "dynamic": "P"
+//! }
+//! ```
+//!
+//! An [`Event`](voice::Event) is `{"Note": {...}}` or `{"Chord": {...}}`, and
+//! a rest is a note with the note name, octave and accidental `"None"` and
+//! the duration type `"Rest"`. Missing optional values like `beam` are `null`.
+//!
+//! A few types are written more compactly, and checked when read:
+//!
+//! - A rational number, like a [`Duration`](rhythm::Duration) or the
+//!   alteration of `{"Other": [1, 3]}` [accidental](pitch::Accidental), is
+//!   an array of its numerator and denominator.
+//! - [`Dots`](rhythm::Dots) are their number, e.g. `1`.
+//! - A [`KeySignature`](key::KeySignature) is its [`Key`](key::Key), e.g.
+//!   `{"Sharps": 2}` or `"None"`.
+//! - An [`Interval`](interval::Interval) is its name, e.g. `"m3"`.
+//! - A [`MidiNote`](crate::midi::midi_note::MidiNote) or
+//!   [`PitchBend`](crate::midi::pitch_bend::PitchBend) is its integer value.
 
+pub mod articulation;
+pub mod beam;
+pub mod chord;
+pub mod clef;
+pub mod dynamic;
+pub mod interval;
 pub mod key;
+pub mod measure;
 pub mod note;
 pub mod pitch;
 pub mod rhythm;
+pub mod scale;
+pub mod score;
 pub mod time;
+pub mod transform;
+pub mod transpose;
+pub mod voice;
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use crate::import::kern::from_kern;
+    use crate::midi::midi_note::MidiNote;
+    use crate::notation::interval::Interval;
+    use crate::notation::key::{Key, KeySignature};
+    use crate::notation::note::Note;
+    use crate::notation::pitch::{Accidental, Alteration};
+    use crate::notation::score::Score;
+    use crate::parser::parse_notes;
+    use crate::NoteNameLanguage;
+    #[test]
+    fn test_json() {
+        let note = &parse_notes("cs''8.[-.\\p", NoteNameLanguage::English).unwrap()[0];
+        let json = serde_json::to_string(note).unwrap();
+        assert_eq!(
+            json,
+            r#"{"pitch":{"note_name":"C","octave":"S5","accidental":"Sharp"},"rhythm":{"length":"Eighth","dots":1,"duration_type":"Note"},"tie":false,"articulations":["Staccato"],"beam":"Start","dynamic":"P"}"#
+        );
+        assert_eq!(&serde_json::from_str::<Note>(&json).unwrap(), note);
+
+        let score = from_kern(include_str!("../../tests/fixtures/minuet.krn")).unwrap();
+        let json = serde_json::to_string(&score).unwrap();
+        assert_eq!(serde_json::from_str::<Score>(&json).unwrap(), score);
+    }
+    #[test]
+    fn test_json_values() {
+        let json = |value: &str| serde_json::from_str::<serde_json::Value>(value).unwrap();
+        let accidental = Accidental::Other(Alteration::new(1, 3));
+        assert_eq!(
+            serde_json::to_value(accidental).unwrap(),
+            json(r#"{"Other":[1,3]}"#)
+        );
+        let key_signature = KeySignature::new(Key::Flats(3)).unwrap();
+        assert_eq!(
+            serde_json::to_value(&key_signature).unwrap(),
+            json(r#"{"Flats":3}"#)
+        );
+        let interval: Interval = "m3".parse().unwrap();
+        assert_eq!(serde_json::to_value(interval).unwrap(), json(r#""m3""#));
+        assert_eq!(
+            serde_json::to_value(MidiNote::new(60).unwrap()).unwrap(),
+            json("60")
+        );
+    }
+    #[test]
+    fn test_json_errors() {
+        assert_eq!(
+            serde_json::from_str::<MidiNote>("-1")
+                .unwrap_err()
+                .to_string(),
+            "Invalid MIDI integer -1."
+        );
+        assert_eq!(
+            serde_json::from_str::<KeySignature>(r#"{"Sharps":8}"#)
+                .unwrap_err()
+                .to_string(),
+            "Invalid number of accidentals 8."
+        );
+        assert!(serde_json::from_str::<Interval>(r#""P3""#).is_err());
+        assert!(serde_json::from_str::<Note>("{}").is_err());
+    }
+}
@@ -1,20 +1,32 @@
 //! Abstract types for musical note, describing pitch and rhythm.
 
 use crate::lilypond_objects::lilypond_note::LilyPondNote;
+use crate::notation::articulation::Articulation;
+use crate::notation::beam::Beam;
+use crate::notation::dynamic::Dynamic;
 use crate::notation::pitch::{NoteName, Pitch};
 use crate::notation::rhythm::Rhythm;
 use crate::note_from_lilypond;
 
 /// A note with rhythm and pitch
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Note {
     pub pitch: Pitch,
     pub rhythm: Rhythm,
+    /// Whether the note is tied to the next one, like LilyPond's `c4~`.
+    pub tie: bool,
+    /// The articulations of the note, in the order they are written.
+    pub articulations: Vec<Articulation>,
+    /// Whether the note starts or ends a manual beam, like LilyPond's `c8[`.
+    pub beam: Option<Beam>,
+    /// The dynamic mark under the note, like LilyPond's `c4\mf`.
+    pub dynamic: Option<Dynamic>,
 }
 
 /// - TODO shorthand for creating a new note with pitch and duration
 /// - TODO also need to create staff, time signature, key signature, chord
-/// before we have a minimum viable product
+///   before we have a minimum viable product
 impl Note {
     /// Construct a new note
     ///
@@ -41,6 +53,10 @@ impl Note {
         Note {
             pitch: Pitch::new(note_name),
             rhythm: Rhythm::new(),
+            tie: false,
+            articulations: vec![],
+            beam: None,
+            dynamic: None,
         }
     }
 }
@@ -1,6 +1,9 @@
 //! Abstractions for specifying the pitch of notes.
 
+use num_rational::Rational32;
+
 use crate::midi::midi_note::MidiNote;
+use crate::tuning::temperament::Tuning;
 
 /// Natural pitch note names.
 ///
@@ -11,7 +14,8 @@ use crate::midi::midi_note::MidiNote;
 /// and can be designated by both
 /// [scientific pitch](https://en.wikipedia.org/wiki/Scientific_pitch_notation)
 /// and [helmholtz pitch](https://en.wikipedia.org/wiki/Helmholtz_pitch_notation).
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NoteName {
     /// # A Defaults:
     ///
@@ -52,11 +56,85 @@ pub enum NoteName {
     None,
 }
 
+impl NoteName {
+    /// Return the position of the note name in the C major scale, from C (0)
+    /// to B (6), or `None` for rests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::NoteName;
+    ///
+    /// assert_eq!(NoteName::C.diatonic_index(), Some(0));
+    /// assert_eq!(NoteName::B.diatonic_index(), Some(6));
+    /// assert_eq!(NoteName::None.diatonic_index(), None);
+    /// ```
+    pub fn diatonic_index(&self) -> Option<i32> {
+        match self {
+            NoteName::C => Some(0),
+            NoteName::D => Some(1),
+            NoteName::E => Some(2),
+            NoteName::F => Some(3),
+            NoteName::G => Some(4),
+            NoteName::A => Some(5),
+            NoteName::B => Some(6),
+            NoteName::None => None,
+        }
+    }
+    /// Return the note name at a position in the C major scale, wrapping
+    /// around every 7 steps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::NoteName;
+    ///
+    /// assert_eq!(NoteName::from_diatonic_index(4), NoteName::G);
+    /// assert_eq!(NoteName::from_diatonic_index(9), NoteName::E);
+    /// assert_eq!(NoteName::from_diatonic_index(-1), NoteName::B);
+    /// ```
+    pub fn from_diatonic_index(index: i32) -> NoteName {
+        match index.rem_euclid(7) {
+            0 => NoteName::C,
+            1 => NoteName::D,
+            2 => NoteName::E,
+            3 => NoteName::F,
+            4 => NoteName::G,
+            5 => NoteName::A,
+            _ => NoteName::B,
+        }
+    }
+    /// Return the number of semitones from C up to the natural note name, or
+    /// `None` for rests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::NoteName;
+    ///
+    /// assert_eq!(NoteName::E.semitones_above_c(), Some(4));
+    /// assert_eq!(NoteName::A.semitones_above_c(), Some(9));
+    /// ```
+    pub fn semitones_above_c(&self) -> Option<i32> {
+        match self {
+            NoteName::C => Some(0),
+            NoteName::D => Some(2),
+            NoteName::E => Some(4),
+            NoteName::F => Some(5),
+            NoteName::G => Some(7),
+            NoteName::A => Some(9),
+            NoteName::B => Some(11),
+            NoteName::None => None,
+        }
+    }
+}
+
 /// Octaves a pitch can have.
 ///
 /// [Scientific Pitch](https://en.wikipedia.org/wiki/Scientific_pitch_notation)
 /// is used to represent possible octave values.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Octave {
     S0,
     S1,
@@ -93,6 +171,60 @@ impl std::fmt::Display for Octave {
     }
 }
 
+impl Octave {
+    /// Return the scientific pitch number of the octave, or `None` for rests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::Octave;
+    ///
+    /// assert_eq!(Octave::S4.number(), Some(4));
+    /// assert_eq!(Octave::None.number(), None);
+    /// ```
+    pub fn number(&self) -> Option<i32> {
+        match self {
+            Octave::S0 => Some(0),
+            Octave::S1 => Some(1),
+            Octave::S2 => Some(2),
+            Octave::S3 => Some(3),
+            Octave::S4 => Some(4),
+            Octave::S5 => Some(5),
+            Octave::S6 => Some(6),
+            Octave::S7 => Some(7),
+            Octave::S8 => Some(8),
+            Octave::S9 => Some(9),
+            Octave::None => None,
+        }
+    }
+    /// Return the octave with a scientific pitch number, or `None` if there
+    /// is no such octave.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::Octave;
+    ///
+    /// assert_eq!(Octave::from_number(2), Some(Octave::S2));
+    /// assert_eq!(Octave::from_number(10), None);
+    /// ```
+    pub fn from_number(number: i32) -> Option<Octave> {
+        match number {
+            0 => Some(Octave::S0),
+            1 => Some(Octave::S1),
+            2 => Some(Octave::S2),
+            3 => Some(Octave::S3),
+            4 => Some(Octave::S4),
+            5 => Some(Octave::S5),
+            6 => Some(Octave::S6),
+            7 => Some(Octave::S7),
+            8 => Some(Octave::S8),
+            9 => Some(Octave::S9),
+            _ => None,
+        }
+    }
+}
+
 impl Default for Octave {
     /// Set octave directly below middle C (C3--B3) as default.
     fn default() -> Self {
@@ -100,14 +232,124 @@ impl Default for Octave {
     }
 }
 
+/// An alteration of a natural pitch, measured in semitones.
+///
+/// For example, a sharp is an alteration of `1`, a flat is `-1`, and a
+/// quarter-tone sharp is `1/2`.
+pub type Alteration = Rational32;
+
 /// Accidentals a note can have.
-#[derive(PartialEq, Debug)]
+///
+/// Besides the common accidentals, quarter-tone and three-quarter-tone
+/// accidentals are named, and any other rational alteration can be expressed
+/// with [`Accidental::Other`].
+///
+/// Two accidentals are equal if they have the same
+/// [alteration][Accidental::alteration], so `Accidental::Other` values that
+/// correspond to a named accidental compare equal to it.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Accidental {
     None,
     Sharp,
     DoubleSharp,
     Flat,
     DoubleFlat,
+    /// Raised by a quarter tone (half a semitone).
+    QuarterSharp,
+    /// Raised by three quarter tones (one and a half semitones).
+    ThreeQuarterSharp,
+    /// Lowered by a quarter tone (half a semitone).
+    QuarterFlat,
+    /// Lowered by three quarter tones (one and a half semitones).
+    ThreeQuarterFlat,
+    /// An arbitrary alteration in semitones.
+    Other(Alteration),
+}
+
+impl Accidental {
+    /// Return the alteration of the accidental in semitones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, Alteration};
+    ///
+    /// assert_eq!(Accidental::DoubleFlat.alteration(), Alteration::from_integer(-2));
+    /// assert_eq!(Accidental::QuarterSharp.alteration(), Alteration::new(1, 2));
+    /// assert_eq!(Accidental::Other(Alteration::new(1, 3)).alteration(), Alteration::new(1, 3));
+    /// ```
+    pub fn alteration(&self) -> Alteration {
+        match self {
+            Accidental::None => Alteration::from_integer(0),
+            Accidental::Sharp => Alteration::from_integer(1),
+            Accidental::DoubleSharp => Alteration::from_integer(2),
+            Accidental::Flat => Alteration::from_integer(-1),
+            Accidental::DoubleFlat => Alteration::from_integer(-2),
+            Accidental::QuarterSharp => Alteration::new(1, 2),
+            Accidental::ThreeQuarterSharp => Alteration::new(3, 2),
+            Accidental::QuarterFlat => Alteration::new(-1, 2),
+            Accidental::ThreeQuarterFlat => Alteration::new(-3, 2),
+            Accidental::Other(alteration) => *alteration,
+        }
+    }
+    /// Construct the accidental for an alteration in semitones.
+    ///
+    /// Alterations with a named accidental return that accidental, and all
+    /// others return [`Accidental::Other`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, Alteration};
+    ///
+    /// let accidental = Accidental::from_alteration(Alteration::new(-3, 2));
+    /// assert!(matches!(accidental, Accidental::ThreeQuarterFlat));
+    /// let accidental = Accidental::from_alteration(Alteration::new(1, 4));
+    /// assert!(matches!(accidental, Accidental::Other(_)));
+    /// ```
+    pub fn from_alteration(alteration: Alteration) -> Accidental {
+        match (alteration.numer(), alteration.denom()) {
+            (0, _) => Accidental::None,
+            (1, 1) => Accidental::Sharp,
+            (2, 1) => Accidental::DoubleSharp,
+            (-1, 1) => Accidental::Flat,
+            (-2, 1) => Accidental::DoubleFlat,
+            (1, 2) => Accidental::QuarterSharp,
+            (3, 2) => Accidental::ThreeQuarterSharp,
+            (-1, 2) => Accidental::QuarterFlat,
+            (-3, 2) => Accidental::ThreeQuarterFlat,
+            _ => Accidental::Other(alteration),
+        }
+    }
+    /// Whether the accidental alters its pitch by a whole number of
+    /// semitones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::Accidental;
+    ///
+    /// assert!(Accidental::Flat.is_chromatic());
+    /// assert!(!Accidental::QuarterFlat.is_chromatic());
+    /// ```
+    pub fn is_chromatic(&self) -> bool {
+        self.alteration().is_integer()
+    }
+}
+
+impl PartialEq for Accidental {
+    fn eq(&self, other: &Self) -> bool {
+        self.alteration() == other.alteration()
+    }
+}
+
+impl Eq for Accidental {}
+
+impl std::hash::Hash for Accidental {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.alteration().hash(state);
+    }
 }
 
 impl Default for Accidental {
@@ -118,7 +360,8 @@ impl Default for Accidental {
 }
 
 /// A single pitch
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pitch {
     /// The note letter name, e.g. C, E, or G.
     pub note_name: NoteName,
@@ -218,6 +461,422 @@ impl Pitch {
     pub fn flatten(&mut self) {
         self.accidental = Accidental::Flat
     }
+    /// Return the number of diatonic steps from C0 up to the pitch, ignoring
+    /// its accidental.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` for rests, which have no pitch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+    ///
+    /// let mut pitch = Pitch::new(NoteName::D);
+    /// pitch.octave(Octave::S1);
+    ///
+    /// assert_eq!(pitch.diatonic_number(), Ok(8));
+    /// ```
+    pub fn diatonic_number(&self) -> Result<i32, String> {
+        match (self.octave.number(), self.note_name.diatonic_index()) {
+            (Some(octave), Some(index)) => Ok(octave * 7 + index),
+            _ => Err(String::from("Rests have no pitch.")),
+        }
+    }
+    /// Return the number of semitones from C-1 up to the pitch, i.e. its
+    /// MIDI note number, including any fraction of a semitone.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` for rests, which have no pitch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, Alteration, Pitch, NoteName, Octave};
+    ///
+    /// let mut pitch = Pitch::new(NoteName::C);
+    /// pitch.octave(Octave::S4);
+    /// assert_eq!(pitch.semitones(), Ok(Alteration::from_integer(60)));
+    /// pitch.accidental(Accidental::QuarterFlat);
+    /// assert_eq!(pitch.semitones(), Ok(Alteration::new(119, 2)));
+    /// ```
+    pub fn semitones(&self) -> Result<Alteration, String> {
+        match (self.octave.number(), self.note_name.semitones_above_c()) {
+            (Some(octave), Some(semitones)) => {
+                Ok(Alteration::from_integer((octave + 1) * 12 + semitones)
+                    + self.accidental.alteration())
+            }
+            _ => Err(String::from("Rests have no pitch.")),
+        }
+    }
+    /// Construct the pitch with a diatonic number (see
+    /// [`diatonic_number`][Pitch::diatonic_number]) that sounds a number of
+    /// semitones above C-1, spelling it with whatever accidental is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the pitch is outside of the octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, Alteration, Pitch, NoteName, Octave};
+    ///
+    /// // the note E in octave 4 that sounds like F4
+    /// let pitch = Pitch::from_diatonic_number(30, Alteration::from_integer(65)).unwrap();
+    ///
+    /// assert_eq!(pitch.note_name, NoteName::E);
+    /// assert_eq!(pitch.octave, Octave::S4);
+    /// assert_eq!(pitch.accidental, Accidental::Sharp);
+    /// ```
+    pub fn from_diatonic_number(
+        diatonic_number: i32,
+        semitones: Alteration,
+    ) -> Result<Pitch, String> {
+        let octave = Octave::from_number(diatonic_number.div_euclid(7))
+            .ok_or_else(|| format!("Invalid diatonic number {}.", diatonic_number))?;
+        let note_name = NoteName::from_diatonic_index(diatonic_number);
+        let mut pitch = Pitch::new(note_name);
+        pitch.octave(octave);
+        let natural = pitch.semitones()?;
+        pitch.accidental(Accidental::from_alteration(semitones - natural));
+        Ok(pitch)
+    }
+    /// Respell a pitch whose accidental alters it by two or more semitones
+    /// with a neighbouring note name, e.g. F double sharp as G, or C double
+    /// flat as B flat. Other pitches, including rests, are returned as they
+    /// are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the respelled pitch is outside of the octaves
+    /// C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, Pitch, NoteName, Octave};
+    ///
+    /// let mut pitch = Pitch::new(NoteName::C);
+    /// pitch.accidental(Accidental::DoubleFlat);
+    /// let pitch = pitch.simplify_double_accidental().unwrap();
+    ///
+    /// assert_eq!(pitch.note_name, NoteName::B);
+    /// assert_eq!(pitch.octave, Octave::S2);
+    /// assert_eq!(pitch.accidental, Accidental::Flat);
+    /// ```
+    pub fn simplify_double_accidental(&self) -> Result<Pitch, String> {
+        let mut pitch = self.clone();
+        let two = Alteration::from_integer(2);
+        loop {
+            let alteration = pitch.accidental.alteration();
+            let step = if alteration >= two {
+                1
+            } else if alteration <= -two {
+                -1
+            } else {
+                break;
+            };
+            pitch =
+                Pitch::from_diatonic_number(pitch.diatonic_number()? + step, self.semitones()?)?;
+        }
+        Ok(pitch)
+    }
+    /// Write the pitch in [Helmholtz pitch
+    /// notation](https://en.wikipedia.org/wiki/Helmholtz_pitch_notation),
+    /// e.g. `c'` for C4 or `Bb,` for B flat 1, or `r` for a rest.
+    ///
+    /// Octaves from C3 up are written in lowercase with an apostrophe for
+    /// each octave above C3, like LilyPond, and lower octaves in uppercase
+    /// with a comma for each octave below C2. Accidentals are written as in
+    /// [scientific pitch notation](#impl-Display-for-Pitch).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, NoteName, Octave, Pitch};
+    ///
+    /// let mut pitch = Pitch::new(NoteName::C);
+    /// pitch.octave(Octave::S4);
+    /// pitch.accidental(Accidental::Sharp);
+    /// assert_eq!(pitch.to_helmholtz(), "c#'");
+    /// pitch.octave(Octave::S1);
+    /// assert_eq!(pitch.to_helmholtz(), "C#,");
+    /// ```
+    pub fn to_helmholtz(&self) -> String {
+        let index = match self.note_name.diatonic_index() {
+            Some(index) => index as usize,
+            None => return String::from("r"),
+        };
+        let letter = LETTERS[index];
+        let accidental = accidental_symbol(self.accidental);
+        match self.octave.number() {
+            Some(octave) if octave >= 3 => format!(
+                "{}{}{}",
+                letter.to_ascii_lowercase(),
+                accidental,
+                "'".repeat((octave - 3) as usize)
+            ),
+            Some(octave) => format!(
+                "{}{}{}",
+                letter,
+                accidental,
+                ",".repeat((2 - octave) as usize)
+            ),
+            None => format!("{}{}", letter.to_ascii_lowercase(), accidental),
+        }
+    }
+    /// Attempt to parse a pitch in [Helmholtz pitch
+    /// notation](https://en.wikipedia.org/wiki/Helmholtz_pitch_notation),
+    /// e.g. `c'`, `C,` or `bb`, as written by
+    /// [`to_helmholtz`][Pitch::to_helmholtz].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the string is not a valid pitch, or if its
+    /// octave is outside of C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, NoteName, Octave, Pitch};
+    ///
+    /// let pitch = Pitch::from_helmholtz("eb''").unwrap();
+    /// assert_eq!(pitch.note_name, NoteName::E);
+    /// assert_eq!(pitch.octave, Octave::S5);
+    /// assert_eq!(pitch.accidental, Accidental::Flat);
+    /// assert_eq!(Pitch::from_helmholtz("C,").unwrap().octave, Octave::S1);
+    /// ```
+    pub fn from_helmholtz(s: &str) -> Result<Pitch, String> {
+        let error = || format!("Invalid Helmholtz pitch '{}'.", s);
+        if s == "r" {
+            return Ok(Pitch::rest());
+        }
+        let mut chars = s.chars();
+        let letter = chars.next().ok_or_else(error)?;
+        let rest = chars.as_str();
+        let unmarked = rest.trim_end_matches(['\'', ',']);
+        let (accidental, marks) = rest.split_at(unmarked.len());
+        let count = marks.chars().count() as i32;
+        let octave = if letter.is_ascii_lowercase() && marks.chars().all(|c| c == '\'') {
+            3 + count
+        } else if letter.is_ascii_uppercase() && marks.chars().all(|c| c == ',') {
+            2 - count
+        } else {
+            return Err(error());
+        };
+        let mut pitch = Pitch::new(letter_name(letter).ok_or_else(error)?);
+        pitch.accidental(read_accidental(accidental).ok_or_else(error)?);
+        pitch.octave(Octave::from_number(octave).ok_or_else(error)?);
+        Ok(pitch)
+    }
+    /// Attempt to return the frequency of the pitch in hertz in a
+    /// [`Tuning`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` for rests, which have no pitch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::Pitch;
+    /// use lilypond::tuning::temperament::{Temperament, Tuning};
+    ///
+    /// let pitch: Pitch = "A3".parse().unwrap();
+    /// let tuning = Tuning::new(415.0, Temperament::Equal).unwrap();
+    /// assert_eq!(pitch.frequency(&tuning), Ok(207.5));
+    /// ```
+    pub fn frequency(&self, tuning: &Tuning) -> Result<f64, String> {
+        tuning.frequency(self)
+    }
+    /// Attempt to find the pitch nearest to a frequency in hertz in a
+    /// [`Tuning`], and return it with the deviation of the frequency from it
+    /// in cents (see [`Tuning::nearest_pitch`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the frequency is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::Pitch;
+    /// use lilypond::tuning::temperament::Tuning;
+    ///
+    /// let (pitch, cents) = Pitch::from_frequency(445.0, &Tuning::default()).unwrap();
+    /// assert_eq!(pitch.to_string(), "A4");
+    /// assert_eq!(cents.round(), 20.0);
+    /// ```
+    pub fn from_frequency(frequency: f64, tuning: &Tuning) -> Result<(Pitch, f64), String> {
+        tuning.nearest_pitch(frequency)
+    }
+    /// The pitch of a rest, with no note name or octave.
+    fn rest() -> Pitch {
+        Pitch {
+            note_name: NoteName::None,
+            octave: Octave::None,
+            accidental: Accidental::None,
+        }
+    }
+}
+
+/// The note names in order from C, as written in scientific pitch notation.
+const LETTERS: [char; 7] = ['C', 'D', 'E', 'F', 'G', 'A', 'B'];
+
+/// Return the note name of a letter from A to G in either case.
+fn letter_name(letter: char) -> Option<NoteName> {
+    LETTERS
+        .iter()
+        .position(|c| *c == letter.to_ascii_uppercase())
+        .map(|index| NoteName::from_diatonic_index(index as i32))
+}
+
+/// Write an accidental as a `#` for each sharp or `b` for each flat,
+/// followed by `+` or `d` for a remaining quarter tone, e.g. `##`, `b` or
+/// `#+`. Any other alteration is written in brackets, e.g. `[+1/3]`.
+fn accidental_symbol(accidental: Accidental) -> String {
+    let alteration = accidental.alteration();
+    let quarters = alteration * 2;
+    if !quarters.is_integer() {
+        return format!(
+            "[{}{}]",
+            if alteration > Alteration::from_integer(0) {
+                "+"
+            } else {
+                ""
+            },
+            alteration
+        );
+    }
+    let quarters = quarters.to_integer();
+    let (whole, quarter) = if quarters >= 0 {
+        ("#", "+")
+    } else {
+        ("b", "d")
+    };
+    let mut symbol = whole.repeat((quarters.unsigned_abs() / 2) as usize);
+    if quarters % 2 != 0 {
+        symbol.push_str(quarter);
+    }
+    symbol
+}
+
+/// Read an accidental written by [`accidental_symbol`], also accepting `x`
+/// for a double sharp and `♯`, `♭` and `♮`.
+fn read_accidental(symbol: &str) -> Option<Accidental> {
+    if let Some(alteration) = symbol
+        .strip_prefix('[')
+        .and_then(|symbol| symbol.strip_suffix(']'))
+    {
+        let alteration = alteration.strip_prefix('+').unwrap_or(alteration);
+        return alteration.parse().ok().map(Accidental::from_alteration);
+    }
+    let (symbol, quarter) = match symbol.strip_suffix('+') {
+        Some(symbol) => (symbol, Alteration::new(1, 2)),
+        None => match symbol.strip_suffix('d') {
+            Some(symbol) => (symbol, Alteration::new(-1, 2)),
+            None => (symbol, Alteration::from_integer(0)),
+        },
+    };
+    let steps = symbol
+        .chars()
+        .map(|c| match c {
+            '#' | '♯' => Some(1),
+            'x' => Some(2),
+            'b' | '♭' => Some(-1),
+            '♮' if symbol == "♮" => Some(0),
+            _ => None,
+        })
+        .collect::<Option<Vec<i32>>>()?;
+    // sharps and flats cannot be mixed, and a quarter tone goes the same way
+    let sharp = quarter > Alteration::from_integer(0) || steps.iter().any(|step| *step > 0);
+    let flat = quarter < Alteration::from_integer(0) || steps.iter().any(|step| *step < 0);
+    if sharp && flat {
+        return None;
+    }
+    let alteration = Alteration::from_integer(steps.iter().sum()) + quarter;
+    Some(Accidental::from_alteration(alteration))
+}
+
+impl std::fmt::Display for Pitch {
+    /// Write the pitch in [scientific pitch
+    /// notation](https://en.wikipedia.org/wiki/Scientific_pitch_notation),
+    /// e.g. `C#4` or `Bb2`, or `r` for a rest.
+    ///
+    /// Each sharp is written as `#` and each flat as `b`. A quarter-tone
+    /// sharp is `+` and a quarter-tone flat `d`, following any sharps or
+    /// flats, e.g. `#+` for three quarter tones sharp. Other alterations are
+    /// written as a fraction of a semitone in brackets, e.g. `C[+1/3]4`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let index = match self.note_name.diatonic_index() {
+            Some(index) => index as usize,
+            None => return write!(f, "r"),
+        };
+        write!(
+            f,
+            "{}{}",
+            LETTERS[index],
+            accidental_symbol(self.accidental)
+        )?;
+        match self.octave.number() {
+            Some(octave) => write!(f, "{}", octave),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::str::FromStr for Pitch {
+    type Err = String;
+
+    /// Attempt to parse a pitch in [scientific pitch
+    /// notation](https://en.wikipedia.org/wiki/Scientific_pitch_notation),
+    /// as written by its [`Display`](#impl-Display-for-Pitch) implementation.
+    /// The letter may be in either case, a double sharp may also be written
+    /// as `x`, and `r` is a rest. Octave 4 starts at middle C, so `C4` is
+    /// [`MidiNote`] 60.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the string is not a valid pitch, or if its
+    /// octave is outside of [`Octave::S0`]--[`Octave::S9`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::pitch::{Accidental, NoteName, Octave, Pitch};
+    ///
+    /// let pitch: Pitch = "Bb2".parse().unwrap();
+    /// assert_eq!(pitch.note_name, NoteName::B);
+    /// assert_eq!(pitch.octave, Octave::S2);
+    /// assert_eq!(pitch.accidental, Accidental::Flat);
+    /// assert_eq!(pitch.to_string(), "Bb2");
+    ///
+    /// assert_eq!("H4".parse::<Pitch>(), Err(String::from("Invalid pitch 'H4'.")));
+    /// assert_eq!("C10".parse::<Pitch>(), Err(String::from("Invalid octave 10 in pitch 'C10'.")));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid pitch '{}'.", s);
+        if s == "r" {
+            return Ok(Pitch::rest());
+        }
+        let mut chars = s.chars();
+        let letter = chars.next().ok_or_else(error)?;
+        let rest = chars.as_str();
+        let accidental = rest.trim_end_matches(|c: char| c.is_ascii_digit());
+        let accidental = accidental.strip_suffix('-').unwrap_or(accidental);
+        let octave = &rest[accidental.len()..];
+        let octave: i32 = octave.parse().map_err(|_| error())?;
+        let mut pitch = Pitch::new(letter_name(letter).ok_or_else(error)?);
+        pitch.accidental(read_accidental(accidental).ok_or_else(error)?);
+        pitch.octave(
+            Octave::from_number(octave)
+                .ok_or_else(|| format!("Invalid octave {} in pitch '{}'.", octave, s))?,
+        );
+        Ok(pitch)
+    }
 }
 
 impl std::convert::TryFrom<&MidiNote> for Pitch {
@@ -303,6 +962,49 @@ mod test {
         assert_eq!(pitch.accidental, Accidental::Flat);
     }
     #[test]
+    fn test_alteration() {
+        assert_eq!(Accidental::None.alteration(), Alteration::from_integer(0));
+        assert_eq!(Accidental::Sharp.alteration(), Alteration::from_integer(1));
+        assert_eq!(
+            Accidental::ThreeQuarterSharp.alteration(),
+            Alteration::new(3, 2)
+        );
+        assert_eq!(Accidental::QuarterFlat.alteration(), Alteration::new(-1, 2));
+        assert_eq!(
+            Accidental::Other(Alteration::new(-2, 3)).alteration(),
+            Alteration::new(-2, 3)
+        );
+    }
+    #[test]
+    fn test_from_alteration() {
+        let alterations = [
+            (Alteration::from_integer(0), Accidental::None),
+            (Alteration::from_integer(2), Accidental::DoubleSharp),
+            (Alteration::from_integer(-1), Accidental::Flat),
+            (Alteration::new(1, 2), Accidental::QuarterSharp),
+            (Alteration::new(-3, 2), Accidental::ThreeQuarterFlat),
+        ];
+        for (alteration, accidental) in alterations {
+            assert_eq!(Accidental::from_alteration(alteration), accidental);
+        }
+        assert!(matches!(
+            Accidental::from_alteration(Alteration::new(4, 2)),
+            Accidental::DoubleSharp
+        ));
+        assert!(matches!(
+            Accidental::from_alteration(Alteration::new(1, 3)),
+            Accidental::Other(_)
+        ));
+    }
+    #[test]
+    fn test_accidental_eq() {
+        assert_eq!(
+            Accidental::Other(Alteration::new(1, 2)),
+            Accidental::QuarterSharp
+        );
+        assert_ne!(Accidental::QuarterSharp, Accidental::Sharp);
+    }
+    #[test]
     fn test_flatten() {
         let mut pitch = Pitch::new(NoteName::A);
         pitch.flatten();
@@ -340,4 +1042,127 @@ mod test {
         assert_eq!(pitch.note_name, NoteName::None);
         assert_eq!(pitch.accidental, Accidental::None);
     }
+    #[test]
+    fn test_simplify_double_accidental() {
+        let cases = [
+            (
+                NoteName::F,
+                Accidental::DoubleSharp,
+                NoteName::G,
+                Accidental::None,
+                Octave::S3,
+            ),
+            (
+                NoteName::B,
+                Accidental::DoubleSharp,
+                NoteName::C,
+                Accidental::Sharp,
+                Octave::S4,
+            ),
+            (
+                NoteName::D,
+                Accidental::DoubleFlat,
+                NoteName::C,
+                Accidental::None,
+                Octave::S3,
+            ),
+            (
+                NoteName::E,
+                Accidental::Sharp,
+                NoteName::E,
+                Accidental::Sharp,
+                Octave::S3,
+            ),
+            (
+                NoteName::A,
+                Accidental::Other(Alteration::new(5, 2)),
+                NoteName::B,
+                Accidental::QuarterSharp,
+                Octave::S3,
+            ),
+        ];
+        for (name, accidental, expected_name, expected_accidental, octave) in cases {
+            let mut pitch = Pitch::new(name);
+            pitch.accidental(accidental);
+            let pitch = pitch.simplify_double_accidental().unwrap();
+            assert_eq!(pitch.note_name, expected_name);
+            assert_eq!(pitch.accidental, expected_accidental);
+            assert_eq!(pitch.octave, octave);
+        }
+        let rest = Pitch::new(NoteName::None);
+        assert_eq!(rest.simplify_double_accidental(), Ok(rest));
+    }
+    #[test]
+    fn test_scientific() {
+        for note in 12..132 {
+            let midi = MidiNote::new(note).unwrap();
+            let pitch = Pitch::try_from(&midi).unwrap();
+            let parsed: Pitch = pitch.to_string().parse().unwrap();
+            assert_eq!(parsed, pitch);
+            assert_eq!(MidiNote::try_from(&parsed), Ok(midi));
+        }
+        let pitches = [
+            ("C#4", NoteName::C, Octave::S4, Accidental::Sharp),
+            ("Bb2", NoteName::B, Octave::S2, Accidental::Flat),
+            ("a4", NoteName::A, Octave::S4, Accidental::None),
+            ("Fx0", NoteName::F, Octave::S0, Accidental::DoubleSharp),
+            (
+                "Ebd9",
+                NoteName::E,
+                Octave::S9,
+                Accidental::ThreeQuarterFlat,
+            ),
+            ("G+5", NoteName::G, Octave::S5, Accidental::QuarterSharp),
+            ("D♭3", NoteName::D, Octave::S3, Accidental::Flat),
+        ];
+        for (name, note_name, octave, accidental) in pitches {
+            let pitch: Pitch = name.parse().unwrap();
+            assert_eq!(pitch.note_name, note_name);
+            assert_eq!(pitch.octave, octave);
+            assert_eq!(pitch.accidental, accidental);
+        }
+        let mut pitch = Pitch::new(NoteName::C);
+        pitch.accidental(Accidental::Other(Alteration::new(-2, 3)));
+        assert_eq!(pitch.to_string(), "C[-2/3]3");
+        assert_eq!("C[-2/3]3".parse(), Ok(pitch));
+        assert_eq!("r".parse::<Pitch>().unwrap().to_string(), "r");
+    }
+    #[test]
+    fn test_scientific_errors() {
+        for name in [
+            "", "C", "H4", "C#b4", "C+b4", "Cd#4", "C4#", "c'", "C-", "C♮♮4",
+        ] {
+            assert_eq!(
+                name.parse::<Pitch>(),
+                Err(format!("Invalid pitch '{}'.", name))
+            );
+        }
+        assert_eq!(
+            "C-1".parse::<Pitch>(),
+            Err(String::from("Invalid octave -1 in pitch 'C-1'."))
+        );
+    }
+    #[test]
+    fn test_helmholtz() {
+        let pitches = [
+            ("c'", "C4"),
+            ("C,", "C1"),
+            ("C", "C2"),
+            ("bb", "Bb3"),
+            ("f#''''''", "F#9"),
+            ("A,,", "A0"),
+            ("r", "r"),
+        ];
+        for (helmholtz, scientific) in pitches {
+            let pitch = Pitch::from_helmholtz(helmholtz).unwrap();
+            assert_eq!(pitch.to_string(), scientific);
+            assert_eq!(pitch.to_helmholtz(), helmholtz);
+        }
+        for name in ["", "c,", "C'", "h", "c'''''''", "C,,,", "cb#"] {
+            assert_eq!(
+                Pitch::from_helmholtz(name),
+                Err(format!("Invalid Helmholtz pitch '{}'.", name))
+            );
+        }
+    }
 }
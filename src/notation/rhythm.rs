@@ -2,9 +2,17 @@
 
 use std::fmt::Display;
 
+use num_rational::Rational32;
+
+/// A length of time, measured in whole notes.
+///
+/// For example, a quarter note lasts `1/4`, and a dotted half note `3/4`.
+pub type Duration = Rational32;
+
 /// Possible note values.
 #[repr(u16)]
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Length {
     Whole = 1,
     Half = 2,
@@ -52,7 +60,8 @@ impl Default for Length {
 }
 
 /// Type of duration.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DurationType {
     Note,
     Rest,
@@ -66,7 +75,9 @@ impl Default for DurationType {
 }
 
 /// A representation of the dot(s) on a note.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Dots {
     dots: u8,
 }
@@ -83,12 +94,6 @@ impl Dots {
     }
 }
 
-impl Default for Dots {
-    fn default() -> Self {
-        Self { dots: 0 }
-    }
-}
-
 impl Display for Dots {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", ".".repeat(self.dots.into()))
@@ -96,7 +101,8 @@ impl Display for Dots {
 }
 
 /// A duration for a note.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rhythm {
     /// Length e.g. Quarter, Half, or Whole.
     pub length: Length,
@@ -106,6 +112,12 @@ pub struct Rhythm {
     pub duration_type: DurationType,
 }
 
+impl Default for Rhythm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Rhythm {
     /// Construct a new rhythm.
     ///
@@ -175,8 +187,115 @@ impl Rhythm {
     pub fn duration_type(&mut self, duration_type: DurationType) {
         self.duration_type = duration_type;
     }
+    /// Return how long the rhythm lasts, including its dots.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::rhythm::{Duration, Length, Rhythm};
+    ///
+    /// let mut rhythm = Rhythm::new();
+    /// rhythm.length(Length::Half);
+    /// rhythm.dots(2);
+    ///
+    /// assert_eq!(rhythm.duration(), Duration::new(7, 8));
+    /// ```
+    pub fn duration(&self) -> Duration {
+        let length = Duration::new(1, i32::from(self.length.as_u16()));
+        // each dot adds half of the previous value; beyond 20 dots the
+        // difference cannot be represented
+        let dots = i32::from(self.dots.get_num_dots()).min(20);
+        length * Duration::new((1 << (dots + 1)) - 1, 1 << dots)
+    }
+    /// Attempt to construct the rhythm of a single note lasting `duration`
+    /// whole notes, with as many dots as it needs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` unless a whole note to a 128th note, with dots no
+    /// shorter than a 128th note, lasts exactly `duration`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::rhythm::{Duration, Length, Rhythm};
+    ///
+    /// let rhythm = Rhythm::from_duration(Duration::new(3, 8)).unwrap();
+    /// assert_eq!(rhythm.length, Length::Quarter);
+    /// assert_eq!(rhythm.dots.get_num_dots(), 1);
+    ///
+    /// assert_eq!(
+    ///     Rhythm::from_duration(Duration::new(5, 16)),
+    ///     Err(String::from("Invalid duration 5/16 for a single note."))
+    /// );
+    /// ```
+    pub fn from_duration(duration: Duration) -> Result<Rhythm, String> {
+        Rhythm::all()
+            .find(|rhythm| rhythm.duration() == duration)
+            .ok_or_else(|| format!("Invalid duration {} for a single note.", duration))
+    }
+    /// Attempt to split `duration` into the rhythms of notes that can be tied
+    /// together to last that long, longest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` unless `duration` is positive and a whole number
+    /// of 128th notes, since tuplets are not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::rhythm::{Duration, Length, Rhythm};
+    ///
+    /// let rhythms = Rhythm::tied(Duration::new(5, 4)).unwrap();
+    /// assert_eq!(rhythms.len(), 2);
+    /// assert_eq!(rhythms[0].length, Length::Whole);
+    /// assert_eq!(rhythms[1].length, Length::Quarter);
+    /// ```
+    pub fn tied(duration: Duration) -> Result<Vec<Rhythm>, String> {
+        let shortest = Duration::new(1, i32::from(Length::OneTwentyEighth.as_u16()));
+        if duration <= Duration::from_integer(0) || !(duration / shortest).is_integer() {
+            return Err(format!("Invalid duration {} for tied notes.", duration));
+        }
+        let mut rhythms = vec![];
+        let mut remaining = duration;
+        while remaining > Duration::from_integer(0) {
+            // a 128th note always fits, so there is always a longest rhythm
+            let rhythm = Rhythm::all()
+                .filter(|rhythm| rhythm.duration() <= remaining)
+                .max_by_key(|rhythm| rhythm.duration())
+                .expect("a 128th note fits");
+            remaining -= rhythm.duration();
+            rhythms.push(rhythm);
+        }
+        Ok(rhythms)
+    }
+    /// Return every rhythm from a whole note to a 128th note, with dots no
+    /// shorter than a 128th note.
+    fn all() -> impl Iterator<Item = Rhythm> {
+        LENGTHS.iter().enumerate().flat_map(|(index, length)| {
+            (0..LENGTHS.len() - index).map(move |dots| {
+                let mut rhythm = Rhythm::new();
+                rhythm.length(*length);
+                rhythm.dots(dots as u8);
+                rhythm
+            })
+        })
+    }
 }
 
+/// Every [`Length`], from longest to shortest.
+static LENGTHS: [Length; 8] = [
+    Length::Whole,
+    Length::Half,
+    Length::Quarter,
+    Length::Eighth,
+    Length::Sixteenth,
+    Length::ThirtySecond,
+    Length::SixtyFourth,
+    Length::OneTwentyEighth,
+];
+
 #[cfg(test)]
 mod test {
     use crate::notation::rhythm::*;
@@ -200,6 +319,42 @@ mod test {
         assert_eq!(rhythm.dots.get_num_dots(), 1);
     }
     #[test]
+    fn test_duration() {
+        let mut rhythm = Rhythm::new();
+        assert_eq!(rhythm.duration(), Duration::new(1, 4));
+        rhythm.length(Length::Eighth);
+        rhythm.dots(1);
+        assert_eq!(rhythm.duration(), Duration::new(3, 16));
+        rhythm.length(Length::Whole);
+        rhythm.dots(3);
+        assert_eq!(rhythm.duration(), Duration::new(15, 8));
+    }
+    #[test]
+    fn test_from_duration() {
+        for rhythm in Rhythm::all() {
+            assert_eq!(Rhythm::from_duration(rhythm.duration()), Ok(rhythm));
+        }
+        // a 128th note cannot be dotted
+        assert!(Rhythm::from_duration(Duration::new(3, 256)).is_err());
+    }
+    #[test]
+    fn test_tied() {
+        let durations = |duration| -> Vec<Duration> {
+            Rhythm::tied(duration)
+                .unwrap()
+                .iter()
+                .map(Rhythm::duration)
+                .collect()
+        };
+        assert_eq!(durations(Duration::new(7, 8)), vec![Duration::new(7, 8)]);
+        assert_eq!(
+            durations(Duration::new(9, 8)),
+            vec![Duration::from_integer(1), Duration::new(1, 8)]
+        );
+        assert!(Rhythm::tied(Duration::new(1, 3)).is_err());
+        assert!(Rhythm::tied(Duration::from_integer(0)).is_err());
+    }
+    #[test]
     fn test_duration_type() {
         let mut rhythm = Rhythm::new();
         rhythm.duration_type(DurationType::Rest);
@@ -0,0 +1,443 @@
+//! Abstractions for scales and modes built on any tonic.
+
+use crate::notation::interval::Interval;
+use crate::notation::note::Note;
+use crate::notation::pitch::{Alteration, Pitch};
+use crate::notation::rhythm::Rhythm;
+use crate::notation::transpose::{Transpose, Transposition};
+use crate::NoteNameLanguage;
+
+/// Built-in scales and modes.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScaleType {
+    Major,
+    /// The natural minor scale, the same as [`ScaleType::Aeolian`].
+    NaturalMinor,
+    /// The natural minor scale with a raised seventh.
+    HarmonicMinor,
+    /// The ascending melodic minor scale, with a raised sixth and seventh.
+    /// It descends as [`ScaleType::NaturalMinor`].
+    MelodicMinor,
+    /// The first church mode, the same as [`ScaleType::Major`].
+    Ionian,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    /// The sixth church mode, the same as [`ScaleType::NaturalMinor`].
+    Aeolian,
+    Locrian,
+    MajorPentatonic,
+    MinorPentatonic,
+    /// Six whole tones, spelled with an augmented fourth, fifth and sixth.
+    WholeTone,
+    /// Alternating semitones and whole tones, starting with a semitone.
+    OctatonicHalfWhole,
+    /// Alternating whole tones and semitones, starting with a whole tone.
+    OctatonicWholeHalf,
+    /// All twelve semitones, spelled with sharps.
+    Chromatic,
+}
+
+impl ScaleType {
+    /// Return the intervals above the tonic of the notes of the scale, as
+    /// abbreviations for [`Interval`]'s `FromStr`.
+    fn intervals(&self) -> &'static [&'static str] {
+        match self {
+            ScaleType::Major | ScaleType::Ionian => &["P1", "M2", "M3", "P4", "P5", "M6", "M7"],
+            ScaleType::NaturalMinor | ScaleType::Aeolian => {
+                &["P1", "M2", "m3", "P4", "P5", "m6", "m7"]
+            }
+            ScaleType::HarmonicMinor => &["P1", "M2", "m3", "P4", "P5", "m6", "M7"],
+            ScaleType::MelodicMinor => &["P1", "M2", "m3", "P4", "P5", "M6", "M7"],
+            ScaleType::Dorian => &["P1", "M2", "m3", "P4", "P5", "M6", "m7"],
+            ScaleType::Phrygian => &["P1", "m2", "m3", "P4", "P5", "m6", "m7"],
+            ScaleType::Lydian => &["P1", "M2", "M3", "A4", "P5", "M6", "M7"],
+            ScaleType::Mixolydian => &["P1", "M2", "M3", "P4", "P5", "M6", "m7"],
+            ScaleType::Locrian => &["P1", "m2", "m3", "P4", "d5", "m6", "m7"],
+            ScaleType::MajorPentatonic => &["P1", "M2", "M3", "P5", "M6"],
+            ScaleType::MinorPentatonic => &["P1", "m3", "P4", "P5", "m7"],
+            ScaleType::WholeTone => &["P1", "M2", "M3", "A4", "A5", "A6"],
+            ScaleType::OctatonicHalfWhole => &["P1", "m2", "m3", "M3", "A4", "P5", "M6", "m7"],
+            ScaleType::OctatonicWholeHalf => &["P1", "M2", "m3", "P4", "d5", "m6", "M6", "M7"],
+            ScaleType::Chromatic => &[
+                "P1", "A1", "M2", "A2", "M3", "P4", "A4", "P5", "A5", "M6", "A6", "M7",
+            ],
+        }
+    }
+}
+
+/// A scale: a tonic and the pitches above it, repeating every period
+/// (usually an octave).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scale {
+    tonic: Pitch,
+    /// The transposition from the tonic to each note of the scale, starting
+    /// with the tonic itself.
+    degrees: Vec<Transposition>,
+    /// The transposition from the tonic to the tonic of the next period.
+    period: Transposition,
+}
+
+impl Scale {
+    /// Attempt to construct a built-in scale on a tonic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the tonic is a rest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Accidental, Pitch, NoteName};
+    /// use lilypond::notation::scale::{Scale, ScaleType};
+    ///
+    /// let mut tonic = Pitch::new(NoteName::E);
+    /// tonic.flatten();
+    /// let scale = Scale::new(&tonic, ScaleType::HarmonicMinor).unwrap();
+    /// let pitches = scale.pitches().unwrap();
+    ///
+    /// assert_eq!(pitches[2].note_name, NoteName::G);
+    /// assert_eq!(pitches[2].accidental, Accidental::Flat);
+    /// assert_eq!(pitches[6].note_name, NoteName::D);
+    /// assert_eq!(pitches[6].accidental, Accidental::None);
+    /// ```
+    pub fn new(tonic: &Pitch, scale_type: ScaleType) -> Result<Scale, String> {
+        let degrees = scale_type
+            .intervals()
+            .iter()
+            .map(|interval| {
+                let interval: Interval = interval.parse().expect("valid built-in interval");
+                Transposition::up(&interval)
+            })
+            .collect();
+        Scale::from_degrees(tonic, degrees, Transposition::up(&octave()))
+    }
+    /// Attempt to construct a scale from the intervals between successive
+    /// notes, e.g. `M2 M2 m2 M2 M2 M2 m2` for a major scale. The last
+    /// interval leads back to the tonic of the next period, so the steps
+    /// add up to the period of the scale.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the tonic is a rest or there are no steps.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::Interval;
+    /// use lilypond::notation::pitch::{Accidental, Pitch, NoteName};
+    /// use lilypond::notation::scale::Scale;
+    ///
+    /// // the Hungarian minor scale
+    /// let steps: Vec<Interval> = ["M2", "m2", "A2", "m2", "m2", "A2", "m2"]
+    ///     .iter()
+    ///     .map(|step| step.parse().unwrap())
+    ///     .collect();
+    /// let scale = Scale::from_steps(&Pitch::new(NoteName::A), &steps).unwrap();
+    /// let pitches = scale.pitches().unwrap();
+    ///
+    /// assert_eq!(pitches[3].note_name, NoteName::D);
+    /// assert_eq!(pitches[3].accidental, Accidental::Sharp);
+    /// ```
+    pub fn from_steps(tonic: &Pitch, steps: &[Interval]) -> Result<Scale, String> {
+        Scale::from_transpositions(tonic, steps.iter().map(Transposition::up))
+    }
+    /// Attempt to construct a scale from the number of semitones between
+    /// successive notes, e.g. `[2, 2, 1, 2, 2, 2, 1]` for a major scale.
+    ///
+    /// Heptatonic scales (seven steps) use each note name once. Other scales
+    /// are spelled with the conventional interval above the tonic for each
+    /// number of semitones, e.g. a minor third rather than an augmented
+    /// second.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the tonic is a rest or there are no steps.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Accidental, Pitch, NoteName};
+    /// use lilypond::notation::scale::Scale;
+    ///
+    /// let blues = Scale::from_semitone_steps(&Pitch::new(NoteName::C), &[3, 2, 1, 1, 3, 2])
+    ///     .unwrap();
+    /// let pitches = blues.pitches().unwrap();
+    ///
+    /// assert_eq!(pitches[1].note_name, NoteName::E);
+    /// assert_eq!(pitches[1].accidental, Accidental::Flat);
+    /// assert_eq!(pitches[3].note_name, NoteName::F);
+    /// assert_eq!(pitches[3].accidental, Accidental::Sharp);
+    /// ```
+    pub fn from_semitone_steps(tonic: &Pitch, steps: &[u8]) -> Result<Scale, String> {
+        if steps.len() == 7 {
+            let seconds = steps
+                .iter()
+                .map(|step| Transposition::from_steps(1, Alteration::from(i32::from(*step))));
+            return Scale::from_transpositions(tonic, seconds);
+        }
+        // spell each note by its distance from the tonic
+        let mut total: u8 = 0;
+        let mut previous = Transposition::default();
+        let mut transpositions = vec![];
+        for step in steps {
+            total = total
+                .checked_add(*step)
+                .ok_or_else(|| String::from("Scale steps are too large."))?;
+            let next = Transposition::up(&Interval::from_semitones(total));
+            transpositions.push(previous.inverse().then(&next));
+            previous = next;
+        }
+        Scale::from_transpositions(tonic, transpositions.into_iter())
+    }
+    /// Construct a scale from the transpositions between successive notes.
+    fn from_transpositions<I>(tonic: &Pitch, steps: I) -> Result<Scale, String>
+    where
+        I: Iterator<Item = Transposition>,
+    {
+        let mut degrees = vec![Transposition::default()];
+        for step in steps {
+            let next = degrees[degrees.len() - 1].then(&step);
+            degrees.push(next);
+        }
+        let period = degrees
+            .pop()
+            .filter(|_| !degrees.is_empty())
+            .ok_or_else(|| String::from("A scale needs at least one step."))?;
+        Scale::from_degrees(tonic, degrees, period)
+    }
+    /// Construct a scale, checking that the tonic is a pitch.
+    fn from_degrees(
+        tonic: &Pitch,
+        degrees: Vec<Transposition>,
+        period: Transposition,
+    ) -> Result<Scale, String> {
+        tonic.diatonic_number()?;
+        Ok(Scale {
+            tonic: tonic.clone(),
+            degrees,
+            period,
+        })
+    }
+    /// Return the tonic of the scale.
+    pub fn tonic(&self) -> &Pitch {
+        &self.tonic
+    }
+    /// Return the number of notes in each period of the scale.
+    pub fn len(&self) -> usize {
+        self.degrees.len()
+    }
+    /// Return whether the scale has no notes, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        self.degrees.is_empty()
+    }
+    /// Attempt to return the note of the scale `index` scale steps away from
+    /// the tonic, continuing into other periods for indices below zero or
+    /// past the end of the scale.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the pitch is outside of the octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+    /// use lilypond::notation::scale::{Scale, ScaleType};
+    ///
+    /// let scale = Scale::new(&Pitch::new(NoteName::C), ScaleType::Major).unwrap();
+    /// let ninth = scale.pitch(8).unwrap();
+    /// let below = scale.pitch(-1).unwrap();
+    ///
+    /// assert_eq!((ninth.note_name, ninth.octave), (NoteName::D, Octave::S4));
+    /// assert_eq!((below.note_name, below.octave), (NoteName::B, Octave::S2));
+    /// ```
+    pub fn pitch(&self, index: i32) -> Result<Pitch, String> {
+        let len = self.degrees.len() as i32;
+        let periods = index.div_euclid(len);
+        let degree = &self.degrees[index.rem_euclid(len) as usize];
+        let transposition = Transposition::from_steps(
+            periods * self.period.steps() + degree.steps(),
+            Alteration::from(periods) * self.period.semitones() + degree.semitones(),
+        );
+        self.tonic.transpose(&transposition)
+    }
+    /// Attempt to return the notes of one period of the scale, starting on
+    /// the tonic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a pitch is outside of the octaves C0--B9.
+    pub fn pitches(&self) -> Result<Vec<Pitch>, String> {
+        (0..self.degrees.len() as i32)
+            .map(|index| self.pitch(index))
+            .collect()
+    }
+    /// Attempt to return the notes of the scale from the tonic up to the
+    /// tonic of the next period, with `rhythm`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a pitch is outside of the octaves C0--B9.
+    pub fn notes(&self, rhythm: &Rhythm) -> Result<Vec<Note>, String> {
+        (0..=self.degrees.len() as i32)
+            .map(|index| {
+                Ok(Note {
+                    pitch: self.pitch(index)?,
+                    rhythm: rhythm.clone(),
+                    tie: false,
+                    articulations: vec![],
+                    beam: None,
+                    dynamic: None,
+                })
+            })
+            .collect()
+    }
+    /// Attempt to write the scale, from the tonic up to the tonic of the
+    /// next period in quarter notes, as LilyPond input with note names in
+    /// `language`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a pitch is outside of the octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Pitch, NoteName};
+    /// use lilypond::notation::scale::{Scale, ScaleType};
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let scale = Scale::new(&Pitch::new(NoteName::D), ScaleType::MajorPentatonic).unwrap();
+    ///
+    /// assert_eq!(
+    ///     scale.to_lilypond(NoteNameLanguage::English).unwrap(),
+    ///     "{ d4 e4 fs4 a4 b4 d'4 }"
+    /// );
+    /// ```
+    pub fn to_lilypond(&self, language: NoteNameLanguage) -> Result<String, String> {
+        let notes: Vec<String> = self
+            .notes(&Rhythm::new())?
+            .iter()
+            .map(|note| language.lilypond_from_note(note))
+            .collect();
+        Ok(format!("{{ {} }}", notes.join(" ")))
+    }
+}
+
+/// Return a perfect octave.
+fn octave() -> Interval {
+    "P8".parse().expect("valid octave")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::pitch::{Accidental, NoteName, Octave};
+    use crate::notation::scale::*;
+    fn names(scale: &Scale) -> String {
+        let notes: Vec<String> = scale
+            .pitches()
+            .unwrap()
+            .iter()
+            .map(|pitch| NoteNameLanguage::English.lilypond_from_pitch(pitch))
+            .collect();
+        notes.join(" ")
+    }
+    #[test]
+    fn test_scale_types() {
+        let mut a = Pitch::new(NoteName::A);
+        a.octave(Octave::S4);
+        let cases = [
+            (ScaleType::Major, "a' b' cs'' d'' e'' fs'' gs''"),
+            (ScaleType::NaturalMinor, "a' b' c'' d'' e'' f'' g''"),
+            (ScaleType::HarmonicMinor, "a' b' c'' d'' e'' f'' gs''"),
+            (ScaleType::MelodicMinor, "a' b' c'' d'' e'' fs'' gs''"),
+            (ScaleType::Dorian, "a' b' c'' d'' e'' fs'' g''"),
+            (ScaleType::Phrygian, "a' bf' c'' d'' e'' f'' g''"),
+            (ScaleType::Lydian, "a' b' cs'' ds'' e'' fs'' gs''"),
+            (ScaleType::Mixolydian, "a' b' cs'' d'' e'' fs'' g''"),
+            (ScaleType::Locrian, "a' bf' c'' d'' ef'' f'' g''"),
+            (ScaleType::MajorPentatonic, "a' b' cs'' e'' fs''"),
+            (ScaleType::MinorPentatonic, "a' c'' d'' e'' g''"),
+            (ScaleType::WholeTone, "a' b' cs'' ds'' es'' fss''"),
+            (
+                ScaleType::OctatonicHalfWhole,
+                "a' bf' c'' cs'' ds'' e'' fs'' g''",
+            ),
+            (
+                ScaleType::OctatonicWholeHalf,
+                "a' b' c'' d'' ef'' f'' fs'' gs''",
+            ),
+            (
+                ScaleType::Chromatic,
+                "a' as' b' bs' cs'' d'' ds'' e'' es'' fs'' fss'' gs''",
+            ),
+        ];
+        for (scale_type, expected) in cases {
+            let scale = Scale::new(&a, scale_type).unwrap();
+            assert_eq!(names(&scale), expected, "{:?}", scale_type);
+        }
+        assert_eq!(
+            Scale::new(&a, ScaleType::Ionian),
+            Scale::new(&a, ScaleType::Major)
+        );
+    }
+    #[test]
+    fn test_spelling() {
+        let mut f_sharp = Pitch::new(NoteName::F);
+        f_sharp.sharpen();
+        let scale = Scale::new(&f_sharp, ScaleType::Major).unwrap();
+        assert_eq!(names(&scale), "fs gs as b cs' ds' es'");
+        let mut g_flat = Pitch::new(NoteName::G);
+        g_flat.flatten();
+        let scale = Scale::new(&g_flat, ScaleType::Major).unwrap();
+        assert_eq!(names(&scale), "gf af bf cf' df' ef' f'");
+    }
+    #[test]
+    fn test_semitone_steps() {
+        let c = Pitch::new(NoteName::C);
+        let major = Scale::from_semitone_steps(&c, &[2, 2, 1, 2, 2, 2, 1]).unwrap();
+        assert_eq!(Ok(major), Scale::new(&c, ScaleType::Major));
+        let pentatonic = Scale::from_semitone_steps(&c, &[2, 2, 3, 2, 3]).unwrap();
+        assert_eq!(Ok(pentatonic), Scale::new(&c, ScaleType::MajorPentatonic));
+        assert!(Scale::from_semitone_steps(&c, &[]).is_err());
+        assert!(Scale::from_semitone_steps(&c, &[200, 100]).is_err());
+    }
+    #[test]
+    fn test_steps() {
+        let c = Pitch::new(NoteName::C);
+        let steps: Vec<Interval> = ["M2", "M2", "m2", "M2", "M2", "M2", "m2"]
+            .iter()
+            .map(|step| step.parse().unwrap())
+            .collect();
+        let major = Scale::from_steps(&c, &steps).unwrap();
+        assert_eq!(Ok(major), Scale::new(&c, ScaleType::Major));
+        let rest = Pitch::new(NoteName::None);
+        assert!(Scale::from_steps(&rest, &steps).is_err());
+    }
+    #[test]
+    fn test_pitch() {
+        let scale = Scale::new(&Pitch::new(NoteName::G), ScaleType::MinorPentatonic).unwrap();
+        let pitch = scale.pitch(-2).unwrap();
+        assert_eq!(pitch.note_name, NoteName::D);
+        assert_eq!(pitch.accidental, Accidental::None);
+        assert_eq!(pitch.octave, Octave::S3);
+        let pitch = scale.pitch(11).unwrap();
+        assert_eq!(pitch.note_name, NoteName::B);
+        assert_eq!(pitch.accidental, Accidental::Flat);
+        assert_eq!(pitch.octave, Octave::S5);
+        assert!(scale.pitch(100).is_err());
+        assert_eq!(scale.len(), 5);
+    }
+    #[test]
+    fn test_to_lilypond() {
+        let scale = Scale::new(&Pitch::new(NoteName::D), ScaleType::Dorian).unwrap();
+        assert_eq!(
+            scale.to_lilypond(NoteNameLanguage::Nederlands).unwrap(),
+            "{ d4 e4 f4 g4 a4 b4 c'4 d'4 }"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Abstract types for staves and whole scores.
+
+use crate::export::layout;
+use crate::notation::clef::Clef;
+use crate::notation::key::KeySignature;
+use crate::notation::measure::lilypond_duration;
+use crate::notation::rhythm::Duration;
+use crate::notation::time::TimeSignature;
+use crate::notation::voice::Voice;
+use crate::NoteNameLanguage;
+
+/// A clef, key signature or time signature taking effect partway through a
+/// staff.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StaffChange {
+    Clef(Clef),
+    KeySignature(KeySignature),
+    TimeSignature(TimeSignature),
+}
+
+/// A staff with one or more simultaneous voices.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Staff {
+    pub voices: Vec<Voice>,
+    /// The clef at the start of the staff.
+    pub clef: Clef,
+    /// The key signature at the start of the staff.
+    pub key_signature: KeySignature,
+    /// The time signature at the start of the staff.
+    pub time_signature: TimeSignature,
+    /// The length of a pickup measure in whole notes, if the staff starts
+    /// with one.
+    pub partial: Option<Duration>,
+    /// Later changes in order, each with the index of the measure it
+    /// starts, counting the first measure as 0.
+    pub changes: Vec<(usize, StaffChange)>,
+}
+
+impl Staff {
+    /// Construct a new staff without voices, in treble clef, without sharps
+    /// or flats, in 4/4.
+    pub fn new() -> Staff {
+        Default::default()
+    }
+    /// Return the clef in effect in a measure, counting the first measure as
+    /// 0.
+    pub fn clef_at(&self, measure: usize) -> Clef {
+        let mut clef = self.clef;
+        for (_, change) in self.changes_until(measure) {
+            if let StaffChange::Clef(new) = change {
+                clef = *new;
+            }
+        }
+        clef
+    }
+    /// Return the key signature in effect in a measure, counting the first
+    /// measure as 0.
+    pub fn key_signature_at(&self, measure: usize) -> &KeySignature {
+        let mut key_signature = &self.key_signature;
+        for (_, change) in self.changes_until(measure) {
+            if let StaffChange::KeySignature(new) = change {
+                key_signature = new;
+            }
+        }
+        key_signature
+    }
+    /// Return the time signature in effect in a measure, counting the first
+    /// measure as 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::score::{Staff, StaffChange};
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let mut staff = Staff::new();
+    /// let waltz = TimeSignature::from_fraction(3, 4).unwrap();
+    /// staff.changes.push((2, StaffChange::TimeSignature(waltz.clone())));
+    ///
+    /// assert_eq!(staff.time_signature_at(1), &TimeSignature::new());
+    /// assert_eq!(staff.time_signature_at(4), &waltz);
+    /// ```
+    pub fn time_signature_at(&self, measure: usize) -> &TimeSignature {
+        let mut time_signature = &self.time_signature;
+        for (_, change) in self.changes_until(measure) {
+            if let StaffChange::TimeSignature(new) = change {
+                time_signature = new;
+            }
+        }
+        time_signature
+    }
+    /// Set the clef, key signature or time signature from the start of a
+    /// measure, counting the first measure as 0. A change in the first
+    /// measure sets the staff's own attribute, a change that changes nothing
+    /// is left out, and a change replaces one of the same kind in the same
+    /// measure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::clef::Clef;
+    /// use lilypond::notation::score::{Staff, StaffChange};
+    ///
+    /// let mut staff = Staff::new();
+    /// staff.change(0, StaffChange::Clef(Clef::Bass));
+    /// staff.change(2, StaffChange::Clef(Clef::Bass));
+    /// staff.change(3, StaffChange::Clef(Clef::Tenor));
+    ///
+    /// assert_eq!(staff.clef, Clef::Bass);
+    /// assert_eq!(staff.changes, vec![(3, StaffChange::Clef(Clef::Tenor))]);
+    /// ```
+    pub fn change(&mut self, measure: usize, change: StaffChange) {
+        if measure == 0 {
+            match change {
+                StaffChange::Clef(clef) => self.clef = clef,
+                StaffChange::KeySignature(key_signature) => self.key_signature = key_signature,
+                StaffChange::TimeSignature(time_signature) => self.time_signature = time_signature,
+            }
+            return;
+        }
+        // a change of the same kind in the measure is replaced
+        self.changes.retain(|(index, other)| {
+            *index != measure || std::mem::discriminant(other) != std::mem::discriminant(&change)
+        });
+        let unchanged = match &change {
+            StaffChange::Clef(clef) => self.clef_at(measure) == *clef,
+            StaffChange::KeySignature(key_signature) => {
+                self.key_signature_at(measure) == key_signature
+            }
+            StaffChange::TimeSignature(time_signature) => {
+                self.time_signature_at(measure) == time_signature
+            }
+        };
+        if !unchanged {
+            self.changes.push((measure, change));
+        }
+    }
+    /// Write the clef, key signature and time signature set in a measure as
+    /// LilyPond: all of them in the first measure, and only the changes in
+    /// later ones.
+    fn lilypond_attributes(&self, measure: usize, language: NoteNameLanguage) -> Vec<String> {
+        if measure == 0 {
+            let mut attributes = vec![
+                self.clef.to_string(),
+                self.key_signature.to_lilypond(language),
+                self.time_signature.to_lilypond(),
+            ];
+            if let Some(partial) = self.partial {
+                attributes.push(format!("\\partial {}", lilypond_duration(partial)));
+            }
+            return attributes;
+        }
+        self.changes
+            .iter()
+            .filter(|(index, _)| *index == measure)
+            .map(|(_, change)| match change {
+                StaffChange::Clef(clef) => clef.to_string(),
+                StaffChange::KeySignature(key_signature) => key_signature.to_lilypond(language),
+                StaffChange::TimeSignature(time_signature) => time_signature.to_lilypond(),
+            })
+            .collect()
+    }
+    fn changes_until(&self, measure: usize) -> impl Iterator<Item = &(usize, StaffChange)> {
+        self.changes
+            .iter()
+            .filter(move |(index, _)| *index <= measure)
+    }
+}
+
+impl From<Voice> for Staff {
+    fn from(voice: Voice) -> Self {
+        Staff {
+            voices: vec![voice],
+            ..Default::default()
+        }
+    }
+}
+
+/// A score of staves, from top to bottom.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Score {
+    pub staves: Vec<Staff>,
+}
+
+impl Score {
+    /// Construct a new score without staves.
+    pub fn new() -> Score {
+        Score { staves: vec![] }
+    }
+    /// Attempt to write the score as a LilyPond document with note names in
+    /// `language`, with a `\new Staff` for each staff and the voices of a
+    /// staff separated by `\\`. Each staff starts with its clef, key
+    /// signature and time signature, and its changes are written before the
+    /// measures they start.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a staff's time signature is invalid or if an
+    /// event crossing a barline cannot be split into notes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::clef::Clef;
+    /// use lilypond::notation::score::{Score, Staff};
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = |notes| Voice::from(parse_notes(notes, NoteNameLanguage::English).unwrap());
+    /// let mut lower = Staff::from(voice("c2 g,"));
+    /// lower.clef = Clef::Bass;
+    /// lower.voices.push(voice("e,1 c,1"));
+    /// let mut score = Score::from(Staff::from(voice("e'4 d' c'2")));
+    /// score.staves.push(lower);
+    ///
+    /// assert_eq!(
+    ///     score.to_lilypond(NoteNameLanguage::English).unwrap(),
+    ///     "\\language \"english\"\n\n\
+    ///      <<\n  \
+    ///      \\new Staff { \\clef treble \\key c \\major \\time 4/4 e'4 d'4 c'2 }\n  \
+    ///      \\new Staff << { \\clef bass \\key c \\major \\time 4/4 c2 g,2 | R1 } \\\\ { e,1 | c,1 } >>\n\
+    ///      >>\n"
+    /// );
+    /// ```
+    pub fn to_lilypond(&self, language: NoteNameLanguage) -> Result<String, String> {
+        let mut staves = vec![];
+        for staff in &self.staves {
+            let measures = layout(staff)?;
+            let mut voices = vec![];
+            for voice in 0..staff.voices.len() {
+                let mut music = vec![];
+                for (index, measure) in measures.iter().enumerate() {
+                    let mut events = vec![];
+                    if voice == 0 {
+                        events.extend(staff.lilypond_attributes(index, language));
+                    }
+                    let length = match staff.partial {
+                        Some(partial) if index == 0 => partial,
+                        _ => staff.time_signature_at(index).measure_duration(),
+                    };
+                    if measure[voice].is_empty() {
+                        let rest = if voice == 0 { "R" } else { "s" };
+                        events.push(format!("{}{}", rest, lilypond_duration(length)));
+                    }
+                    events.extend(
+                        measure[voice]
+                            .iter()
+                            .map(|placed| language.lilypond_from_event(&placed.event)),
+                    );
+                    music.push(events.join(" "));
+                }
+                voices.push(format!("{{ {} }}", music.join(" | ")));
+            }
+            staves.push(match voices.len() {
+                0 => String::from("\\new Staff { }"),
+                1 => format!("\\new Staff {}", voices[0]),
+                _ => format!("\\new Staff << {} >>", voices.join(" \\\\ ")),
+            });
+        }
+        Ok(format!(
+            "\\language \"{}\"\n\n<<\n{}>>\n",
+            language,
+            staves
+                .iter()
+                .map(|staff| format!("  {}\n", staff))
+                .collect::<String>()
+        ))
+    }
+}
+
+impl From<Staff> for Score {
+    fn from(staff: Staff) -> Self {
+        Score {
+            staves: vec![staff],
+        }
+    }
+}
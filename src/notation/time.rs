@@ -1,11 +1,31 @@
 //! Abstractions for defining time signatures.
 
-#[derive(PartialEq, Debug)]
+use std::convert::TryFrom;
+
+use crate::notation::rhythm::Duration;
+
+/// A time signature, e.g. 3/4, 6/8 or `3+2/8`.
+///
+/// The constructors [`from_fraction`](TimeSignature::from_fraction) and
+/// [`additive`](TimeSignature::additive) and the setters check that the time
+/// signature is one LilyPond can write. The fields are public, but a time
+/// signature built with a struct literal, changed through its fields or
+/// deserialized is not checked; call [`validate`](TimeSignature::validate)
+/// on it before relying on it.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeSignature {
     /// The number of beats per measure.
     pub numerator: u8,
     /// The fraction of a whole note representing the length of a beat.
     pub denominator: u8,
+    /// How the measure is grouped into beats, in units of the denominator,
+    /// e.g. `[2, 2, 3]` for 7/8 counted 2+2+3. If this is empty, the beats
+    /// are the [default](TimeSignature::groups) for the time signature.
+    pub beat_structure: Vec<u8>,
+    /// Whether the numerator is written as the sum of the beat structure,
+    /// e.g. `3+2/8` rather than `5/8`.
+    pub additive: bool,
 }
 
 impl Default for TimeSignature {
@@ -14,6 +34,8 @@ impl Default for TimeSignature {
         TimeSignature {
             numerator: 4,
             denominator: 4,
+            beat_structure: vec![],
+            additive: false,
         }
     }
 }
@@ -36,9 +58,86 @@ impl TimeSignature {
     pub fn new() -> TimeSignature {
         Default::default()
     }
-    /// Set number of beats.
+    /// Attempt to construct a time signature of `numerator` beats of
+    /// `denominator` each, e.g. `6` and `8` for 6/8.
+    ///
+    /// # Errors
     ///
-    /// This will set the numerator of a time signature.
+    /// Returns `Err(String)` if the numerator is zero, or if the denominator
+    /// is not a power of two from 1 to 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let time_signature = TimeSignature::from_fraction(6, 8).unwrap();
+    /// assert!(time_signature.is_compound());
+    ///
+    /// assert_eq!(
+    ///     TimeSignature::from_fraction(3, 5),
+    ///     Err(String::from("Invalid time signature '3/5'."))
+    /// );
+    /// ```
+    pub fn from_fraction(numerator: u8, denominator: u8) -> Result<TimeSignature, String> {
+        let time_signature = TimeSignature {
+            numerator,
+            denominator,
+            ..Default::default()
+        };
+        time_signature.validate()?;
+        Ok(time_signature)
+    }
+    /// Attempt to construct an additive time signature, whose numerator is
+    /// written as a sum of beat groups, e.g. `[3, 2]` and `8` for `3+2/8`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if there are no groups, a group is zero, the
+    /// groups add up to more than 255, or the denominator is not a power of
+    /// two from 1 to 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let time_signature = TimeSignature::additive(&[3, 2], 8).unwrap();
+    ///
+    /// assert_eq!(time_signature.numerator, 5);
+    /// assert_eq!(time_signature.beat_structure, vec![3, 2]);
+    /// assert_eq!(time_signature.to_string(), "3+2/8");
+    /// ```
+    pub fn additive(groups: &[u8], denominator: u8) -> Result<TimeSignature, String> {
+        let error = || {
+            let groups: Vec<String> = groups.iter().map(u8::to_string).collect();
+            format!(
+                "Invalid time signature '{}/{}'.",
+                groups.join("+"),
+                denominator
+            )
+        };
+        let numerator = groups.iter().map(|group| u32::from(*group)).sum::<u32>();
+        if groups.is_empty() {
+            return Err(error());
+        }
+        let time_signature = TimeSignature {
+            numerator: u8::try_from(numerator).map_err(|_| error())?,
+            denominator,
+            beat_structure: groups.to_vec(),
+            additive: true,
+        };
+        time_signature.validate()?;
+        Ok(time_signature)
+    }
+    /// Attempt to set number of beats.
+    ///
+    /// This will set the numerator of a time signature, and reset its beat
+    /// structure to the default.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the numerator is zero.
     ///
     /// # Examples
     ///
@@ -46,37 +145,329 @@ impl TimeSignature {
     /// use lilypond::notation::time::TimeSignature;
     ///
     /// let mut time_signature = TimeSignature::new();
-    /// time_signature.numerator(7);
+    /// time_signature.numerator(7).unwrap();
     ///
     /// assert_eq!(time_signature.numerator, 7);
     /// assert_eq!(time_signature.denominator, 4);
+    /// assert!(time_signature.numerator(0).is_err());
     /// ```
-    pub fn numerator(&mut self, numerator: u8) {
+    pub fn numerator(&mut self, numerator: u8) -> Result<(), String> {
+        TimeSignature::from_fraction(numerator, self.denominator)?;
         self.numerator = numerator;
+        self.beat_structure = vec![];
+        self.additive = false;
+        Ok(())
     }
-    /// Set length of beat.
+    /// Attempt to set length of beat.
     ///
     /// This will set the denominator of a time signature.
     ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the denominator is not a power of two from 1
+    /// to 128.
+    ///
     /// # Examples
     ///
     /// ```
     /// use lilypond::notation::time::TimeSignature;
     ///
     /// let mut time_signature = TimeSignature::new();
-    /// time_signature.denominator(8);
+    /// time_signature.denominator(8).unwrap();
     ///
     /// assert_eq!(time_signature.numerator, 4);
     /// assert_eq!(time_signature.denominator, 8);
+    /// assert!(time_signature.denominator(6).is_err());
     /// ```
-    pub fn denominator(&mut self, denominator: u8) {
+    pub fn denominator(&mut self, denominator: u8) -> Result<(), String> {
+        TimeSignature::from_fraction(self.numerator, denominator)?;
         self.denominator = denominator;
+        Ok(())
+    }
+    /// Attempt to set grouping of beats.
+    ///
+    /// This will set the beat structure of a time signature, in units of
+    /// its denominator.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a group is zero or the groups do not add up
+    /// to the numerator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let mut time_signature = TimeSignature::from_fraction(7, 8).unwrap();
+    /// time_signature.beat_structure(vec![2, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(time_signature.beat_structure, vec![2, 2, 3]);
+    /// assert!(time_signature.beat_structure(vec![2, 2]).is_err());
+    /// ```
+    pub fn beat_structure(&mut self, beat_structure: Vec<u8>) -> Result<(), String> {
+        let time_signature = TimeSignature {
+            beat_structure,
+            ..self.clone()
+        };
+        time_signature.validate()?;
+        *self = time_signature;
+        Ok(())
+    }
+    /// Check that the time signature is one that LilyPond can write, for one
+    /// built from its fields rather than with
+    /// [`from_fraction`](TimeSignature::from_fraction) or
+    /// [`additive`](TimeSignature::additive).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the numerator is zero, the denominator is not
+    /// a power of two from 1 to 128, or the beat structure has a zero group
+    /// or does not add up to the numerator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let mut time_signature = TimeSignature::new();
+    /// time_signature.beat_structure = vec![2, 1];
+    ///
+    /// assert_eq!(
+    ///     time_signature.validate(),
+    ///     Err(String::from("Beat structure 2+1 does not fill a measure of 4/4."))
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        if self.numerator == 0 || !self.denominator.is_power_of_two() || self.denominator > 128 {
+            return Err(format!(
+                "Invalid time signature '{}/{}'.",
+                self.numerator, self.denominator
+            ));
+        }
+        let sum = self
+            .beat_structure
+            .iter()
+            .map(|group| u32::from(*group))
+            .sum::<u32>();
+        if self.beat_structure.contains(&0)
+            || (!self.beat_structure.is_empty() && sum != u32::from(self.numerator))
+        {
+            let groups: Vec<String> = self.beat_structure.iter().map(u8::to_string).collect();
+            return Err(format!(
+                "Beat structure {} does not fill a measure of {}/{}.",
+                groups.join("+"),
+                self.numerator,
+                self.denominator
+            ));
+        }
+        Ok(())
+    }
+    /// Return whether the time signature is a compound meter, whose beats
+    /// are each divided into three, like 6/8, 9/8, 12/8 or 6/4.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// assert!(TimeSignature::from_fraction(12, 8).unwrap().is_compound());
+    /// assert!(!TimeSignature::from_fraction(3, 8).unwrap().is_compound());
+    /// assert!(!TimeSignature::additive(&[3, 3, 2], 8).unwrap().is_compound());
+    /// ```
+    pub fn is_compound(&self) -> bool {
+        self.numerator > 3
+            && self.numerator % 3 == 0
+            && self.beat_structure.iter().all(|group| *group == 3)
+    }
+    /// Return the groups of the beat structure, in units of the denominator.
+    ///
+    /// Without an explicit beat structure, compound meters and 3/8 are
+    /// grouped in threes and every other meter has one group per denominator
+    /// unit, as in LilyPond.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let time_signature = TimeSignature::from_fraction(6, 8).unwrap();
+    /// assert_eq!(time_signature.groups(), vec![3, 3]);
+    ///
+    /// let time_signature = TimeSignature::from_fraction(3, 4).unwrap();
+    /// assert_eq!(time_signature.groups(), vec![1, 1, 1]);
+    /// ```
+    pub fn groups(&self) -> Vec<u8> {
+        if !self.beat_structure.is_empty() {
+            self.beat_structure.clone()
+        } else if self.is_compound() || (self.numerator == 3 && self.denominator >= 8) {
+            vec![3; usize::from(self.numerator / 3)]
+        } else {
+            vec![1; usize::from(self.numerator)]
+        }
+    }
+    /// Return the duration of each beat of the measure, in whole notes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::rhythm::Duration;
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let time_signature = TimeSignature::additive(&[3, 2], 8).unwrap();
+    ///
+    /// assert_eq!(
+    ///     time_signature.beats(),
+    ///     vec![Duration::new(3, 8), Duration::new(1, 4)]
+    /// );
+    /// ```
+    pub fn beats(&self) -> Vec<Duration> {
+        self.groups()
+            .into_iter()
+            .map(|group| Duration::new(i32::from(group), i32::from(self.denominator)))
+            .collect()
+    }
+    /// Return the duration of one beat, in whole notes: a dotted beat for a
+    /// compound meter, and the denominator otherwise. The beats of an
+    /// additive or irregular meter differ; see
+    /// [`beats`](TimeSignature::beats).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::rhythm::Duration;
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let six_eight = TimeSignature::from_fraction(6, 8).unwrap();
+    /// assert_eq!(six_eight.beat_duration(), Duration::new(3, 8));
+    ///
+    /// let three_four = TimeSignature::from_fraction(3, 4).unwrap();
+    /// assert_eq!(three_four.beat_duration(), Duration::new(1, 4));
+    /// ```
+    pub fn beat_duration(&self) -> Duration {
+        let unit = Duration::new(1, i32::from(self.denominator));
+        if self.is_compound() {
+            unit * 3
+        } else {
+            unit
+        }
+    }
+    /// Return the duration of a whole measure, in whole notes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::rhythm::Duration;
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let time_signature = TimeSignature::additive(&[2, 2, 3], 8).unwrap();
+    ///
+    /// assert_eq!(time_signature.measure_duration(), Duration::new(7, 8));
+    /// ```
+    pub fn measure_duration(&self) -> Duration {
+        Duration::new(i32::from(self.numerator), i32::from(self.denominator))
+    }
+    /// Return the LilyPond command for the time signature: `\time 6/8`,
+    /// `\time 2,2,3 7/8` with an explicit beat structure, or
+    /// `\compoundMeter #'((3 2 8))` for an additive one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let time_signature = TimeSignature::from_fraction(6, 8).unwrap();
+    /// assert_eq!(time_signature.to_lilypond(), "\\time 6/8");
+    ///
+    /// let mut time_signature = TimeSignature::from_fraction(7, 8).unwrap();
+    /// time_signature.beat_structure(vec![2, 2, 3]).unwrap();
+    /// assert_eq!(time_signature.to_lilypond(), "\\time 2,2,3 7/8");
+    ///
+    /// let time_signature = TimeSignature::additive(&[3, 2], 8).unwrap();
+    /// assert_eq!(time_signature.to_lilypond(), "\\compoundMeter #'((3 2 8))");
+    /// ```
+    pub fn to_lilypond(&self) -> String {
+        if self.additive {
+            let groups: Vec<String> = self.groups().iter().map(u8::to_string).collect();
+            format!(
+                "\\compoundMeter #'(({} {}))",
+                groups.join(" "),
+                self.denominator
+            )
+        } else if !self.beat_structure.is_empty() {
+            let groups: Vec<String> = self.beat_structure.iter().map(u8::to_string).collect();
+            format!(
+                "\\time {} {}/{}",
+                groups.join(","),
+                self.numerator,
+                self.denominator
+            )
+        } else {
+            format!("\\time {}/{}", self.numerator, self.denominator)
+        }
+    }
+    /// Return the LilyPond command setting the beat structure, e.g.
+    /// `\set Timing.beatStructure = 2,2,3`, which must come after the
+    /// `\time` it applies to. [`to_lilypond`](TimeSignature::to_lilypond)
+    /// already includes any explicit beat structure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lilypond::notation::time::TimeSignature;
+    ///
+    /// let mut time_signature = TimeSignature::from_fraction(7, 8).unwrap();
+    /// time_signature.beat_structure(vec![2, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     time_signature.lilypond_beat_structure(),
+    ///     "\\set Timing.beatStructure = 2,2,3"
+    /// );
+    /// ```
+    pub fn lilypond_beat_structure(&self) -> String {
+        let groups: Vec<String> = self.groups().iter().map(u8::to_string).collect();
+        format!("\\set Timing.beatStructure = {}", groups.join(","))
+    }
+}
+
+impl std::fmt::Display for TimeSignature {
+    /// Write the time signature as a fraction, e.g. `6/8`, or `3+2/8` if it
+    /// is additive.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.additive {
+            let groups: Vec<String> = self.groups().iter().map(u8::to_string).collect();
+            write!(f, "{}/{}", groups.join("+"), self.denominator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl std::str::FromStr for TimeSignature {
+    type Err = String;
+
+    /// Parse a time signature written as a fraction, e.g. `6/8`, or with an
+    /// additive numerator, e.g. `3+2/8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid time signature '{}'.", s);
+        let (numerator, denominator) = s.split_once('/').ok_or_else(error)?;
+        let denominator = denominator.parse().map_err(|_| error())?;
+        let groups = numerator
+            .split('+')
+            .map(str::parse)
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| error())?;
+        match groups.as_slice() {
+            [numerator] => TimeSignature::from_fraction(*numerator, denominator),
+            _ => TimeSignature::additive(&groups, denominator),
+        }
+        .map_err(|_| error())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::notation::time::TimeSignature;
+    use crate::notation::time::*;
     #[test]
     fn test_new() {
         let time_signature = TimeSignature::new();
@@ -86,13 +477,86 @@ mod test {
     #[test]
     fn test_numerator() {
         let mut time_signature = TimeSignature::new();
-        time_signature.numerator(7);
+        time_signature.numerator(7).unwrap();
+        assert_eq!(time_signature.numerator, 7);
+        assert!(time_signature.numerator(0).is_err());
         assert_eq!(time_signature.numerator, 7);
     }
     #[test]
     fn test_denominator() {
         let mut time_signature = TimeSignature::new();
-        time_signature.denominator(8);
+        time_signature.denominator(8).unwrap();
         assert_eq!(time_signature.denominator, 8);
+        for denominator in [0, 3, 12, 255] {
+            assert!(time_signature.denominator(denominator).is_err());
+        }
+        assert_eq!(time_signature.denominator, 8);
+    }
+    #[test]
+    fn test_groups() {
+        let mut time_signature = TimeSignature::new();
+        assert_eq!(time_signature.groups(), vec![1, 1, 1, 1]);
+        time_signature.numerator(12).unwrap();
+        time_signature.denominator(8).unwrap();
+        assert_eq!(time_signature.groups(), vec![3, 3, 3, 3]);
+        time_signature.numerator(3).unwrap();
+        assert_eq!(time_signature.groups(), vec![3]);
+        time_signature.numerator(7).unwrap();
+        time_signature.beat_structure(vec![3, 4]).unwrap();
+        assert_eq!(time_signature.groups(), vec![3, 4]);
+        assert_eq!(
+            time_signature.beats(),
+            vec![Duration::new(3, 8), Duration::new(1, 2)]
+        );
+        // a new numerator resets the beat structure
+        time_signature.numerator(9).unwrap();
+        assert_eq!(time_signature.groups(), vec![3, 3, 3]);
+    }
+    #[test]
+    fn test_beat_structure_errors() {
+        let mut time_signature = TimeSignature::from_fraction(5, 8).unwrap();
+        assert!(time_signature.beat_structure(vec![3, 3]).is_err());
+        assert!(time_signature.beat_structure(vec![5, 0]).is_err());
+        assert!(time_signature.beat_structure.is_empty());
+        assert!(TimeSignature::additive(&[], 8).is_err());
+        assert!(TimeSignature::additive(&[2, 0], 8).is_err());
+        assert!(TimeSignature::additive(&[200, 100], 8).is_err());
+    }
+    #[test]
+    fn test_compound() {
+        for (time, compound, beat) in [
+            ("6/8", true, Duration::new(3, 8)),
+            ("9/16", true, Duration::new(3, 16)),
+            ("6/4", true, Duration::new(3, 4)),
+            ("3/8", false, Duration::new(1, 8)),
+            ("4/4", false, Duration::new(1, 4)),
+            ("3+3+2/8", false, Duration::new(1, 8)),
+        ] {
+            let time_signature: TimeSignature = time.parse().unwrap();
+            assert_eq!(time_signature.is_compound(), compound);
+            assert_eq!(time_signature.beat_duration(), beat);
+        }
+    }
+    #[test]
+    fn test_parse_display() {
+        for time in ["4/4", "6/8", "3+2/8", "2+2+3/16", "1/1"] {
+            let time_signature: TimeSignature = time.parse().unwrap();
+            assert_eq!(time_signature.to_string(), time);
+        }
+        for time in ["4", "4/", "/4", "3/0", "0/4", "3+/8", "3.5/8", "4/256"] {
+            assert_eq!(
+                time.parse::<TimeSignature>(),
+                Err(format!("Invalid time signature '{}'.", time))
+            );
+        }
+    }
+    #[test]
+    fn test_to_lilypond() {
+        let time_signature: TimeSignature = "2+2+3/8".parse().unwrap();
+        assert_eq!(
+            time_signature.to_lilypond(),
+            "\\compoundMeter #'((2 2 3 8))"
+        );
+        assert_eq!(TimeSignature::new().to_lilypond(), "\\time 4/4");
     }
 }
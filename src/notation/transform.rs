@@ -0,0 +1,463 @@
+//! Melodic transformations of voices: retrograde, inversion, augmentation
+//! and diminution, rotation, and octave displacement.
+//!
+//! Each transformation returns a new voice. Articulations stay with the
+//! notes they are attached to, and ties are kept wherever the notes they join
+//! are still next to each other with the same pitches.
+
+use crate::notation::beam::Beam;
+use crate::notation::pitch::{Alteration, Pitch};
+use crate::notation::rhythm::{Duration, DurationType, Rhythm};
+use crate::notation::scale::Scale;
+use crate::notation::voice::{Event, Voice};
+
+impl Voice {
+    /// Return the voice backwards. A tie from one note to the next becomes a
+    /// tie from the next to the one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c4-. d2~ d8 e8", NoteNameLanguage::English).unwrap());
+    /// let retrograde = voice.retrograde();
+    /// let expected = Voice::from(parse_notes("e8 d8~ d2 c4-.", NoteNameLanguage::English).unwrap());
+    ///
+    /// assert_eq!(retrograde, expected);
+    /// ```
+    pub fn retrograde(&self) -> Voice {
+        let mut events: Vec<Event> = self.events.iter().rev().cloned().collect();
+        let ties: Vec<bool> = events.iter().map(tie).collect();
+        for (index, event) in events.iter_mut().enumerate() {
+            set_tie(event, ties.get(index + 1).cloned().unwrap_or(false));
+        }
+        Voice { events }
+    }
+    /// Attempt to invert the voice chromatically around an axis pitch, so
+    /// that each interval above the axis becomes the same interval below it.
+    /// Rests are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the axis is a rest or if an inverted pitch
+    /// would be outside of the octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c'4 e' g' r", NoteNameLanguage::English).unwrap());
+    /// let mut axis = Pitch::new(NoteName::C);
+    /// axis.octave(Octave::S4);
+    /// let inverted = voice.invert_chromatic(&axis).unwrap();
+    /// let expected = Voice::from(parse_notes("c'4 af f r", NoteNameLanguage::English).unwrap());
+    ///
+    /// assert_eq!(inverted, expected);
+    /// ```
+    pub fn invert_chromatic(&self, axis: &Pitch) -> Result<Voice, String> {
+        let (axis_steps, axis_semitones) = (axis.diatonic_number()?, axis.semitones()?);
+        self.map_pitches(|pitch| {
+            Pitch::from_diatonic_number(
+                2 * axis_steps - pitch.diatonic_number()?,
+                axis_semitones * 2 - pitch.semitones()?,
+            )
+        })
+    }
+    /// Attempt to invert the voice diatonically within a scale around an
+    /// axis pitch, so that each step of the scale above the axis becomes a
+    /// step below it. Notes outside of the scale keep their alteration from
+    /// the scale, mirrored, e.g. a raised note becomes a lowered one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the axis is a rest, if the scale does not
+    /// have one note for each of the seven note names, or if an inverted
+    /// pitch would be outside of the octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Pitch, NoteName, Octave};
+    /// use lilypond::notation::scale::{Scale, ScaleType};
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let mut e = Pitch::new(NoteName::E);
+    /// e.octave(Octave::S4);
+    /// let c_major = Scale::new(&Pitch::new(NoteName::C), ScaleType::Major).unwrap();
+    /// let voice = Voice::from(parse_notes("e'4 f' g' e'", NoteNameLanguage::English).unwrap());
+    ///
+    /// let inverted = voice.invert_diatonic(&e, &c_major).unwrap();
+    /// let expected = Voice::from(parse_notes("e'4 d' c' e'", NoteNameLanguage::English).unwrap());
+    ///
+    /// assert_eq!(inverted, expected);
+    /// ```
+    pub fn invert_diatonic(&self, axis: &Pitch, scale: &Scale) -> Result<Voice, String> {
+        let tonic_steps = scale.tonic().diatonic_number()?;
+        for index in 0..=7 {
+            if scale.len() != 7 || scale.pitch(index)?.diatonic_number()? != tonic_steps + index {
+                return Err(String::from(
+                    "Diatonic inversion needs a scale with one note for each note name.",
+                ));
+            }
+        }
+        let axis_steps = axis.diatonic_number()?;
+        self.map_pitches(|pitch| {
+            let steps = pitch.diatonic_number()?;
+            let alteration = pitch.semitones()? - scale.pitch(steps - tonic_steps)?.semitones()?;
+            let inverted_steps = 2 * axis_steps - steps;
+            let inverted = scale.pitch(inverted_steps - tonic_steps)?;
+            Pitch::from_diatonic_number(inverted_steps, inverted.semitones()? - alteration)
+        })
+    }
+    /// Attempt to multiply the durations of the voice by a factor, which
+    /// augments it if the factor is greater than 1 and diminishes it if it
+    /// is less. Notes that no single note value can represent are split into
+    /// tied notes, with the articulations on the first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the factor is not positive, or if a new
+    /// duration is not a whole number of 128th notes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::rhythm::Duration;
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c4-> d8 e8", NoteNameLanguage::English).unwrap());
+    ///
+    /// let augmented = voice.augment(Duration::new(5, 2)).unwrap();
+    /// let expected =
+    ///     Voice::from(parse_notes("c2->~ c8 d4~ d16 e4~ e16", NoteNameLanguage::English).unwrap());
+    /// assert_eq!(augmented, expected);
+    ///
+    /// let diminished = voice.augment(Duration::new(1, 2)).unwrap();
+    /// let expected = Voice::from(parse_notes("c8-> d16 e16", NoteNameLanguage::English).unwrap());
+    /// assert_eq!(diminished, expected);
+    /// ```
+    pub fn augment(&self, factor: Duration) -> Result<Voice, String> {
+        if factor <= Duration::from_integer(0) {
+            return Err(format!("Invalid augmentation factor {}.", factor));
+        }
+        let mut events = vec![];
+        for event in &self.events {
+            let rhythms = Rhythm::tied(rhythm(event).duration() * factor)?;
+            events.extend(split(event, rhythms));
+        }
+        Ok(Voice { events })
+    }
+    /// Return the voice rotated by a number of events: to the left, so that
+    /// it starts with event `steps`, if `steps` is positive, and to the right
+    /// if it is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c4 d e~ e", NoteNameLanguage::English).unwrap());
+    ///
+    /// let expected = Voice::from(parse_notes("e4~ e c d", NoteNameLanguage::English).unwrap());
+    /// assert_eq!(voice.rotate(2), expected);
+    /// assert_eq!(voice.rotate(-2), expected);
+    /// ```
+    pub fn rotate(&self, steps: isize) -> Voice {
+        let mut events = self.events.clone();
+        if !events.is_empty() {
+            let steps = steps.rem_euclid(events.len() as isize) as usize;
+            events.rotate_left(steps);
+        }
+        untie_changed(Voice { events })
+    }
+    /// Attempt to move the events of the voice by numbers of octaves, taken
+    /// in turn from `octaves` and repeated as needed, e.g. `[1]` to move the
+    /// whole voice up an octave or `[0, -1]` to move every second event down
+    /// one. Rests are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if `octaves` is empty, or if a displaced pitch
+    /// would be outside of the octaves C0--B9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::voice::Voice;
+    /// use lilypond::parser::parse_notes;
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let voice = Voice::from(parse_notes("c'4 d' e' f'", NoteNameLanguage::English).unwrap());
+    /// let displaced = voice.displace(&[0, -1]).unwrap();
+    /// let expected = Voice::from(parse_notes("c'4 d e' f", NoteNameLanguage::English).unwrap());
+    ///
+    /// assert_eq!(displaced, expected);
+    /// ```
+    pub fn displace(&self, octaves: &[i32]) -> Result<Voice, String> {
+        if octaves.is_empty() {
+            return Err(String::from("Expected at least one octave displacement."));
+        }
+        let mut events = vec![];
+        for (event, octaves) in self.events.iter().zip(octaves.iter().cycle()) {
+            let displace = |pitch: &Pitch| -> Result<Pitch, String> {
+                Pitch::from_diatonic_number(
+                    pitch.diatonic_number()? + 7 * octaves,
+                    pitch.semitones()? + Alteration::from(12 * octaves),
+                )
+            };
+            events.push(map_event(event, &displace)?);
+        }
+        Ok(untie_changed(Voice { events }))
+    }
+    /// Attempt to replace the pitch of each note and chord, leaving rests and
+    /// everything else as it is.
+    fn map_pitches<F>(&self, f: F) -> Result<Voice, String>
+    where
+        F: Fn(&Pitch) -> Result<Pitch, String>,
+    {
+        Ok(Voice {
+            events: self
+                .events
+                .iter()
+                .map(|event| map_event(event, &f))
+                .collect::<Result<Vec<Event>, String>>()?,
+        })
+    }
+}
+
+/// Attempt to replace the pitches of an event, leaving rests as they are.
+fn map_event<F>(event: &Event, f: &F) -> Result<Event, String>
+where
+    F: Fn(&Pitch) -> Result<Pitch, String>,
+{
+    let map = |pitch: &Pitch| match pitch.semitones() {
+        Ok(_) => f(pitch),
+        Err(_) => Ok(pitch.clone()),
+    };
+    let mut event = event.clone();
+    match &mut event {
+        Event::Note(note) => {
+            if note.rhythm.duration_type == DurationType::Note {
+                note.pitch = map(&note.pitch)?;
+            }
+        }
+        Event::Chord(chord) => {
+            chord.pitches = chord
+                .pitches
+                .iter()
+                .map(map)
+                .collect::<Result<Vec<Pitch>, String>>()?;
+        }
+    }
+    Ok(event)
+}
+
+/// Split an event into pieces with the given rhythms, tied together unless
+/// the event is a rest. The articulations and dynamic stay on the first
+/// piece, a beam starts on the first piece and ends on the last one, and the
+/// last piece keeps the event's own tie.
+pub(crate) fn split(event: &Event, rhythms: Vec<Rhythm>) -> Vec<Event> {
+    let rest = rhythm(event).duration_type == DurationType::Rest;
+    let last = rhythms.len().saturating_sub(1);
+    let beam = |beam: Option<Beam>, index: usize| match beam {
+        Some(Beam::Start) if index == 0 => Some(Beam::Start),
+        Some(Beam::End) if index == last => Some(Beam::End),
+        _ => None,
+    };
+    let mut pieces = vec![];
+    for (index, new_rhythm) in rhythms.into_iter().enumerate() {
+        let mut piece = event.clone();
+        match &mut piece {
+            Event::Note(note) => {
+                note.rhythm = Rhythm {
+                    duration_type: note.rhythm.duration_type,
+                    ..new_rhythm
+                };
+                note.beam = beam(note.beam, index);
+                if index > 0 {
+                    note.articulations.clear();
+                    note.dynamic = None;
+                }
+            }
+            Event::Chord(chord) => {
+                chord.rhythm = Rhythm {
+                    duration_type: chord.rhythm.duration_type,
+                    ..new_rhythm
+                };
+                chord.beam = beam(chord.beam, index);
+                if index > 0 {
+                    chord.articulations.clear();
+                    chord.dynamic = None;
+                }
+            }
+        }
+        if index < last {
+            set_tie(&mut piece, !rest);
+        }
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Return the rhythm of an event.
+pub(crate) fn rhythm(event: &Event) -> &Rhythm {
+    match event {
+        Event::Note(note) => &note.rhythm,
+        Event::Chord(chord) => &chord.rhythm,
+    }
+}
+
+/// Return whether an event is tied to the next one.
+fn tie(event: &Event) -> bool {
+    match event {
+        Event::Note(note) => note.tie,
+        Event::Chord(chord) => chord.tie,
+    }
+}
+
+/// Choose whether an event is tied to the next one.
+fn set_tie(event: &mut Event, tie: bool) {
+    match event {
+        Event::Note(note) => note.tie = tie,
+        Event::Chord(chord) => chord.tie = tie,
+    }
+}
+
+/// Remove the ties of events that are not followed by the same pitches.
+fn untie_changed(mut voice: Voice) -> Voice {
+    let pitches: Vec<Option<Vec<Pitch>>> = voice
+        .events
+        .iter()
+        .map(|event| match event {
+            Event::Note(note) if note.rhythm.duration_type == DurationType::Note => {
+                Some(vec![note.pitch.clone()])
+            }
+            Event::Chord(chord) => Some(chord.pitches.clone()),
+            _ => None,
+        })
+        .collect();
+    for (index, event) in voice.events.iter_mut().enumerate() {
+        let same = pitches[index].is_some() && pitches.get(index + 1) == Some(&pitches[index]);
+        if !same {
+            set_tie(event, false);
+        }
+    }
+    voice
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::articulation::Articulation;
+    use crate::notation::chord::Chord;
+    use crate::notation::pitch::{NoteName, Octave};
+    use crate::notation::scale::ScaleType;
+    use crate::notation::transform::*;
+    use crate::parser::voice;
+    use crate::NoteNameLanguage;
+    fn pitch(note_name: NoteName, octave: Octave) -> Pitch {
+        let mut pitch = Pitch::new(note_name);
+        pitch.octave(octave);
+        pitch
+    }
+    #[test]
+    fn test_retrograde() {
+        let original = voice("c4~ c8 r8 d4->");
+        assert_eq!(original.retrograde(), voice("d4-> r8 c8~ c4"));
+        assert_eq!(original.retrograde().retrograde(), original);
+    }
+    #[test]
+    fn test_invert_chromatic() {
+        let mut chord_voice = voice("e'4-.~ e'");
+        chord_voice.push(Chord::new(vec![
+            pitch(NoteName::C, Octave::S4),
+            pitch(NoteName::G, Octave::S4),
+        ]));
+        let inverted = chord_voice
+            .invert_chromatic(&pitch(NoteName::D, Octave::S4))
+            .unwrap();
+        let mut expected = voice("c'4-.~ c'");
+        expected.push(Chord::new(vec![
+            pitch(NoteName::E, Octave::S4),
+            pitch(NoteName::A, Octave::S3),
+        ]));
+        assert_eq!(inverted, expected);
+        assert!(voice("c4")
+            .invert_chromatic(&Pitch::new(NoteName::None))
+            .is_err());
+    }
+    #[test]
+    fn test_invert_diatonic() {
+        // in D major, around D: F sharp (a third up) becomes B (a third down)
+        // and a chromatic G sharp becomes A flat
+        let d_major = Scale::new(&Pitch::new(NoteName::D), ScaleType::Major).unwrap();
+        let inverted = voice("d'4 fs' gs'")
+            .invert_diatonic(&pitch(NoteName::D, Octave::S4), &d_major)
+            .unwrap();
+        assert_eq!(inverted, voice("d'4 b af"));
+        let pentatonic = Scale::new(&Pitch::new(NoteName::C), ScaleType::MajorPentatonic).unwrap();
+        assert!(voice("c4")
+            .invert_diatonic(&Pitch::new(NoteName::C), &pentatonic)
+            .is_err());
+    }
+    #[test]
+    fn test_augment() {
+        assert_eq!(
+            voice("c4. r8 d16-!").augment(Duration::from_integer(2)),
+            Ok(voice("c2. r4 d8-!"))
+        );
+        // rests are split without ties
+        assert_eq!(
+            voice("r4 c4~ c4").augment(Duration::new(5, 4)),
+            Ok(voice("r4 r16 c4~ c16~ c4~ c16"))
+        );
+        // beams start on the first piece and end on the last
+        let augmented = voice("e8[ f8]").augment(Duration::new(5, 4)).unwrap();
+        assert_eq!(augmented, voice("e8[~ e32 f8~ f32]"));
+        let written: Vec<String> = augmented
+            .events
+            .iter()
+            .map(|event| NoteNameLanguage::English.lilypond_from_event(event))
+            .collect();
+        assert_eq!(written.join(" "), "e8[~ e32 f8~ f32]");
+        // so does a dynamic
+        assert_eq!(
+            voice("c4\\f").augment(Duration::new(5, 4)),
+            Ok(voice("c4\\f~ c16"))
+        );
+        assert!(voice("c4").augment(Duration::from_integer(0)).is_err());
+        assert!(voice("c4").augment(Duration::new(1, 3)).is_err());
+    }
+    #[test]
+    fn test_rotate() {
+        let original = voice("c4 d e");
+        assert_eq!(original.rotate(0), original);
+        assert_eq!(original.rotate(4), voice("d4 e c"));
+        assert_eq!(Voice::new().rotate(3), Voice::new());
+        // a tie that is no longer followed by its note is removed
+        assert_eq!(voice("c4 d~ d").rotate(-1), voice("d4 c d"));
+    }
+    #[test]
+    fn test_displace() {
+        let displaced = voice("c'4->~ c' d' r").displace(&[1, 0]).unwrap();
+        let mut expected = voice("c''4 c' d'' r");
+        if let Event::Note(note) = &mut expected.events[0] {
+            note.articulations.push(Articulation::Accent);
+        }
+        assert_eq!(displaced, expected);
+        assert!(voice("c4").displace(&[]).is_err());
+        assert!(voice("c4").displace(&[9]).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! Transposition of pitches, notes, chords, voices and scores.
+
+use crate::notation::chord::Chord;
+use crate::notation::interval::Interval;
+use crate::notation::key::KeySignature;
+use crate::notation::note::Note;
+use crate::notation::pitch::{Alteration, Pitch};
+use crate::notation::score::{Score, Staff, StaffChange};
+use crate::notation::voice::{Event, Voice};
+
+/// A transposition by a number of diatonic steps and semitones, like
+/// LilyPond's `\transpose`.
+///
+/// Transposed pitches move by the diatonic steps, so their note names follow
+/// the transposition's spelling, and take whatever accidental makes them
+/// sound the right number of semitones higher or lower.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transposition {
+    steps: i32,
+    semitones: Alteration,
+    simplify: bool,
+}
+
+impl Transposition {
+    /// Attempt to construct the transposition that moves `from` to `to`, like
+    /// `\transpose from to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if either pitch is a rest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Accidental, Pitch, NoteName};
+    /// use lilypond::notation::transpose::{Transpose, Transposition};
+    ///
+    /// // \transpose c d
+    /// let transposition =
+    ///     Transposition::new(&Pitch::new(NoteName::C), &Pitch::new(NoteName::D)).unwrap();
+    /// let pitch = Pitch::new(NoteName::F).transpose(&transposition).unwrap();
+    ///
+    /// assert_eq!(pitch.note_name, NoteName::G);
+    /// assert_eq!(pitch.accidental, Accidental::None);
+    /// ```
+    pub fn new(from: &Pitch, to: &Pitch) -> Result<Transposition, String> {
+        Ok(Transposition {
+            steps: to.diatonic_number()? - from.diatonic_number()?,
+            semitones: to.semitones()? - from.semitones()?,
+            simplify: false,
+        })
+    }
+    /// Construct the transposition by a number of diatonic steps and a
+    /// number of semitones, either of which may be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::{Accidental, Alteration, Pitch, NoteName};
+    /// use lilypond::notation::transpose::{Transpose, Transposition};
+    ///
+    /// // a second that is three quarter tones wide
+    /// let transposition = Transposition::from_steps(1, Alteration::new(3, 2));
+    /// let pitch = Pitch::new(NoteName::C).transpose(&transposition).unwrap();
+    ///
+    /// assert_eq!(pitch.note_name, NoteName::D);
+    /// assert_eq!(pitch.accidental, Accidental::QuarterFlat);
+    /// ```
+    pub fn from_steps(steps: i32, semitones: Alteration) -> Transposition {
+        Transposition {
+            steps,
+            semitones,
+            simplify: false,
+        }
+    }
+    /// Return the number of diatonic steps the transposition moves pitches.
+    pub fn steps(&self) -> i32 {
+        self.steps
+    }
+    /// Return the number of semitones the transposition moves pitches.
+    pub fn semitones(&self) -> Alteration {
+        self.semitones
+    }
+    /// Construct the transposition up by an interval.
+    pub fn up(interval: &Interval) -> Transposition {
+        Transposition {
+            steps: i32::from(interval.number()) - 1,
+            semitones: Alteration::from_integer(interval.semitones()),
+            simplify: false,
+        }
+    }
+    /// Construct the transposition down by an interval.
+    pub fn down(interval: &Interval) -> Transposition {
+        Transposition::up(interval).inverse()
+    }
+    /// Choose whether transposed pitches with double accidentals are
+    /// respelled with [`Pitch::simplify_double_accidental`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::interval::Interval;
+    /// use lilypond::notation::pitch::{Accidental, Pitch, NoteName};
+    /// use lilypond::notation::transpose::{Transpose, Transposition};
+    ///
+    /// let mut transposition = Transposition::up(&"A2".parse::<Interval>().unwrap());
+    /// let mut pitch = Pitch::new(NoteName::F);
+    /// pitch.sharpen();
+    ///
+    /// let transposed = pitch.transpose(&transposition).unwrap();
+    /// assert_eq!(transposed.note_name, NoteName::G);
+    /// assert_eq!(transposed.accidental, Accidental::DoubleSharp);
+    ///
+    /// transposition.simplify(true);
+    /// let transposed = pitch.transpose(&transposition).unwrap();
+    /// assert_eq!(transposed.note_name, NoteName::A);
+    /// assert_eq!(transposed.accidental, Accidental::None);
+    /// ```
+    pub fn simplify(&mut self, simplify: bool) {
+        self.simplify = simplify;
+    }
+    /// Return the transposition that undoes this one.
+    pub fn inverse(&self) -> Transposition {
+        Transposition {
+            steps: -self.steps,
+            semitones: -self.semitones,
+            simplify: self.simplify,
+        }
+    }
+    /// Return the transposition equivalent to this one followed by `other`.
+    /// Double accidentals are simplified if either transposition simplifies
+    /// them.
+    pub fn then(&self, other: &Transposition) -> Transposition {
+        Transposition {
+            steps: self.steps + other.steps,
+            semitones: self.semitones + other.semitones,
+            simplify: self.simplify || other.simplify,
+        }
+    }
+}
+
+/// Types that can be transposed.
+pub trait Transpose: Sized {
+    /// Attempt to transpose a copy of the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if a transposed pitch would be outside of the
+    /// octaves C0--B9.
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String>;
+}
+
+impl Transpose for Pitch {
+    /// Transpose the pitch. Rests are left as they are.
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        let (steps, semitones) = match (self.diatonic_number(), self.semitones()) {
+            (Ok(steps), Ok(semitones)) => (steps, semitones),
+            _ => return Ok(self.clone()),
+        };
+        let pitch = Pitch::from_diatonic_number(
+            steps + transposition.steps,
+            semitones + transposition.semitones,
+        )?;
+        if transposition.simplify {
+            pitch.simplify_double_accidental()
+        } else {
+            Ok(pitch)
+        }
+    }
+}
+
+impl Transpose for Note {
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        Ok(Note {
+            pitch: self.pitch.transpose(transposition)?,
+            rhythm: self.rhythm.clone(),
+            tie: self.tie,
+            articulations: self.articulations.clone(),
+            beam: self.beam,
+            dynamic: self.dynamic,
+        })
+    }
+}
+
+impl Transpose for Chord {
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        Ok(Chord {
+            pitches: self.pitches.transpose(transposition)?,
+            rhythm: self.rhythm.clone(),
+            tie: self.tie,
+            articulations: self.articulations.clone(),
+            beam: self.beam,
+            dynamic: self.dynamic,
+        })
+    }
+}
+
+impl Transpose for Event {
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        Ok(match self {
+            Event::Note(note) => Event::Note(note.transpose(transposition)?),
+            Event::Chord(chord) => Event::Chord(chord.transpose(transposition)?),
+        })
+    }
+}
+
+impl Transpose for Voice {
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        Ok(Voice {
+            events: self.events.transpose(transposition)?,
+        })
+    }
+}
+
+impl Transpose for KeySignature {
+    /// Attempt to transpose the key signature, e.g. from one flat to three
+    /// sharps for a transposition up a major sixth.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the transposition is microtonal, or if the
+    /// transposed key signature would have more than 7 sharps or flats.
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        let semitones = transposition.semitones();
+        if !semitones.is_integer() {
+            return Err(format!(
+                "Cannot transpose a key signature by {} semitones.",
+                semitones
+            ));
+        }
+        // a fifth is 4 steps and 7 semitones, and an octave 7 steps and 12
+        let fifths = 7 * semitones.to_integer() - 12 * transposition.steps();
+        KeySignature::from_fifths(self.fifths() + fifths)
+    }
+}
+
+impl Transpose for Staff {
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        Ok(Staff {
+            voices: self.voices.transpose(transposition)?,
+            key_signature: self.key_signature.transpose(transposition)?,
+            changes: self
+                .changes
+                .iter()
+                .map(|(measure, change)| match change {
+                    StaffChange::KeySignature(key_signature) => Ok((
+                        *measure,
+                        StaffChange::KeySignature(key_signature.transpose(transposition)?),
+                    )),
+                    _ => Ok((*measure, change.clone())),
+                })
+                .collect::<Result<Vec<(usize, StaffChange)>, String>>()?,
+            ..self.clone()
+        })
+    }
+}
+
+impl Transpose for Score {
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        Ok(Score {
+            staves: self.staves.transpose(transposition)?,
+        })
+    }
+}
+
+impl<T: Transpose> Transpose for Vec<T> {
+    fn transpose(&self, transposition: &Transposition) -> Result<Self, String> {
+        self.iter().map(|t| t.transpose(transposition)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::key::Key;
+    use crate::notation::pitch::{Accidental, NoteName, Octave};
+    use crate::notation::transpose::*;
+    fn pitch(note_name: NoteName, accidental: Accidental, octave: Octave) -> Pitch {
+        Pitch {
+            note_name,
+            accidental,
+            octave,
+        }
+    }
+    #[test]
+    fn test_new() {
+        // \transpose c' bf: down a major second
+        let from = pitch(NoteName::C, Accidental::None, Octave::S4);
+        let to = pitch(NoteName::B, Accidental::Flat, Octave::S3);
+        let transposition = Transposition::new(&from, &to).unwrap();
+        assert_eq!(transposition, Transposition::down(&"M2".parse().unwrap()));
+        let e = pitch(NoteName::E, Accidental::None, Octave::S4);
+        assert_eq!(
+            e.transpose(&transposition),
+            Ok(pitch(NoteName::D, Accidental::None, Octave::S4))
+        );
+        let rest = Pitch::new(NoteName::None);
+        assert!(Transposition::new(&from, &rest).is_err());
+    }
+    #[test]
+    fn test_spelling() {
+        // \transpose c cs keeps the note names, \transpose c df changes them
+        let c = pitch(NoteName::C, Accidental::None, Octave::S3);
+        let cs = pitch(NoteName::C, Accidental::Sharp, Octave::S3);
+        let df = pitch(NoteName::D, Accidental::Flat, Octave::S3);
+        let b = pitch(NoteName::B, Accidental::None, Octave::S3);
+        let sharp = Transposition::new(&c, &cs).unwrap();
+        let flat = Transposition::new(&c, &df).unwrap();
+        assert_eq!(
+            b.transpose(&sharp),
+            Ok(pitch(NoteName::B, Accidental::Sharp, Octave::S3))
+        );
+        assert_eq!(
+            b.transpose(&flat),
+            Ok(pitch(NoteName::C, Accidental::None, Octave::S4))
+        );
+    }
+    #[test]
+    fn test_simplify() {
+        let mut transposition = Transposition::down(&"A2".parse().unwrap());
+        let c = pitch(NoteName::C, Accidental::None, Octave::S4);
+        assert_eq!(
+            c.transpose(&transposition),
+            Ok(pitch(NoteName::B, Accidental::DoubleFlat, Octave::S3))
+        );
+        transposition.simplify(true);
+        assert_eq!(
+            c.transpose(&transposition),
+            Ok(pitch(NoteName::A, Accidental::None, Octave::S3))
+        );
+    }
+    #[test]
+    fn test_then_inverse() {
+        let up = Transposition::up(&"P5".parse().unwrap());
+        let down = Transposition::down(&"P4".parse().unwrap());
+        let octave = Transposition::up(&"P8".parse().unwrap());
+        assert_eq!(up.then(&down.inverse()), octave);
+        assert_eq!(up.then(&up.inverse()), Transposition::default());
+    }
+    #[test]
+    fn test_key_signature() {
+        let key = KeySignature::new(Key::Flats(1)).unwrap();
+        let up = Transposition::up(&"M6".parse().unwrap());
+        assert_eq!(
+            key.transpose(&up),
+            Ok(KeySignature::new(Key::Sharps(2)).unwrap())
+        );
+        let sharps = KeySignature::new(Key::Sharps(6)).unwrap();
+        assert!(sharps.transpose(&up).is_err());
+    }
+    #[test]
+    fn test_score() {
+        let transposition = Transposition::up(&"m3".parse().unwrap());
+        let mut voice = Voice::new();
+        voice.push(Note::new(NoteName::E));
+        voice.push(Note::new(NoteName::None));
+        voice.push(Chord::new(vec![
+            pitch(NoteName::A, Accidental::None, Octave::S3),
+            pitch(NoteName::C, Accidental::Sharp, Octave::S4),
+        ]));
+        let mut score = Score::new();
+        score.staves.push(Staff::from(voice));
+        let transposed = score.transpose(&transposition).unwrap();
+        let events = &transposed.staves[0].voices[0].events;
+        assert_eq!(events[0], Event::Note(Note::new(NoteName::G)));
+        assert_eq!(events[1], Event::Note(Note::new(NoteName::None)));
+        assert_eq!(
+            events[2],
+            Event::Chord(Chord::new(vec![
+                pitch(NoteName::C, Accidental::None, Octave::S4),
+                pitch(NoteName::E, Accidental::None, Octave::S4),
+            ]))
+        );
+        let high = pitch(NoteName::A, Accidental::None, Octave::S9);
+        assert!(vec![high].transpose(&transposition).is_err());
+    }
+}
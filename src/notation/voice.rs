@@ -0,0 +1,66 @@
+//! Abstract types for a single line of music.
+
+use crate::notation::chord::Chord;
+use crate::notation::note::Note;
+
+/// Something that happens at one point in a voice.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    /// A single note or rest.
+    Note(Note),
+    /// Several pitches at once.
+    Chord(Chord),
+}
+
+impl From<Note> for Event {
+    fn from(note: Note) -> Self {
+        Event::Note(note)
+    }
+}
+
+impl From<Chord> for Event {
+    fn from(chord: Chord) -> Self {
+        Event::Chord(chord)
+    }
+}
+
+/// A sequence of events, one after the other.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Voice {
+    pub events: Vec<Event>,
+}
+
+impl Voice {
+    /// Construct a new, empty voice.
+    pub fn new() -> Voice {
+        Voice { events: vec![] }
+    }
+    /// Append a note or chord to the end of the voice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::note::Note;
+    /// use lilypond::notation::pitch::NoteName;
+    /// use lilypond::notation::voice::{Event, Voice};
+    ///
+    /// let mut voice = Voice::new();
+    /// voice.push(Note::new(NoteName::C));
+    ///
+    /// assert_eq!(voice.events, vec![Event::Note(Note::new(NoteName::C))]);
+    /// ```
+    pub fn push<E: Into<Event>>(&mut self, event: E) {
+        self.events.push(event.into());
+    }
+}
+
+impl From<Vec<Note>> for Voice {
+    /// Make a voice of notes, e.g. those from [`parse_notes`][crate::parser::parse_notes].
+    fn from(notes: Vec<Note>) -> Self {
+        Voice {
+            events: notes.into_iter().map(Event::from).collect(),
+        }
+    }
+}
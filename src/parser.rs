@@ -1,9 +1,26 @@
 //! This contains logic for parsing LilyPond
 
+use std::convert::TryFrom;
+
 use lazy_static::lazy_static;
 
 use regex::Regex;
 
+use crate::{
+    lilypond_objects::lilypond_note::LilyPondNote,
+    notation::articulation::Articulation,
+    notation::beam::Beam,
+    notation::dynamic::Dynamic,
+    notation::measure::{Measure, Measures},
+    notation::note::Note,
+    notation::pitch::{NoteName, Octave, Pitch},
+    notation::rhythm::Duration,
+    notation::time::TimeSignature,
+    notation::transpose::{Transpose, Transposition},
+    notation::voice::Event,
+    NoteNameLanguage,
+};
+
 /// Gets content between curly brackets
 ///
 /// # Examples
@@ -23,3 +40,824 @@ pub fn curly_brackets(input: &str) -> Option<&str> {
         .captures(input)
         .and_then(|cap| cap.name("music").map(|login| login.as_str()))
 }
+
+/// A lexical token of LilyPond input.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Token {
+    /// A backslash command without the backslash, e.g. `language`.
+    Command(String),
+    /// The contents of a double-quoted string.
+    String(String),
+    /// An opening curly bracket.
+    OpenBrace,
+    /// A closing curly bracket.
+    CloseBrace,
+    /// A bar check, `|`.
+    BarCheck,
+    /// Any other word, e.g. a note like `fis'8.`.
+    Word(String),
+}
+
+/// Characters that end a [`Token::Word`].
+fn is_word_end(c: char) -> bool {
+    c.is_whitespace() || "{}|\"\\%".contains(c)
+}
+
+/// Split LilyPond input into [`Token`]s, skipping whitespace and comments.
+///
+/// # Errors
+///
+/// Returns `Err(String)` for unterminated strings and block comments, and for
+/// backslashes that do not start a command.
+///
+/// # Examples
+///
+/// ```
+/// use lilypond::parser::{tokenize, Token};
+///
+/// let tokens = tokenize("\\language \"deutsch\" { c4 % comment\n h }").unwrap();
+///
+/// assert_eq!(
+///     tokens,
+///     vec![
+///         Token::Command(String::from("language")),
+///         Token::String(String::from("deutsch")),
+///         Token::OpenBrace,
+///         Token::Word(String::from("c4")),
+///         Token::Word(String::from("h")),
+///         Token::CloseBrace,
+///     ]
+/// );
+/// ```
+pub fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '%' => {
+                if chars.peek() == Some(&'{') {
+                    // block comment, ended by %}
+                    chars.next();
+                    let mut previous = ' ';
+                    loop {
+                        match chars.next() {
+                            Some('}') if previous == '%' => break,
+                            Some(c) => previous = c,
+                            None => return Err(String::from("Unterminated block comment.")),
+                        }
+                    }
+                } else {
+                    // line comment
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\\' => {
+                let mut command = String::new();
+                while let Some(c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    command.push(*c);
+                    chars.next();
+                }
+                if command.is_empty() {
+                    return Err(String::from("Invalid command \"\\\"."));
+                }
+                tokens.push(Token::Command(command));
+            }
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => string.push(c),
+                            None => return Err(String::from("Unterminated string.")),
+                        },
+                        Some(c) => string.push(c),
+                        None => return Err(String::from("Unterminated string.")),
+                    }
+                }
+                tokens.push(Token::String(string));
+            }
+            '{' => tokens.push(Token::OpenBrace),
+            '}' => tokens.push(Token::CloseBrace),
+            '|' => tokens.push(Token::BarCheck),
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.peek().filter(|c| !is_word_end(**c)) {
+                    word.push(*c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse the notes of LilyPond input, reading note names in `language` until
+/// the input selects another language.
+///
+/// The input may switch languages any number of times with
+/// `\language "..."`, or with the legacy `\include "<language>.ly"` form.
+/// As in LilyPond, a note without a duration takes the duration of the
+/// note before it.
+///
+/// The notes are returned at concert pitch: `\transpose from to` transposes
+/// the note or curly-bracketed music after it, and `\transposition pitch`
+/// declares that the notes after it are written for an instrument that
+/// sounds `pitch` for a written `c'`.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the input cannot be tokenized, contains an
+/// invalid note for the current language, selects an unsupported language,
+/// has unbalanced curly brackets, transposes a note out of range, has a tie,
+/// beam or articulation without a note, or uses a command other than
+/// `\language`, `\include`, `\version`, `\transpose`, `\transposition`,
+/// `\time`, `\compoundMeter`, `\partial`, or an [`Articulation`]'s or a
+/// [`Dynamic`]'s command.
+///
+/// # Examples
+///
+/// ```
+/// use lilypond::parser::parse_notes;
+/// use lilypond::notation::pitch::Accidental;
+/// use lilypond::NoteNameLanguage;
+///
+/// let notes = parse_notes(
+///     "{ fs8 \\language \"nederlands\" fis }",
+///     NoteNameLanguage::English,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(notes.len(), 2);
+/// assert_eq!(notes[0], notes[1]);
+/// assert_eq!(notes[1].pitch.accidental, Accidental::Sharp);
+/// ```
+///
+/// Transposing:
+///
+/// ```
+/// use lilypond::parser::parse_notes;
+/// use lilypond::notation::pitch::{Accidental, NoteName};
+/// use lilypond::NoteNameLanguage;
+///
+/// let notes = parse_notes("\\transpose c d { c e }", NoteNameLanguage::English).unwrap();
+///
+/// assert_eq!(notes[0].pitch.note_name, NoteName::D);
+/// assert_eq!(notes[1].pitch.accidental, Accidental::Sharp);
+/// ```
+pub fn parse_notes(input: &str, language: NoteNameLanguage) -> Result<Vec<Note>, String> {
+    Ok(parse_music(input, language)?.notes)
+}
+
+/// Parse LilyPond input like [`parse_notes`], splitting the notes into
+/// measures at its bar checks, `|`.
+///
+/// The input may start with a `\time` signature, like `\time 6/8` or
+/// `\time 2,2,3 7/8`, or an additive `\compoundMeter #'((3 2 8))`, which is
+/// 4/4 otherwise, and a `\partial` pickup, e.g. `\partial 4.` or `\partial 8*5`. Use
+/// [`Measures::check`] to find the measures that do not fit the time
+/// signature.
+///
+/// # Errors
+///
+/// Returns `Err(String)` for the same input as [`parse_notes`], and if a
+/// `\time`, `\compoundMeter` or `\partial` is not followed by a valid
+/// argument or comes after the first note.
+///
+/// # Examples
+///
+/// ```
+/// use lilypond::notation::rhythm::Duration;
+/// use lilypond::parser::parse_measures;
+/// use lilypond::NoteNameLanguage;
+///
+/// let measures = parse_measures(
+///     "{ \\time 3/4 \\partial 4 g4 | c'2 e'4 | g'2. }",
+///     NoteNameLanguage::English,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(measures.time_signature.numerator, 3);
+/// assert_eq!(measures.partial, Some(Duration::new(1, 4)));
+/// assert_eq!(measures.measures.len(), 3);
+/// assert_eq!(measures.check(), Ok(vec![]));
+/// ```
+pub fn parse_measures(input: &str, language: NoteNameLanguage) -> Result<Measures, String> {
+    let music = parse_music(input, language)?;
+    let mut measures = vec![];
+    let mut start = 0;
+    for end in music.bars.into_iter().chain(Some(music.notes.len())) {
+        if end > start {
+            measures.push(Measure {
+                events: music.notes[start..end]
+                    .iter()
+                    .cloned()
+                    .map(Event::from)
+                    .collect(),
+            });
+        }
+        start = end;
+    }
+    Ok(Measures {
+        time_signature: music.time_signature,
+        partial: music.partial,
+        measures,
+    })
+}
+
+/// The notes of LilyPond input, with what it says about their measures.
+struct Music {
+    notes: Vec<Note>,
+    /// The number of notes before each bar check.
+    bars: Vec<usize>,
+    time_signature: TimeSignature,
+    partial: Option<Duration>,
+}
+
+/// Parse the notes of LilyPond input, and its bar checks, time signature and
+/// pickup; see [`parse_notes`] and [`parse_measures`].
+fn parse_music(input: &str, language: NoteNameLanguage) -> Result<Music, String> {
+    let mut language = language;
+    let mut notes: Vec<Note> = vec![];
+    let mut bars: Vec<usize> = vec![];
+    let mut time_signature = TimeSignature::new();
+    let mut partial: Option<Duration> = None;
+    let mut depth: usize = 0;
+    // the transposition of each open curly bracket, with its depth
+    let mut scopes: Vec<(usize, Transposition)> = vec![];
+    // a \transpose waiting for the music it applies to
+    let mut pending: Option<Transposition> = None;
+    let mut instrument = Transposition::default();
+    let mut tokens = tokenize(input)?.into_iter();
+    while let Some(token) = tokens.next() {
+        let music = match &token {
+            Token::OpenBrace | Token::Word(_) => true,
+            Token::Command(command) => command == "transpose",
+            _ => false,
+        };
+        if pending.is_some() && !music {
+            return Err(String::from("Expected music after \"\\transpose\"."));
+        }
+        match token {
+            Token::Command(command) => match command.as_str() {
+                "language" => language = string_argument(&command, tokens.next())?.parse()?,
+                "include" => {
+                    let file = string_argument(&command, tokens.next())?;
+                    language = match file.strip_suffix(".ly") {
+                        Some(name) => name.parse().ok(),
+                        None => None,
+                    }
+                    .ok_or_else(|| format!("Unsupported include \"{}\".", file))?;
+                }
+                "version" => {
+                    string_argument(&command, tokens.next())?;
+                }
+                "transpose" => {
+                    let from = pitch_argument(&command, tokens.next(), language)?;
+                    let to = pitch_argument(&command, tokens.next(), language)?;
+                    let transposition = Transposition::new(&from, &to)?;
+                    pending = Some(match pending {
+                        Some(outer) => outer.then(&transposition),
+                        None => transposition,
+                    });
+                }
+                "transposition" => {
+                    let mut middle_c = Pitch::new(NoteName::C);
+                    middle_c.octave(Octave::S4);
+                    let pitch = pitch_argument(&command, tokens.next(), language)?;
+                    instrument = Transposition::new(&middle_c, &pitch)?;
+                }
+                "time" | "compoundMeter" | "partial" if !notes.is_empty() => {
+                    return Err(format!("Expected \"\\{}\" before the first note.", command));
+                }
+                "time" => time_signature = time_argument(&command, &mut tokens)?,
+                "compoundMeter" => time_signature = compound_meter_argument(&command, &mut tokens)?,
+                "partial" => partial = Some(duration_argument(&command, tokens.next())?),
+                name => {
+                    let articulation = Articulation::from_name(name);
+                    let dynamic = Dynamic::from_name(name);
+                    if articulation.is_none() && dynamic.is_none() {
+                        return Err(format!("Unsupported command \"\\{}\".", command));
+                    }
+                    let note = notes
+                        .last_mut()
+                        .ok_or_else(|| format!("Expected a note before \"\\{}\".", command))?;
+                    note.articulations.extend(articulation);
+                    note.dynamic = dynamic.or(note.dynamic);
+                }
+            },
+            Token::String(string) => return Err(format!("Unexpected string \"{}\".", string)),
+            Token::OpenBrace => {
+                depth += 1;
+                if let Some(transposition) = pending.take() {
+                    let outer = scopes.last().map(|(_, outer)| *outer).unwrap_or_default();
+                    scopes.push((depth, outer.then(&transposition)));
+                }
+            }
+            Token::CloseBrace => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| String::from("Unmatched \"}\"."))?;
+                while scopes.last().is_some_and(|(scope, _)| *scope > depth) {
+                    scopes.pop();
+                }
+            }
+            Token::BarCheck => bars.push(notes.len()),
+            Token::Word(word) => {
+                let (word, attachments) = split_attachments(&word);
+                if word.is_empty() {
+                    // attachments written apart from their note, e.g. `c4 ~`
+                    let note = notes.last_mut().ok_or_else(|| {
+                        String::from("Expected a note before a tie, beam or articulation.")
+                    })?;
+                    attachments.attach(note);
+                    continue;
+                }
+                let ly_note = LilyPondNote::with_language(word, language)?;
+                let mut note = Note::try_from(&ly_note)?;
+                attachments.attach(&mut note);
+                if let Some(previous) = notes.last() {
+                    if ly_note.get_capture("duration").is_empty() {
+                        note.rhythm.length(previous.rhythm.length);
+                        note.rhythm.dots(previous.rhythm.dots.get_num_dots());
+                    }
+                }
+                let mut transposition = scopes.last().map(|(_, t)| *t).unwrap_or_default();
+                if let Some(pending) = pending.take() {
+                    transposition = transposition.then(&pending);
+                }
+                notes.push(note.transpose(&transposition.then(&instrument))?);
+            }
+        }
+    }
+    if pending.is_some() {
+        return Err(String::from("Expected music after \"\\transpose\"."));
+    }
+    if depth > 0 {
+        return Err(String::from("Unmatched \"{\"."));
+    }
+    Ok(Music {
+        notes,
+        bars,
+        time_signature,
+        partial,
+    })
+}
+
+/// The tie, beam and articulations written after a note.
+#[derive(Default)]
+struct Attachments {
+    /// The articulations, in the order they were written.
+    articulations: Vec<Articulation>,
+    beam: Option<Beam>,
+    tie: bool,
+}
+
+impl Attachments {
+    /// Add the attachments to a note.
+    fn attach(self, note: &mut Note) {
+        note.articulations.extend(self.articulations);
+        note.beam = self.beam.or(note.beam);
+        note.tie |= self.tie;
+    }
+}
+
+/// Split the tie, beam and articulation shorthands, e.g. `~`, `[` and `-.`,
+/// off the end of a word, returning the rest of the word and the
+/// attachments.
+fn split_attachments(word: &str) -> (&str, Attachments) {
+    let mut rest = word;
+    let mut attachments = Attachments::default();
+    loop {
+        if let Some(before) = rest.strip_suffix('~') {
+            attachments.tie = true;
+            rest = before;
+        } else if let Some(before) = rest.strip_suffix('[') {
+            attachments.beam = Some(Beam::Start);
+            rest = before;
+        } else if let Some(before) = rest.strip_suffix(']') {
+            attachments.beam = Some(Beam::End);
+            rest = before;
+        } else if let Some(articulation) = rest
+            .get(rest.len().saturating_sub(2)..)
+            .and_then(Articulation::from_shorthand)
+        {
+            attachments.articulations.insert(0, articulation);
+            rest = &rest[..rest.len() - 2];
+        } else {
+            return (rest, attachments);
+        }
+    }
+}
+
+/// Get the pitch argument of `command`, a note name in `language`.
+fn pitch_argument(
+    command: &str,
+    token: Option<Token>,
+    language: NoteNameLanguage,
+) -> Result<Pitch, String> {
+    match token {
+        Some(Token::Word(word)) => {
+            let note = Note::try_from(&LilyPondNote::with_language(&word, language)?)?;
+            match note.pitch.note_name {
+                NoteName::None => Err(format!("Expected a pitch after \"\\{}\".", command)),
+                _ => Ok(note.pitch),
+            }
+        }
+        _ => Err(format!("Expected a pitch after \"\\{}\".", command)),
+    }
+}
+
+/// Get the time signature argument of `command`, e.g. `3/4`, optionally
+/// after its beat structure, e.g. `2,2,3 7/8`.
+fn time_argument<I>(command: &str, tokens: &mut I) -> Result<TimeSignature, String>
+where
+    I: Iterator<Item = Token>,
+{
+    let error = || format!("Expected a time signature after \"\\{}\".", command);
+    let mut word = match tokens.next() {
+        Some(Token::Word(word)) => word,
+        _ => return Err(error()),
+    };
+    let mut beat_structure = vec![];
+    if word.contains(',') {
+        beat_structure = word
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| error())?;
+        word = match tokens.next() {
+            Some(Token::Word(word)) => word,
+            _ => return Err(error()),
+        };
+    }
+    let mut time_signature: TimeSignature = word.parse().map_err(|_| error())?;
+    if time_signature.additive {
+        return Err(error());
+    }
+    if !beat_structure.is_empty() {
+        time_signature.beat_structure(beat_structure)?;
+    }
+    Ok(time_signature)
+}
+
+/// Get the additive time signature argument of `command`, a Scheme list like
+/// `#'((3 2 8))`.
+fn compound_meter_argument<I>(command: &str, tokens: &mut I) -> Result<TimeSignature, String>
+where
+    I: Iterator<Item = Token>,
+{
+    let error = || {
+        format!(
+            "Expected an additive time signature after \"\\{}\".",
+            command
+        )
+    };
+    let mut words = vec![];
+    while !words
+        .last()
+        .is_some_and(|word: &String| word.ends_with("))"))
+    {
+        match tokens.next() {
+            Some(Token::Word(word)) => words.push(word),
+            _ => return Err(error()),
+        }
+    }
+    let list = words.join(" ");
+    let numbers = list
+        .strip_prefix("#'((")
+        .and_then(|list| list.strip_suffix("))"))
+        .ok_or_else(error)?
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| error())?;
+    match numbers.split_last() {
+        Some((denominator, groups)) if !groups.is_empty() => {
+            TimeSignature::additive(groups, *denominator)
+        }
+        _ => Err(error()),
+    }
+}
+
+/// Get the duration argument of `command`, e.g. `4.` or `8*5`.
+fn duration_argument(command: &str, token: Option<Token>) -> Result<Duration, String> {
+    let error = || format!("Expected a duration after \"\\{}\".", command);
+    match token {
+        Some(Token::Word(word)) => {
+            let (length, multiplier) = match word.split_once('*') {
+                Some((length, multiplier)) => {
+                    let multiplier: Duration = multiplier.parse().map_err(|_| error())?;
+                    (length, multiplier)
+                }
+                None => (word.as_str(), Duration::from_integer(1)),
+            };
+            let rest = LilyPondNote::new(&format!("r{}", length)).map_err(|_| error())?;
+            if rest.get_capture("duration").is_empty() || multiplier <= Duration::from_integer(0) {
+                return Err(error());
+            }
+            Ok(Note::try_from(&rest)?.rhythm.duration() * multiplier)
+        }
+        _ => Err(error()),
+    }
+}
+
+/// Get the string argument of `command`.
+fn string_argument(command: &str, token: Option<Token>) -> Result<String, String> {
+    match token {
+        Some(Token::String(string)) => Ok(string),
+        _ => Err(format!("Expected a string after \"\\{}\".", command)),
+    }
+}
+
+/// Parse notes in English note names into a voice, for tests.
+#[cfg(test)]
+pub(crate) fn voice(notes: &str) -> crate::notation::voice::Voice {
+    crate::notation::voice::Voice::from(parse_notes(notes, NoteNameLanguage::English).unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::measure::Fullness;
+    use crate::notation::pitch::Accidental;
+    use crate::notation::rhythm::Length;
+    use crate::parser::*;
+    #[test]
+    fn test_tokenize_comments() {
+        let tokens = tokenize("%{ block { comment %} c % line comment\n d").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Word(String::from("c")),
+                Token::Word(String::from("d"))
+            ]
+        );
+        assert!(tokenize("%{ unterminated").is_err());
+    }
+    #[test]
+    fn test_tokenize_strings() {
+        let tokens = tokenize("\\version \"2.24.0\" \"a \\\"b\\\"\"").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Command(String::from("version")),
+                Token::String(String::from("2.24.0")),
+                Token::String(String::from("a \"b\"")),
+            ]
+        );
+        assert!(tokenize("\"unterminated").is_err());
+    }
+    #[test]
+    fn test_tokenize_words() {
+        let tokens = tokenize("{c4|d'8.}").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::OpenBrace,
+                Token::Word(String::from("c4")),
+                Token::BarCheck,
+                Token::Word(String::from("d'8.")),
+                Token::CloseBrace,
+            ]
+        );
+    }
+    #[test]
+    fn test_parse_notes_language() {
+        let notes = parse_notes(
+            "\\version \"2.24.0\" { bf4 \\language \"nederlands\" bes \\language \"english\" bf }",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        assert_eq!(notes.len(), 3);
+        for note in notes {
+            assert_eq!(note.pitch.note_name, NoteName::B);
+            assert_eq!(note.pitch.accidental, Accidental::Flat);
+        }
+    }
+    #[test]
+    fn test_parse_notes_languages() {
+        let notes = parse_notes(
+            "\\language \"deutsch\" { h b } \\language \"italiano\" { si sib }",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        assert_eq!(notes[0], notes[2]);
+        assert_eq!(notes[1], notes[3]);
+        assert_eq!(notes[1].pitch.accidental, Accidental::Flat);
+    }
+    #[test]
+    fn test_parse_notes_include() {
+        let notes = parse_notes(
+            "\\include \"english.ly\" { ef }",
+            NoteNameLanguage::Nederlands,
+        )
+        .unwrap();
+        assert_eq!(notes[0].pitch.accidental, Accidental::Flat);
+        let error = parse_notes("\\include \"articulate.ly\"", NoteNameLanguage::English);
+        assert_eq!(
+            error,
+            Err(String::from("Unsupported include \"articulate.ly\"."))
+        );
+    }
+    #[test]
+    fn test_parse_notes_duration() {
+        let notes = parse_notes("{ c8. d e2 f }", NoteNameLanguage::English).unwrap();
+        assert_eq!(notes[1].rhythm.length, Length::Eighth);
+        assert_eq!(notes[1].rhythm.dots.get_num_dots(), 1);
+        assert_eq!(notes[3].rhythm.length, Length::Half);
+        assert_eq!(notes[3].rhythm.dots.get_num_dots(), 0);
+    }
+    #[test]
+    fn test_parse_notes_transpose() {
+        let notes = parse_notes(
+            "{ c \\transpose c' bf { c' \\transpose c e { c' } e' } c' \\transpose c g c' c' }",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        let expected = parse_notes("{ c bf d' d' c' g' c' }", NoteNameLanguage::English).unwrap();
+        assert_eq!(notes, expected);
+    }
+    #[test]
+    fn test_parse_notes_transposition() {
+        let notes = parse_notes(
+            "\\transposition bf { c' d' } \\transposition c' { c' }",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        let expected = parse_notes("{ bf c' c' }", NoteNameLanguage::English).unwrap();
+        assert_eq!(notes, expected);
+        let notes = parse_notes(
+            "\\language \"deutsch\" \\transposition es { a' }",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        assert_eq!(notes[0].pitch.note_name, NoteName::C);
+        assert_eq!(notes[0].pitch.octave, Octave::S4);
+    }
+    #[test]
+    fn test_parse_notes_attachments() {
+        let notes = parse_notes(
+            "{ c4->~ c8-.-- ~ \\fermata f-sharp2-! }",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        assert!(notes[0].tie);
+        assert_eq!(notes[0].articulations, vec![Articulation::Accent]);
+        assert!(notes[1].tie);
+        assert_eq!(
+            notes[1].articulations,
+            vec![
+                Articulation::Staccato,
+                Articulation::Tenuto,
+                Articulation::Fermata
+            ]
+        );
+        assert_eq!(notes[1].rhythm.length, Length::Eighth);
+        assert!(!notes[2].tie);
+        assert_eq!(notes[2].pitch.accidental, Accidental::Sharp);
+        assert_eq!(notes[2].articulations, vec![Articulation::Staccatissimo]);
+        let dynamics = parse_notes("c4\\p d\\sfz", NoteNameLanguage::English).unwrap();
+        assert_eq!(dynamics[0].dynamic, Some(Dynamic::P));
+        assert_eq!(dynamics[1].dynamic, Some(Dynamic::SFZ));
+        let beamed = parse_notes("c8[-. d e8 ]", NoteNameLanguage::English).unwrap();
+        assert_eq!(beamed[0].beam, Some(Beam::Start));
+        assert_eq!(beamed[0].articulations, vec![Articulation::Staccato]);
+        assert_eq!(beamed[1].beam, None);
+        assert_eq!(beamed[2].beam, Some(Beam::End));
+    }
+    #[test]
+    fn test_parse_measures() {
+        let measures = parse_measures(
+            "\\time 6/8 \\partial 8*2 | g8 a | b4. c'4 | d'4. ~ d'8 |",
+            NoteNameLanguage::English,
+        )
+        .unwrap();
+        assert_eq!(measures.partial, Some(Duration::new(1, 4)));
+        assert_eq!(measures.measures.len(), 3);
+        assert_eq!(
+            measures.check(),
+            Ok(vec![Fullness::Underfull {
+                measure: 1,
+                missing: Duration::new(1, 8)
+            }])
+        );
+        // the written music parses back to the same measures
+        let written = measures.to_lilypond(NoteNameLanguage::English);
+        assert_eq!(
+            written,
+            "{ \\time 6/8 \\partial 4 g8 a8 | b4. c'4 | d'4.~ d'8 }"
+        );
+        assert_eq!(
+            parse_measures(&written, NoteNameLanguage::English),
+            Ok(measures)
+        );
+        assert_eq!(
+            parse_measures("c1", NoteNameLanguage::English)
+                .unwrap()
+                .time_signature,
+            TimeSignature::new()
+        );
+    }
+    #[test]
+    fn test_parse_measures_time() {
+        for (input, time) in [
+            ("\\time 9/8 c4.", "9/8"),
+            ("\\compoundMeter #'((3 2 8)) c4.", "3+2/8"),
+            ("\\time 2,2,3 7/8 c4", "7/8"),
+        ] {
+            let measures = parse_measures(input, NoteNameLanguage::English).unwrap();
+            assert_eq!(measures.time_signature.to_string(), time);
+            // the written time signature parses back the same
+            let written = measures.to_lilypond(NoteNameLanguage::English);
+            assert_eq!(
+                parse_measures(&written, NoteNameLanguage::English),
+                Ok(measures)
+            );
+        }
+        let measures = parse_measures("\\time 2,2,3 7/8 c4", NoteNameLanguage::English).unwrap();
+        assert_eq!(measures.time_signature.beat_structure, vec![2, 2, 3]);
+    }
+    #[test]
+    fn test_parse_notes_errors() {
+        let errors = [
+            ("{ c", "Unmatched \"{\"."),
+            ("c }", "Unmatched \"}\"."),
+            (
+                "\\language \"klingon\"",
+                "Unsupported note-name language \"klingon\".",
+            ),
+            ("\\language {", "Expected a string after \"\\language\"."),
+            ("\\relative c' { c }", "Unsupported command \"\\relative\"."),
+            ("{ cis }", "Invalid LilyPond note \"cis\"."),
+            (
+                "\\transpose c { d }",
+                "Expected a pitch after \"\\transpose\".",
+            ),
+            (
+                "\\transpose c r { d }",
+                "Expected a pitch after \"\\transpose\".",
+            ),
+            (
+                "{ \\transpose c d }",
+                "Expected music after \"\\transpose\".",
+            ),
+            ("\\transpose c d", "Expected music after \"\\transpose\"."),
+            (
+                "\\transposition",
+                "Expected a pitch after \"\\transposition\".",
+            ),
+            (
+                "{ ~ c }",
+                "Expected a note before a tie, beam or articulation.",
+            ),
+            (
+                "[ c8 d ]",
+                "Expected a note before a tie, beam or articulation.",
+            ),
+            ("\\fermata c", "Expected a note before \"\\fermata\"."),
+            ("\\mf c", "Expected a note before \"\\mf\"."),
+            ("{ c4-? }", "Invalid LilyPond note \"c4-?\"."),
+            ("\\time 3", "Expected a time signature after \"\\time\"."),
+            ("\\time 3/0", "Expected a time signature after \"\\time\"."),
+            ("\\time 3/6", "Expected a time signature after \"\\time\"."),
+            (
+                "\\time 3+2/8",
+                "Expected a time signature after \"\\time\".",
+            ),
+            (
+                "\\time 2,2 5/8",
+                "Beat structure 2+2 does not fill a measure of 5/8.",
+            ),
+            (
+                "\\compoundMeter #'((8))",
+                "Expected an additive time signature after \"\\compoundMeter\".",
+            ),
+            (
+                "\\compoundMeter #'((3 2 8)",
+                "Expected an additive time signature after \"\\compoundMeter\".",
+            ),
+            ("\\partial c4", "Expected a duration after \"\\partial\"."),
+            ("\\partial 4*0", "Expected a duration after \"\\partial\"."),
+            (
+                "c4 \\partial 4",
+                "Expected \"\\partial\" before the first note.",
+            ),
+            (
+                "c4 | \\time 3/4",
+                "Expected \"\\time\" before the first note.",
+            ),
+        ];
+        for (input, error) in errors {
+            assert_eq!(
+                parse_notes(input, NoteNameLanguage::English),
+                Err(String::from(error))
+            );
+        }
+    }
+}
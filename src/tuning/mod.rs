@@ -0,0 +1,4 @@
+//! Tunings that give pitches a frequency.
+
+pub mod scala;
+pub mod temperament;
@@ -0,0 +1,597 @@
+//! Scales in the [Scala](https://www.huygens-fokker.org/scala/scl_format.html)
+//! `.scl` format, and the keyboard mappings of `.kbm` files that tune MIDI
+//! notes to them.
+
+use crate::midi::midi_note::MidiNote;
+use crate::notation::note::Note;
+use crate::tuning::temperament::Tuning;
+use crate::NoteNameLanguage;
+
+/// A scale read from a Scala `.scl` file: the pitches above its first
+/// degree, in cents, ending with the period at which the scale repeats
+/// (usually an octave of 1200 cents).
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScalaScale {
+    description: String,
+    pitches: Vec<f64>,
+}
+
+impl ScalaScale {
+    /// Attempt to construct a scale with a description and the pitches above
+    /// its first degree in cents, the last of which is the period.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if there are no pitches, or if the period is not
+    /// a positive number of cents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::tuning::scala::ScalaScale;
+    ///
+    /// let scale = ScalaScale::new("5-TET", vec![240.0, 480.0, 720.0, 960.0, 1200.0]).unwrap();
+    /// assert_eq!(scale.len(), 5);
+    /// assert_eq!(scale.period(), 1200.0);
+    /// assert!(ScalaScale::new("Empty", vec![]).is_err());
+    /// ```
+    pub fn new(description: &str, pitches: Vec<f64>) -> Result<ScalaScale, String> {
+        match pitches.last() {
+            Some(period) if period.is_finite() && *period > 0.0 => Ok(ScalaScale {
+                description: String::from(description),
+                pitches,
+            }),
+            Some(period) => Err(format!("Invalid period of {} cents.", period)),
+            None => Err(String::from("Expected at least one pitch in a scale.")),
+        }
+    }
+    /// Return the description of the scale.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+    /// Return the pitches above the first degree in cents, ending with the
+    /// period.
+    pub fn pitches(&self) -> &[f64] {
+        &self.pitches
+    }
+    /// Return the number of degrees in a period of the scale.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.pitches.len()
+    }
+    /// Return the interval in cents at which the scale repeats.
+    pub fn period(&self) -> f64 {
+        self.pitches[self.pitches.len() - 1]
+    }
+    /// Return the number of cents from the first degree up to a degree,
+    /// counting on into the following periods, or down into the previous
+    /// periods for negative degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::tuning::scala::ScalaScale;
+    ///
+    /// let scale = ScalaScale::new("Fifths", vec![700.0, 1200.0]).unwrap();
+    /// assert_eq!(scale.cents(1), 700.0);
+    /// assert_eq!(scale.cents(4), 2400.0);
+    /// assert_eq!(scale.cents(-1), -500.0);
+    /// ```
+    pub fn cents(&self, degree: i32) -> f64 {
+        let len = self.pitches.len() as i32;
+        let period = f64::from(degree.div_euclid(len)) * self.period();
+        match degree.rem_euclid(len) {
+            0 => period,
+            index => period + self.pitches[index as usize - 1],
+        }
+    }
+    /// Attempt to return the frequency in hertz of a MIDI note that a
+    /// keyboard mapping maps to a degree of the scale.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the note or the mapping's reference note is
+    /// not mapped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::midi_note::MidiNote;
+    /// use lilypond::tuning::scala::{KeyboardMapping, ScalaScale};
+    ///
+    /// let scale = ScalaScale::new("Fifths", vec![700.0, 1200.0]).unwrap();
+    /// // map the degrees to every other key from A4 at 440 Hz
+    /// let mapping = KeyboardMapping {
+    ///     middle: MidiNote::new(69).unwrap(),
+    ///     octave_degree: 2,
+    ///     mapping: vec![Some(0), None, Some(1), None],
+    ///     ..KeyboardMapping::default()
+    /// };
+    ///
+    /// let note = MidiNote::new(73).unwrap();
+    /// assert_eq!(scale.frequency(&note, &mapping), Ok(880.0));
+    /// assert_eq!(
+    ///     scale.frequency(&MidiNote::new(70).unwrap(), &mapping),
+    ///     Err(String::from("MIDI note 70 is not mapped."))
+    /// );
+    /// ```
+    pub fn frequency(&self, note: &MidiNote, mapping: &KeyboardMapping) -> Result<f64, String> {
+        let degree = mapping
+            .degree(note)
+            .ok_or_else(|| format!("MIDI note {} is not mapped.", note.get_note()))?;
+        let reference = mapping.map(&mapping.reference).ok_or_else(|| {
+            format!(
+                "Reference MIDI note {} is not mapped.",
+                mapping.reference.get_note()
+            )
+        })?;
+        let cents = self.cents(degree) - self.cents(reference);
+        Ok(mapping.frequency * (cents / 1200.0).exp2())
+    }
+    /// Attempt to write a LilyPond file that shows the mapped notes from
+    /// the first to the last note of a keyboard mapping, each as the nearest
+    /// pitch in twelve-tone equal temperament with A4 at 440 Hz, marked with
+    /// its deviation from that pitch in cents.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the mapping's reference note is not mapped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::midi_note::MidiNote;
+    /// use lilypond::tuning::scala::{KeyboardMapping, ScalaScale};
+    /// use lilypond::NoteNameLanguage;
+    ///
+    /// let scale = ScalaScale::new("Quarter tones", vec![50.0, 100.0]).unwrap();
+    /// // map A4 and the next key to the first two degrees
+    /// let mapping = KeyboardMapping {
+    ///     first: MidiNote::new(69).unwrap(),
+    ///     last: MidiNote::new(70).unwrap(),
+    ///     middle: MidiNote::new(69).unwrap(),
+    ///     ..KeyboardMapping::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     scale.to_lilypond(&mapping, NoteNameLanguage::English).unwrap(),
+    ///     "\\language \"english\"\n\n\
+    ///      \\header { title = \"Quarter tones\" }\n\n\
+    ///      { \\cadenzaOn a'4^\\markup { \"+0\" } bf'4^\\markup { \"-50\" } }\n"
+    /// );
+    /// ```
+    pub fn to_lilypond(
+        &self,
+        mapping: &KeyboardMapping,
+        language: NoteNameLanguage,
+    ) -> Result<String, String> {
+        let tuning = Tuning::default();
+        let mut notes = vec![String::from("\\cadenzaOn")];
+        for note in mapping.first.get_note()..=mapping.last.get_note() {
+            let note = MidiNote::new(note)?;
+            if mapping.degree(&note).is_none() {
+                continue;
+            }
+            let (pitch, cents) = tuning.nearest_pitch(self.frequency(&note, mapping)?)?;
+            let mut note = Note::new(pitch.note_name);
+            note.pitch = pitch;
+            notes.push(format!(
+                "{}^\\markup {{ \"{:+}\" }}",
+                language.lilypond_from_note(&note),
+                cents.round() as i64
+            ));
+        }
+        Ok(format!(
+            "\\language \"{}\"\n\n\\header {{ title = \"{}\" }}\n\n{{ {} }}\n",
+            language,
+            self.description.replace('\\', "\\\\").replace('"', "\\\""),
+            notes.join(" ")
+        ))
+    }
+}
+
+/// A keyboard mapping read from a Scala `.kbm` file, which maps a range of
+/// MIDI notes to the degrees of a scale.
+///
+/// The note `middle` is mapped to the first degree of the scale, and the
+/// notes above and below it follow `mapping`, which repeats every
+/// `mapping.len()` notes, moving `octave_degree` degrees each time. An
+/// empty `mapping` maps every note to the next degree.
+#[derive(Debug, PartialEq, Clone)]
+pub struct KeyboardMapping {
+    /// The lowest note to map.
+    pub first: MidiNote,
+    /// The highest note to map.
+    pub last: MidiNote,
+    /// The note mapped to the first degree of the scale.
+    pub middle: MidiNote,
+    /// The note whose frequency is given.
+    pub reference: MidiNote,
+    /// The frequency of the reference note in hertz.
+    pub frequency: f64,
+    /// The degree of the scale that each repetition of the mapping moves by.
+    pub octave_degree: i32,
+    /// The degree of the scale that each note from `middle` on is mapped to,
+    /// or `None` if it is not mapped.
+    pub mapping: Vec<Option<i32>>,
+}
+
+impl Default for KeyboardMapping {
+    /// Map every MIDI note to the next degree, with middle C as the first
+    /// degree and A4 at 440 Hz.
+    fn default() -> Self {
+        KeyboardMapping {
+            first: MidiNote::new(0).unwrap_or_default(),
+            last: MidiNote::new(127).unwrap_or_default(),
+            middle: MidiNote::new(60).unwrap_or_default(),
+            reference: MidiNote::new(69).unwrap_or_default(),
+            frequency: 440.0,
+            octave_degree: 12,
+            mapping: vec![],
+        }
+    }
+}
+
+impl KeyboardMapping {
+    /// Return the degree of the scale that a MIDI note is mapped to, or
+    /// `None` if it is not mapped or outside of the mapped range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::midi_note::MidiNote;
+    /// use lilypond::tuning::scala::KeyboardMapping;
+    ///
+    /// let mapping = KeyboardMapping {
+    ///     octave_degree: 7,
+    ///     mapping: vec![Some(0), None, Some(1), None, Some(2), Some(3), None, Some(4), None, Some(5), None, Some(6)],
+    ///     ..KeyboardMapping::default()
+    /// };
+    /// assert_eq!(mapping.degree(&MidiNote::new(57).unwrap()), Some(-2));
+    /// assert_eq!(mapping.degree(&MidiNote::new(62).unwrap()), Some(1));
+    /// assert_eq!(mapping.degree(&MidiNote::new(73).unwrap()), None);
+    /// ```
+    pub fn degree(&self, note: &MidiNote) -> Option<i32> {
+        if note < &self.first || note > &self.last {
+            return None;
+        }
+        self.map(note)
+    }
+    /// Return the degree that a note is mapped to, ignoring the mapped range.
+    fn map(&self, note: &MidiNote) -> Option<i32> {
+        let offset = i32::from(note.get_note()) - i32::from(self.middle.get_note());
+        if self.mapping.is_empty() {
+            return Some(offset);
+        }
+        let size = self.mapping.len() as i32;
+        self.mapping[offset.rem_euclid(size) as usize]
+            .map(|degree| offset.div_euclid(size) * self.octave_degree + degree)
+    }
+}
+
+/// Attempt to read a scale from the contents of a Scala `.scl` file.
+///
+/// After comment lines starting with `!`, a `.scl` file has a description
+/// line, the number of pitches, and a line for each pitch. A pitch with a
+/// period is in cents, like `701.955`, and any other pitch is a ratio, like
+/// `3/2`, or a whole number, like `2`. Anything after a pitch on its line is
+/// ignored.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if the file is missing its description or number
+/// of pitches, if a pitch is invalid, or if there are fewer pitches than
+/// its number.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::tuning::scala::from_scl;
+///
+/// let scale = from_scl("! pythagorean.scl\n!\nPythagorean pentatonic\n 5\n!\n 9/8\n 81/64\n 3/2\n 27/16\n 2/1\n").unwrap();
+///
+/// assert_eq!(scale.description(), "Pythagorean pentatonic");
+/// assert_eq!(scale.len(), 5);
+/// assert_eq!(scale.period(), 1200.0);
+/// ```
+pub fn from_scl(input: &str) -> Result<ScalaScale, String> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.starts_with('!'));
+    let description = lines
+        .next()
+        .map(|(_, line)| line.trim())
+        .ok_or_else(|| String::from("Expected a description in a .scl file."))?;
+    let (number, count) = lines
+        .next()
+        .ok_or_else(|| String::from("Expected the number of pitches in a .scl file."))?;
+    let count: usize = count.trim().parse().map_err(|_| {
+        format!(
+            "Invalid number of pitches '{}' in line {}.",
+            count.trim(),
+            number + 1
+        )
+    })?;
+    let mut pitches = vec![];
+    for (number, line) in lines.take(count) {
+        let pitch = line.split_whitespace().next().unwrap_or("");
+        pitches.push(
+            read_pitch(pitch).map_err(|e| format!("{} '{}' in line {}.", e, pitch, number + 1))?,
+        );
+    }
+    if pitches.len() < count {
+        return Err(format!(
+            "Expected {} pitches in a .scl file, found {}.",
+            count,
+            pitches.len()
+        ));
+    }
+    ScalaScale::new(description, pitches)
+}
+
+/// Attempt to read a keyboard mapping from the contents of a Scala `.kbm`
+/// file.
+///
+/// After comment lines starting with `!`, a `.kbm` file has a line for each
+/// of the size of the mapping, the first, last and middle notes, the
+/// reference note and its frequency, and the octave degree, followed by a
+/// line for each note of the mapping, with a degree of the scale or `x` for
+/// a note that is not mapped. Notes missing from the end of the mapping are
+/// not mapped.
+///
+/// # Errors
+///
+/// Returns `Err(String)` if a line is missing or invalid, or if there are
+/// more notes in the mapping than its size.
+///
+/// # Examples
+///
+/// ```rust
+/// use lilypond::tuning::scala::from_kbm;
+///
+/// let mapping = from_kbm("! 415.kbm\n0\n0\n127\n60\n69\n415.0\n12\n").unwrap();
+///
+/// assert_eq!(mapping.frequency, 415.0);
+/// assert!(mapping.mapping.is_empty());
+/// ```
+pub fn from_kbm(input: &str) -> Result<KeyboardMapping, String> {
+    let lines: Vec<(usize, &str)> = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.starts_with('!'))
+        .map(|(number, line)| (number + 1, line.split_whitespace().next().unwrap_or("")))
+        .collect();
+    let field = |index: usize, name: &str| {
+        lines
+            .get(index)
+            .copied()
+            .ok_or_else(|| format!("Expected the {} in a .kbm file.", name))
+    };
+    let invalid = |name: &str, (number, value): (usize, &str)| {
+        format!("Invalid {} '{}' in line {}.", name, value, number)
+    };
+    let note = |index: usize, name: &str| {
+        let line = field(index, name)?;
+        line.1
+            .parse()
+            .ok()
+            .filter(|note| *note <= 127)
+            .and_then(|note| MidiNote::new(note).ok())
+            .ok_or_else(|| invalid(name, line))
+    };
+    let line = field(0, "map size")?;
+    let size: usize = line.1.parse().map_err(|_| invalid("map size", line))?;
+    let first = note(1, "first note")?;
+    let last = note(2, "last note")?;
+    let middle = note(3, "middle note")?;
+    let reference = note(4, "reference note")?;
+    let line = field(5, "reference frequency")?;
+    let frequency = line
+        .1
+        .parse::<f64>()
+        .ok()
+        .filter(|frequency| frequency.is_finite() && *frequency > 0.0)
+        .ok_or_else(|| invalid("reference frequency", line))?;
+    let line = field(6, "octave degree")?;
+    let octave_degree = line.1.parse().map_err(|_| invalid("octave degree", line))?;
+    let mut mapping = vec![];
+    for line in lines.iter().skip(7).take(size) {
+        mapping.push(match line.1 {
+            "x" => None,
+            degree => Some(degree.parse().map_err(|_| invalid("degree", *line))?),
+        });
+    }
+    mapping.resize(size, None);
+    if let Some((number, _)) = lines
+        .iter()
+        .skip(7 + size)
+        .find(|(_, line)| !line.is_empty())
+    {
+        return Err(format!(
+            "Expected {} notes in a .kbm file, found more in line {}.",
+            size, number
+        ));
+    }
+    Ok(KeyboardMapping {
+        first,
+        last,
+        middle,
+        reference,
+        frequency,
+        octave_degree,
+        mapping,
+    })
+}
+
+/// Read a pitch in cents, like `701.955`, or as a ratio, like `3/2` or `2`.
+fn read_pitch(pitch: &str) -> Result<f64, &'static str> {
+    if pitch.contains('.') {
+        return pitch
+            .parse::<f64>()
+            .ok()
+            .filter(|cents| cents.is_finite())
+            .ok_or("Invalid cents");
+    }
+    let (numerator, denominator) = match pitch.split_once('/') {
+        Some((numerator, denominator)) => (numerator, denominator),
+        None => (pitch, "1"),
+    };
+    match (numerator.parse::<u64>(), denominator.parse::<u64>()) {
+        (Ok(numerator), Ok(denominator)) if numerator > 0 && denominator > 0 => {
+            Ok(1200.0 * (numerator as f64 / denominator as f64).log2())
+        }
+        _ => Err("Invalid ratio"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::tuning::scala::*;
+    #[test]
+    fn test_from_scl() {
+        let scale = from_scl(include_str!("../../tests/fixtures/werckmeister.scl")).unwrap();
+        assert_eq!(scale.description(), "Werckmeister III, well temperament");
+        assert_eq!(scale.len(), 12);
+        assert!((scale.cents(7) - 696.09).abs() < 0.01);
+        assert!((scale.cents(-5) - -503.91).abs() < 0.01);
+        assert_eq!(scale.period(), 1200.0);
+    }
+    #[test]
+    fn test_read_pitch() {
+        assert_eq!(read_pitch("100.0"), Ok(100.0));
+        assert_eq!(read_pitch("-5."), Ok(-5.0));
+        assert_eq!(read_pitch("2"), Ok(1200.0));
+        assert!((read_pitch("3/2").unwrap() - 701.955).abs() < 0.001);
+        assert_eq!(read_pitch("0/1"), Err("Invalid ratio"));
+        assert_eq!(read_pitch("-3/2"), Err("Invalid ratio"));
+        assert_eq!(read_pitch("a.b"), Err("Invalid cents"));
+    }
+    #[test]
+    fn test_from_kbm() {
+        let mapping = from_kbm(include_str!("../../tests/fixtures/white_keys.kbm")).unwrap();
+        assert_eq!(mapping.middle, MidiNote::new(60).unwrap());
+        assert_eq!(mapping.reference, MidiNote::new(69).unwrap());
+        assert_eq!(mapping.frequency, 440.0);
+        assert_eq!(mapping.octave_degree, 7);
+        assert_eq!(mapping.mapping.len(), 12);
+        assert_eq!(mapping.mapping[1], None);
+        assert_eq!(mapping.mapping[11], Some(6));
+        let mapping = from_kbm("12\n21\n108\n60\n69\n440\n12\n0\nx\n").unwrap();
+        assert_eq!(mapping.mapping[..3], [Some(0), None, None]);
+        assert_eq!(mapping.degree(&MidiNote::new(20).unwrap()), None);
+    }
+    #[test]
+    fn test_frequency() {
+        let scale = from_scl(include_str!("../../tests/fixtures/just_major.scl")).unwrap();
+        let mapping = from_kbm(include_str!("../../tests/fixtures/white_keys.kbm")).unwrap();
+        let frequency = |note| scale.frequency(&MidiNote::new(note).unwrap(), &mapping);
+        assert!((frequency(60).unwrap() - 264.0).abs() < 1e-9);
+        assert!((frequency(64).unwrap() - 330.0).abs() < 1e-9);
+        assert!((frequency(47).unwrap() - 123.75).abs() < 1e-9);
+        assert!((frequency(72).unwrap() - 528.0).abs() < 1e-9);
+        assert_eq!(
+            frequency(61),
+            Err(String::from("MIDI note 61 is not mapped."))
+        );
+        let mapping = KeyboardMapping {
+            reference: MidiNote::new(70).unwrap(),
+            ..mapping.clone()
+        };
+        assert_eq!(
+            scale.frequency(&MidiNote::new(60).unwrap(), &mapping),
+            Err(String::from("Reference MIDI note 70 is not mapped."))
+        );
+    }
+    #[test]
+    fn test_to_lilypond() {
+        let scale = from_scl(include_str!("../../tests/fixtures/werckmeister.scl")).unwrap();
+        let mapping = KeyboardMapping {
+            first: MidiNote::new(60).unwrap(),
+            last: MidiNote::new(72).unwrap(),
+            ..KeyboardMapping::default()
+        };
+        assert_eq!(
+            scale
+                .to_lilypond(&mapping, NoteNameLanguage::English)
+                .unwrap(),
+            "\\language \"english\"\n\n\
+             \\header { title = \"Werckmeister III, well temperament\" }\n\n\
+             { \\cadenzaOn c'4^\\markup { \"+12\" } cs'4^\\markup { \"+2\" } \
+             d'4^\\markup { \"+4\" } ef'4^\\markup { \"+6\" } e'4^\\markup { \"+2\" } \
+             f'4^\\markup { \"+10\" } fs'4^\\markup { \"+0\" } g'4^\\markup { \"+8\" } \
+             af'4^\\markup { \"+4\" } a'4^\\markup { \"+0\" } bf'4^\\markup { \"+8\" } \
+             b'4^\\markup { \"+4\" } c''4^\\markup { \"+12\" } }\n"
+        );
+        let scale = ScalaScale::new("\"Quoted\" \\ scale", vec![1200.0]).unwrap();
+        let mapping = KeyboardMapping {
+            first: MidiNote::new(69).unwrap(),
+            last: MidiNote::new(69).unwrap(),
+            ..KeyboardMapping::default()
+        };
+        assert_eq!(
+            scale
+                .to_lilypond(&mapping, NoteNameLanguage::Deutsch)
+                .unwrap(),
+            "\\language \"deutsch\"\n\n\
+             \\header { title = \"\\\"Quoted\\\" \\\\ scale\" }\n\n\
+             { \\cadenzaOn a'4^\\markup { \"+0\" } }\n"
+        );
+    }
+    #[test]
+    fn test_from_kbm_errors() {
+        assert_eq!(
+            from_kbm("! empty.kbm\n"),
+            Err(String::from("Expected the map size in a .kbm file."))
+        );
+        assert_eq!(
+            from_kbm("0\n0\n127\n60\n69\n"),
+            Err(String::from(
+                "Expected the reference frequency in a .kbm file."
+            ))
+        );
+        assert_eq!(
+            from_kbm("0\n0\n128\n60\n69\n440.0\n12\n"),
+            Err(String::from("Invalid last note '128' in line 3."))
+        );
+        assert_eq!(
+            from_kbm("0\n0\n127\n60\n69\n-440.0\n12\n"),
+            Err(String::from(
+                "Invalid reference frequency '-440.0' in line 6."
+            ))
+        );
+        assert_eq!(
+            from_kbm("2\n0\n127\n60\n69\n440.0\n12\n0\n!\ny\n"),
+            Err(String::from("Invalid degree 'y' in line 10."))
+        );
+        assert_eq!(
+            from_kbm("1\n0\n127\n60\n69\n440.0\n12\n0\n1\n"),
+            Err(String::from(
+                "Expected 1 notes in a .kbm file, found more in line 9."
+            ))
+        );
+    }
+    #[test]
+    fn test_from_scl_errors() {
+        assert_eq!(
+            from_scl("! empty.scl\n"),
+            Err(String::from("Expected a description in a .scl file."))
+        );
+        assert_eq!(
+            from_scl("Scale\nfive\n"),
+            Err(String::from("Invalid number of pitches 'five' in line 2."))
+        );
+        assert_eq!(
+            from_scl("Scale\n2\n3/2\n"),
+            Err(String::from("Expected 2 pitches in a .scl file, found 1."))
+        );
+        assert_eq!(
+            from_scl("Scale\n2\n3:2\n2/1\n"),
+            Err(String::from("Invalid ratio '3:2' in line 3."))
+        );
+        assert_eq!(
+            from_scl("Scale\n1\n-1200.0\n"),
+            Err(String::from("Invalid period of -1200 cents."))
+        );
+    }
+}
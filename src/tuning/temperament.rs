@@ -0,0 +1,462 @@
+//! Conversion of pitches to and from frequencies in hertz.
+
+use std::convert::TryFrom;
+
+use num_rational::Rational32;
+
+use crate::midi::midi_note::MidiNote;
+use crate::notation::pitch::{Alteration, NoteName, Octave, Pitch};
+use crate::tuning::scala::ScalaScale;
+
+/// The ratio to the tonic of each semitone above it in 5-limit just
+/// intonation.
+const JUST: [(u8, u8); 12] = [
+    (1, 1),
+    (16, 15),
+    (9, 8),
+    (6, 5),
+    (5, 4),
+    (4, 3),
+    (45, 32),
+    (3, 2),
+    (8, 5),
+    (5, 3),
+    (9, 5),
+    (15, 8),
+];
+
+/// The cents above C of each semitone in Werckmeister III.
+const WERCKMEISTER: [f64; 12] = [
+    0.0, 90.225, 192.180, 294.135, 390.225, 498.045, 588.270, 696.090, 792.180, 888.270, 996.090,
+    1092.180,
+];
+
+/// A system of tuning the notes of an octave.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Temperament {
+    /// Twelve-tone equal temperament, in which every semitone is 100 cents.
+    Equal,
+    /// Five-limit just intonation, with pure intervals above a tonic.
+    Just(Pitch),
+    /// Pythagorean tuning, in which every fifth is pure.
+    Pythagorean,
+    /// Meantone temperament, in which every fifth is narrowed by a fraction
+    /// of a syntonic comma, e.g. `1/4` for quarter-comma meantone.
+    Meantone(Rational32),
+    /// Werckmeister III, a well temperament in which the fifths C--G, G--D,
+    /// D--A and B--F sharp are narrowed by a quarter of a Pythagorean comma.
+    Werckmeister,
+    /// A Scala scale, whose first degree is a tonic and whose every further
+    /// degree is a semitone higher. MIDI notes can be mapped to the degrees
+    /// in other ways with a
+    /// [`KeyboardMapping`](crate::tuning::scala::KeyboardMapping).
+    Scala(ScalaScale, Pitch),
+}
+
+impl Default for Temperament {
+    /// Default to twelve-tone equal temperament.
+    fn default() -> Self {
+        Temperament::Equal
+    }
+}
+
+/// A tuning of pitches to frequencies: a temperament, and the frequency of
+/// A4 that the other pitches are tuned relative to.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tuning {
+    reference: f64,
+    temperament: Temperament,
+}
+
+impl Default for Tuning {
+    /// Default to twelve-tone equal temperament with A4 at 440 Hz.
+    fn default() -> Self {
+        Tuning {
+            reference: 440.0,
+            temperament: Temperament::Equal,
+        }
+    }
+}
+
+impl Tuning {
+    /// Attempt to construct a tuning with the frequency of A4 in hertz and a
+    /// temperament.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the reference frequency is not positive, or
+    /// if the tonic of the temperament is a rest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::tuning::temperament::{Temperament, Tuning};
+    ///
+    /// let baroque = Tuning::new(415.0, Temperament::Werckmeister).unwrap();
+    /// assert_eq!(baroque.reference(), 415.0);
+    /// assert_eq!(
+    ///     Tuning::new(0.0, Temperament::Equal),
+    ///     Err(String::from("Invalid frequency 0."))
+    /// );
+    /// ```
+    pub fn new(reference: f64, temperament: Temperament) -> Result<Tuning, String> {
+        check_frequency(reference)?;
+        if let Temperament::Just(tonic) | Temperament::Scala(_, tonic) = &temperament {
+            if tonic.note_name == NoteName::None {
+                return Err(String::from("Expected a tonic that is not a rest."));
+            }
+        }
+        Ok(Tuning {
+            reference,
+            temperament,
+        })
+    }
+    /// Return the frequency of A4 in hertz.
+    pub fn reference(&self) -> f64 {
+        self.reference
+    }
+    /// Return the temperament.
+    pub fn temperament(&self) -> &Temperament {
+        &self.temperament
+    }
+    /// Return the number of cents above C-1 of a pitch with a number of
+    /// semitones above C-1 and a position on the line of fifths, before
+    /// tuning to the reference.
+    fn cents(&self, semitones: Alteration, fifths: i32) -> f64 {
+        let semitones = f64::from(*semitones.numer()) / f64::from(*semitones.denom());
+        match &self.temperament {
+            Temperament::Equal => 100.0 * semitones,
+            // each fifth up from C moves the pitch by the difference
+            // between a tempered fifth and an equal one
+            Temperament::Pythagorean => {
+                100.0 * semitones + f64::from(fifths) * (fifth(0.0) - 700.0)
+            }
+            Temperament::Meantone(fraction) => {
+                let fraction = f64::from(*fraction.numer()) / f64::from(*fraction.denom());
+                100.0 * semitones + f64::from(fifths) * (fifth(fraction) - 700.0)
+            }
+            Temperament::Just(tonic) => {
+                let just = JUST.map(|(numerator, denominator)| {
+                    1200.0 * (f64::from(numerator) / f64::from(denominator)).log2()
+                });
+                steps(tonic_semitones(tonic), semitones, |degree| {
+                    f64::from(degree.div_euclid(12)) * 1200.0 + just[degree.rem_euclid(12) as usize]
+                })
+            }
+            Temperament::Werckmeister => steps(0, semitones, |degree| {
+                f64::from(degree.div_euclid(12)) * 1200.0
+                    + WERCKMEISTER[degree.rem_euclid(12) as usize]
+            }),
+            Temperament::Scala(scale, tonic) => {
+                steps(tonic_semitones(tonic), semitones, |degree| {
+                    scale.cents(degree)
+                })
+            }
+        }
+    }
+    /// Return the frequency in hertz of a pitch with a number of semitones
+    /// above C-1 and a position on the line of fifths.
+    fn tune(&self, semitones: Alteration, fifths: i32) -> f64 {
+        let a4 = self.cents(Alteration::from_integer(69), 3);
+        self.reference * ((self.cents(semitones, fifths) - a4) / 1200.0).exp2()
+    }
+    /// Attempt to return the frequency of a pitch in hertz.
+    ///
+    /// Pitches are tuned by their spelling in Pythagorean tuning and
+    /// meantone temperament, so that e.g. G sharp and A flat differ, and by
+    /// the number of semitones above the tonic in the others. A microtonal
+    /// alteration moves the pitch that fraction of the way to the next
+    /// semitone.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` for rests, which have no pitch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::notation::pitch::Pitch;
+    /// use lilypond::tuning::temperament::{Temperament, Tuning};
+    ///
+    /// let a4: Pitch = "A4".parse().unwrap();
+    /// assert_eq!(Tuning::default().frequency(&a4), Ok(440.0));
+    ///
+    /// let just = Tuning::new(440.0, Temperament::Just("A2".parse().unwrap())).unwrap();
+    /// let e5: Pitch = "E5".parse().unwrap();
+    /// assert!((just.frequency(&e5).unwrap() - 660.0).abs() < 0.01);
+    /// ```
+    pub fn frequency(&self, pitch: &Pitch) -> Result<f64, String> {
+        Ok(self.tune(pitch.semitones()?, line_of_fifths(pitch)?))
+    }
+    /// Return the frequency of a MIDI note in hertz, spelling it like the
+    /// [`Pitch`] it converts to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::midi::midi_note::MidiNote;
+    /// use lilypond::tuning::temperament::Tuning;
+    ///
+    /// let tuning = Tuning::default();
+    /// let frequency = tuning.midi_frequency(&MidiNote::new(60).unwrap());
+    /// assert!((frequency - 261.626).abs() < 0.001);
+    /// ```
+    pub fn midi_frequency(&self, note: &MidiNote) -> f64 {
+        let semitones = i32::from(note.get_note());
+        // C, C sharp, D, E flat, E, F, F sharp, G, A flat, A, B flat and B
+        let fifths = [0, 7, 2, -3, 4, -1, 6, 1, -4, 3, -2, 5][semitones.rem_euclid(12) as usize];
+        self.tune(Alteration::from_integer(semitones), fifths)
+    }
+    /// Attempt to find the pitch from C0 to B9 whose frequency is nearest to
+    /// a frequency in hertz, and return it with the deviation of the
+    /// frequency from it in cents. Pitches are spelled like
+    /// [`MidiNote`]s convert to them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the frequency is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::tuning::temperament::Tuning;
+    ///
+    /// let (pitch, cents) = Tuning::default().nearest_pitch(450.0).unwrap();
+    /// assert_eq!(pitch.to_string(), "A4");
+    /// assert!((cents - 38.906).abs() < 0.001);
+    /// ```
+    pub fn nearest_pitch(&self, frequency: f64) -> Result<(Pitch, f64), String> {
+        let (note, cents) = self.nearest(frequency, 12..=131)?;
+        Ok((Pitch::try_from(&note)?, cents))
+    }
+    /// Attempt to find the MIDI note from 0 to 127 whose frequency is
+    /// nearest to a frequency in hertz, and return it with the deviation of
+    /// the frequency from it in cents.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(String)` if the frequency is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lilypond::tuning::temperament::Tuning;
+    ///
+    /// let (note, cents) = Tuning::default().nearest_midi_note(255.0).unwrap();
+    /// assert_eq!(note.get_note(), 60);
+    /// assert!((cents - -44.408).abs() < 0.001);
+    /// ```
+    pub fn nearest_midi_note(&self, frequency: f64) -> Result<(MidiNote, f64), String> {
+        self.nearest(frequency, 0..=127)
+    }
+    /// Find the MIDI note in a range whose frequency is nearest to a
+    /// frequency, with the deviation of the frequency from it in cents.
+    fn nearest(
+        &self,
+        frequency: f64,
+        notes: std::ops::RangeInclusive<i16>,
+    ) -> Result<(MidiNote, f64), String> {
+        check_frequency(frequency)?;
+        let mut nearest = None;
+        for note in notes {
+            let note = MidiNote::new(note)?;
+            let cents = 1200.0 * (frequency / self.midi_frequency(&note)).log2();
+            match nearest {
+                Some((_, nearest)) if f64::abs(nearest) <= cents.abs() => {}
+                _ => nearest = Some((note, cents)),
+            }
+        }
+        nearest.ok_or_else(|| format!("Invalid frequency {}.", frequency))
+    }
+}
+
+fn check_frequency(frequency: f64) -> Result<(), String> {
+    if frequency.is_finite() && frequency > 0.0 {
+        Ok(())
+    } else {
+        Err(format!("Invalid frequency {}.", frequency))
+    }
+}
+
+/// Return the size in cents of a fifth narrowed by a fraction of a
+/// syntonic comma.
+fn fifth(fraction: f64) -> f64 {
+    1200.0 * (1.5f64.log2() - fraction * (81.0f64 / 80.0).log2())
+}
+
+/// Return the number of semitones above C of the tonic's pitch class.
+fn tonic_semitones(tonic: &Pitch) -> i32 {
+    let mut tonic = tonic.clone();
+    tonic.octave(Octave::S0);
+    tonic
+        .semitones()
+        .map(|semitones| semitones.floor().to_integer() - 12)
+        .unwrap_or(0)
+}
+
+/// Return the cents above C-1 of a pitch a number of semitones above C-1,
+/// where each semitone above the tonic is a degree with the cents above the
+/// tonic given by `cents`. A fraction of a semitone moves the pitch that
+/// fraction of the way to the next degree.
+fn steps(tonic: i32, semitones: f64, cents: impl Fn(i32) -> f64) -> f64 {
+    let steps = semitones - f64::from(tonic);
+    let degree = steps.floor();
+    let lower = cents(degree as i32);
+    let upper = cents(degree as i32 + 1);
+    f64::from(tonic) * 100.0 + lower + (steps - degree) * (upper - lower)
+}
+
+/// Return the position of a pitch on the line of fifths, counting fifths up
+/// from C, e.g. `2` for D or `-3` for E flat. Microtonal alterations are
+/// truncated toward the natural note.
+fn line_of_fifths(pitch: &Pitch) -> Result<i32, String> {
+    let natural = match pitch.note_name {
+        NoteName::F => -1,
+        NoteName::C => 0,
+        NoteName::G => 1,
+        NoteName::D => 2,
+        NoteName::A => 3,
+        NoteName::E => 4,
+        NoteName::B => 5,
+        NoteName::None => return Err(String::from("Rests have no pitch.")),
+    };
+    Ok(natural + 7 * pitch.accidental.alteration().trunc().to_integer())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::tuning::temperament::*;
+    fn parse(name: &str) -> Pitch {
+        name.parse().unwrap()
+    }
+    fn assert_near(frequency: f64, expected: f64) {
+        assert!(
+            (frequency - expected).abs() < 0.001,
+            "{} is not {}",
+            frequency,
+            expected
+        );
+    }
+    #[test]
+    fn test_equal() {
+        let tuning = Tuning::default();
+        assert_near(tuning.frequency(&parse("A5")).unwrap(), 880.0);
+        assert_near(tuning.frequency(&parse("C4")).unwrap(), 261.626);
+        assert_near(tuning.frequency(&parse("A+4")).unwrap(), 452.893);
+        let tuning = Tuning::new(415.0, Temperament::Equal).unwrap();
+        assert_near(tuning.frequency(&parse("A3")).unwrap(), 207.5);
+        assert_near(tuning.midi_frequency(&MidiNote::new(0).unwrap()), 7.711);
+        assert_eq!(
+            tuning.frequency(&"r".parse().unwrap()),
+            Err(String::from("Rests have no pitch."))
+        );
+    }
+    #[test]
+    fn test_just() {
+        let tuning = Tuning::new(440.0, Temperament::Just(parse("C4"))).unwrap();
+        let c4 = tuning.frequency(&parse("C4")).unwrap();
+        // A is a just major sixth above the tonic C
+        assert_near(c4, 264.0);
+        assert_near(tuning.frequency(&parse("E4")).unwrap(), c4 * 5.0 / 4.0);
+        assert_near(tuning.frequency(&parse("G3")).unwrap(), c4 * 3.0 / 4.0);
+        assert_near(tuning.frequency(&parse("Bb5")).unwrap(), c4 * 18.0 / 5.0);
+        // the spelling is ignored
+        assert_eq!(
+            tuning.frequency(&parse("A#4")),
+            tuning.frequency(&parse("Bb4"))
+        );
+    }
+    #[test]
+    fn test_pythagorean() {
+        let tuning = Tuning::new(440.0, Temperament::Pythagorean).unwrap();
+        assert_near(tuning.frequency(&parse("E5")).unwrap(), 660.0);
+        assert_near(tuning.frequency(&parse("D4")).unwrap(), 293.333);
+        // G sharp is a Pythagorean comma higher than A flat
+        let comma = 1200.0
+            * (tuning.frequency(&parse("G#4")).unwrap() / tuning.frequency(&parse("Ab4")).unwrap())
+                .log2();
+        assert_near(comma, 23.460);
+    }
+    #[test]
+    fn test_meantone() {
+        let tuning = Tuning::new(440.0, Temperament::Meantone(Rational32::new(1, 4))).unwrap();
+        let c4 = tuning.frequency(&parse("C4")).unwrap();
+        // quarter-comma meantone has pure major thirds
+        assert_near(tuning.frequency(&parse("E4")).unwrap(), c4 * 5.0 / 4.0);
+        assert_near(tuning.frequency(&parse("G#4")).unwrap(), c4 * 25.0 / 16.0);
+        let pythagorean = Tuning::new(440.0, Temperament::Meantone(Rational32::from_integer(0)));
+        assert_near(
+            pythagorean.unwrap().frequency(&parse("Eb3")).unwrap(),
+            Tuning::new(440.0, Temperament::Pythagorean)
+                .unwrap()
+                .frequency(&parse("Eb3"))
+                .unwrap(),
+        );
+    }
+    #[test]
+    fn test_werckmeister() {
+        let tuning = Tuning::new(440.0, Temperament::Werckmeister).unwrap();
+        let c4 = tuning.frequency(&parse("C4")).unwrap();
+        assert_near(
+            1200.0 * (tuning.frequency(&parse("G4")).unwrap() / c4).log2(),
+            696.09,
+        );
+        assert_near(
+            1200.0 * (tuning.frequency(&parse("C#5")).unwrap() / c4).log2(),
+            1290.225,
+        );
+        assert_near(tuning.frequency(&parse("A4")).unwrap(), 440.0);
+    }
+    #[test]
+    fn test_scala() {
+        let scale =
+            crate::tuning::scala::from_scl(include_str!("../../tests/fixtures/werckmeister.scl"))
+                .unwrap();
+        let tuning = Tuning::new(440.0, Temperament::Scala(scale, parse("C0"))).unwrap();
+        let werckmeister = Tuning::new(440.0, Temperament::Werckmeister).unwrap();
+        for note in ["C4", "Eb2", "F#7", "B1", "Dd5"] {
+            assert_near(
+                tuning.frequency(&parse(note)).unwrap(),
+                werckmeister.frequency(&parse(note)).unwrap(),
+            );
+        }
+        // five equal steps to the octave, starting on D
+        let scale = ScalaScale::new("5-TET", vec![240.0, 480.0, 720.0, 960.0, 1200.0]).unwrap();
+        let tuning = Tuning::new(440.0, Temperament::Scala(scale, parse("D4"))).unwrap();
+        let d4 = tuning.frequency(&parse("D4")).unwrap();
+        assert_near(tuning.frequency(&parse("G4")).unwrap(), d4 * 2.0);
+        assert_near(
+            tuning.frequency(&parse("D#4")).unwrap(),
+            d4 * (240.0f64 / 1200.0).exp2(),
+        );
+    }
+    #[test]
+    fn test_nearest() {
+        let tuning = Tuning::default();
+        let (pitch, cents) = tuning.nearest_pitch(261.0).unwrap();
+        assert_eq!(pitch.to_string(), "C4");
+        assert_near(cents, -4.144);
+        let (pitch, cents) = tuning.nearest_pitch(5.0).unwrap();
+        assert_eq!(pitch.to_string(), "C0");
+        assert!(cents < -1200.0);
+        let (note, cents) = tuning.nearest_midi_note(8.0).unwrap();
+        assert_eq!(note.get_note(), 0);
+        assert_near(cents, -37.632);
+        let tuning = Tuning::new(440.0, Temperament::Just(parse("A4"))).unwrap();
+        let (pitch, cents) = tuning.nearest_pitch(550.0).unwrap();
+        assert_eq!(pitch.to_string(), "C#5");
+        assert_near(cents, 0.0);
+        assert_eq!(
+            tuning.nearest_pitch(-1.0),
+            Err(String::from("Invalid frequency -1."))
+        );
+    }
+    #[test]
+    fn test_new_errors() {
+        assert!(Tuning::new(f64::NAN, Temperament::Equal).is_err());
+        assert_eq!(
+            Tuning::new(440.0, Temperament::Just("r".parse().unwrap())),
+            Err(String::from("Expected a tonic that is not a rest."))
+        );
+    }
+}